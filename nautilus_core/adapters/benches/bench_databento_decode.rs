@@ -0,0 +1,154 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{path::PathBuf, str::FromStr};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use dbn::decode::{dbn::Decoder, DecodeStream};
+use nautilus_adapters::databento::decode::{
+    decode_options_contract_v1, decode_record, decode_records, parse_currency,
+};
+use nautilus_model::{identifiers::instrument_id::InstrumentId, types::currency::Currency};
+use streaming_iterator::StreamingIterator;
+
+const NUM_RECORDS: usize = 10_000;
+const NUM_DEFINITIONS: usize = 50_000;
+
+fn load_msgs<T>(filename: &str) -> Vec<T>
+where
+    T: dbn::HasRType + Clone + 'static,
+{
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/test_data/databento")
+        .join(filename);
+    let mut dbn_stream = Decoder::from_zstd_file(path).unwrap().decode_stream::<T>();
+
+    let mut msgs = Vec::new();
+    while let Some(msg) = dbn_stream.next() {
+        msgs.push(msg.clone());
+    }
+
+    // Cycle the test data to a more realistic chunk size
+    msgs.iter().cycle().take(NUM_RECORDS).cloned().collect()
+}
+
+fn decode_mbp10_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_mbp10");
+    let msgs = load_msgs::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+    let records: Vec<dbn::RecordRef> = msgs.iter().map(dbn::RecordRef::from).collect();
+    let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+    group.bench_function("decode_record", |b| {
+        b.iter(|| {
+            let mut data = Vec::new();
+            for rec_ref in &records {
+                let (data1, data2) = decode_record(rec_ref, instrument_id, 2, None, false).unwrap();
+                data.extend(data1);
+                data.extend(data2);
+            }
+            black_box(data)
+        });
+    });
+
+    group.bench_function("decode_records", |b| {
+        b.iter(|| {
+            for data in decode_records(records.iter().copied(), instrument_id, 2, None, false) {
+                black_box(data.unwrap());
+            }
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_decode_throughput<T>(c: &mut Criterion, name: &str, filename: &str)
+where
+    T: dbn::HasRType + Clone + 'static,
+{
+    let msgs = load_msgs::<T>(filename);
+    let records: Vec<dbn::RecordRef> = msgs.iter().map(dbn::RecordRef::from).collect();
+    let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+    // Reports records per second, so schemas are comparable for the same batch size
+    let mut group = c.benchmark_group("decode_throughput");
+    group.throughput(Throughput::Elements(records.len() as u64));
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            for rec_ref in &records {
+                black_box(decode_record(rec_ref, instrument_id, 2, None, true).unwrap());
+            }
+        });
+    });
+    group.finish();
+}
+
+fn decode_throughput_bench(c: &mut Criterion) {
+    bench_decode_throughput::<dbn::MboMsg>(c, "mbo", "mbo.dbn.zst");
+    bench_decode_throughput::<dbn::Mbp1Msg>(c, "mbp1", "mbp-1.dbn.zst");
+    bench_decode_throughput::<dbn::Mbp10Msg>(c, "mbp10", "mbp-10.dbn.zst");
+}
+
+fn decode_definitions_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_definitions");
+    let msgs: Vec<dbn::compat::InstrumentDefMsgV1> = load_msgs("definition-opra.dbn.zst")
+        .into_iter()
+        .cycle()
+        .take(NUM_DEFINITIONS)
+        .collect();
+    let instrument_id = InstrumentId::from("SPY   240119P00340000.OPRA");
+
+    group.bench_function("decode_options_contract_v1", |b| {
+        b.iter(|| {
+            for msg in &msgs {
+                black_box(decode_options_contract_v1(msg, instrument_id, 0, None).unwrap());
+            }
+        });
+    });
+
+    group.finish();
+}
+
+fn currency_lookup_bench(c: &mut Criterion) {
+    // Compares the cached currency lookup directly against parsing the currency each time
+    let mut group = c.benchmark_group("currency_lookup");
+    group.throughput(Throughput::Elements(NUM_DEFINITIONS as u64));
+
+    group.bench_function("parse_currency_cached", |b| {
+        b.iter(|| {
+            for _ in 0..NUM_DEFINITIONS {
+                black_box(parse_currency(black_box("USD")).unwrap());
+            }
+        });
+    });
+
+    group.bench_function("currency_from_str_uncached", |b| {
+        b.iter(|| {
+            for _ in 0..NUM_DEFINITIONS {
+                black_box(Currency::from_str(black_box("USD")).unwrap());
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    decode_mbp10_bench,
+    decode_throughput_bench,
+    decode_definitions_bench,
+    currency_lookup_bench
+);
+criterion_main!(benches);
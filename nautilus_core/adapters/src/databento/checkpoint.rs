@@ -0,0 +1,333 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{collections::HashMap, io};
+
+use anyhow::{anyhow, Result};
+use dbn::{
+    compat::InstrumentDefMsgV1,
+    decode::{dbn::RecordDecoder, DecodeRecordRef},
+};
+use nautilus_model::{
+    data::Data,
+    identifiers::{instrument_id::InstrumentId, symbol::Symbol, venue::Venue},
+};
+use serde::{Deserialize, Serialize};
+
+use super::decode::{
+    decode_raw_symbol, decode_record, fixed_bytes_to_str, get_checked, DatabentoDecodeError,
+    EmptySymbolPolicy,
+};
+
+/// Represents the serializable state of a streaming decode, from which decoding
+/// can be resumed without re-reading the records already decoded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodeCheckpoint {
+    /// The DBN version of the records.
+    pub version: u8,
+    /// If the records have an appended `ts_out` field.
+    pub ts_out: bool,
+    /// The venue for instrument IDs resolved from definitions.
+    pub venue: Venue,
+    /// The price precision for decoding.
+    pub price_precision: u8,
+    /// If trades should be included when decoding MBO and MBP-1 records.
+    pub include_trades: bool,
+    /// The byte offset of the next record from the start of the record stream.
+    pub offset: u64,
+    /// The Nautilus instrument IDs keyed by DBN instrument ID.
+    pub instrument_map: HashMap<u32, InstrumentId>,
+    /// The last decoded sequence number keyed by DBN instrument ID.
+    pub sequences: HashMap<u32, u32>,
+}
+
+impl DecodeCheckpoint {
+    /// Creates a new checkpoint at the start of the record stream described by `metadata`.
+    #[must_use]
+    pub fn new(
+        metadata: &dbn::Metadata,
+        venue: Venue,
+        price_precision: u8,
+        include_trades: bool,
+    ) -> Self {
+        Self {
+            version: metadata.version,
+            ts_out: metadata.ts_out,
+            venue,
+            price_precision,
+            include_trades,
+            offset: 0,
+            instrument_map: HashMap::new(),
+            sequences: HashMap::new(),
+        }
+    }
+}
+
+/// Provides a streaming decoder of DBN records into Nautilus data, which tracks
+/// its state in a [`DecodeCheckpoint`].
+///
+/// Instrument definitions are not decoded to data, but update the instrument map
+/// used to resolve the instrument IDs of subsequent data records.
+pub struct CheckpointDecoder<D> {
+    decoder: D,
+    checkpoint: DecodeCheckpoint,
+}
+
+impl<D: DecodeRecordRef> CheckpointDecoder<D> {
+    /// Creates a new decoder reading records from `decoder`, which must be positioned
+    /// at the `offset` of the given `checkpoint`.
+    pub fn new(decoder: D, checkpoint: DecodeCheckpoint) -> Self {
+        Self {
+            decoder,
+            checkpoint,
+        }
+    }
+
+    /// Returns the checkpoint for the records decoded so far.
+    #[must_use]
+    pub fn checkpoint(&self) -> &DecodeCheckpoint {
+        &self.checkpoint
+    }
+
+    /// Decodes the next record, returning `None` at the end of the record stream.
+    pub fn decode_next(
+        &mut self,
+    ) -> Option<Result<(Option<Data>, Option<Data>), DatabentoDecodeError>> {
+        let rec_ref = match self.decoder.decode_record_ref() {
+            Ok(Some(rec_ref)) => rec_ref,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e.into())),
+        };
+        let result = decode_checkpoint_record(&rec_ref, &mut self.checkpoint);
+        self.checkpoint.offset += rec_ref.record_size() as u64;
+
+        Some(result)
+    }
+}
+
+impl<D: DecodeRecordRef> Iterator for CheckpointDecoder<D> {
+    type Item = Result<(Option<Data>, Option<Data>), DatabentoDecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decode_next()
+    }
+}
+
+/// Resumes decoding from the given `checkpoint`, with `reader` positioned at the
+/// checkpoint `offset` of the uncompressed record stream.
+pub fn resume_from<R: io::Read>(
+    reader: R,
+    checkpoint: DecodeCheckpoint,
+) -> Result<CheckpointDecoder<RecordDecoder<R>>, DatabentoDecodeError> {
+    let decoder = RecordDecoder::with_version(
+        reader,
+        checkpoint.version,
+        dbn::VersionUpgradePolicy::AsIs,
+        checkpoint.ts_out,
+    )?;
+
+    Ok(CheckpointDecoder::new(decoder, checkpoint))
+}
+
+fn decode_checkpoint_record(
+    rec_ref: &dbn::RecordRef,
+    checkpoint: &mut DecodeCheckpoint,
+) -> Result<(Option<Data>, Option<Data>), DatabentoDecodeError> {
+    let header = rec_ref.header();
+
+    if rec_ref.rtype()? == dbn::RType::InstrumentDef {
+        let raw_symbol = if checkpoint.version == 1 {
+            let msg = get_checked::<InstrumentDefMsgV1>(rec_ref)?;
+            fixed_bytes_to_str(&msg.raw_symbol)?
+        } else {
+            let msg = get_checked::<dbn::InstrumentDefMsg>(rec_ref)?;
+            fixed_bytes_to_str(&msg.raw_symbol)?
+        };
+        let raw_symbol = decode_raw_symbol(
+            raw_symbol,
+            header.instrument_id,
+            EmptySymbolPolicy::default(),
+        )?;
+        let instrument_id = InstrumentId::new(Symbol { value: raw_symbol }, checkpoint.venue);
+        checkpoint
+            .instrument_map
+            .insert(header.instrument_id, instrument_id);
+        return Ok((None, None));
+    }
+
+    let instrument_id = *checkpoint
+        .instrument_map
+        .get(&header.instrument_id)
+        .ok_or_else(|| {
+            anyhow!(
+                "No instrument ID mapped for DBN instrument ID {}",
+                header.instrument_id
+            )
+        })?;
+
+    let result = decode_record(
+        rec_ref,
+        instrument_id,
+        checkpoint.price_precision,
+        None,
+        checkpoint.include_trades,
+    )?;
+
+    if let Some(sequence) = decode_record_sequence(rec_ref)? {
+        checkpoint.sequences.insert(header.instrument_id, sequence);
+    }
+
+    Ok(result)
+}
+
+fn decode_record_sequence(rec_ref: &dbn::RecordRef) -> Result<Option<u32>, DatabentoDecodeError> {
+    let sequence = match rec_ref.rtype()? {
+        dbn::RType::Mbo => Some(get_checked::<dbn::MboMsg>(rec_ref)?.sequence),
+        dbn::RType::Mbp0 => Some(get_checked::<dbn::TradeMsg>(rec_ref)?.sequence),
+        dbn::RType::Mbp1 => Some(get_checked::<dbn::Mbp1Msg>(rec_ref)?.sequence),
+        dbn::RType::Mbp10 => Some(get_checked::<dbn::Mbp10Msg>(rec_ref)?.sequence),
+        _ => None,
+    };
+
+    Ok(sequence)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use dbn::decode::{dbn::Decoder, DbnMetadata, DecodeStream};
+    use rstest::*;
+    use streaming_iterator::StreamingIterator;
+
+    use super::*;
+
+    fn test_data_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/test_data/databento")
+    }
+
+    fn load_metadata(filename: &str) -> dbn::Metadata {
+        let decoder = Decoder::from_zstd_file(test_data_path().join(filename)).unwrap();
+        decoder.metadata().clone()
+    }
+
+    fn load_records<T>(filename: &str) -> Vec<T>
+    where
+        T: dbn::Record + dbn::HasRType + Clone + 'static,
+    {
+        let decoder = Decoder::from_zstd_file(test_data_path().join(filename)).unwrap();
+        let mut dbn_stream = decoder.decode_stream::<T>();
+        let mut msgs = Vec::new();
+        while let Some(msg) = dbn_stream.next() {
+            msgs.push(msg.clone());
+        }
+        msgs
+    }
+
+    fn to_bytes<T: AsRef<[u8]>>(msgs: &[T]) -> Vec<u8> {
+        msgs.iter().flat_map(|msg| msg.as_ref().to_vec()).collect()
+    }
+
+    fn decode_all(
+        decoder: impl Iterator<Item = Result<(Option<Data>, Option<Data>), DatabentoDecodeError>>,
+    ) -> Vec<String> {
+        decoder
+            .map(|result| format!("{:?}", result.unwrap()))
+            .collect()
+    }
+
+    fn decode_with_checkpoint(bytes: &[u8], checkpoint: DecodeCheckpoint, split: usize) {
+        let single_pass = decode_all(resume_from(bytes, checkpoint.clone()).unwrap());
+
+        let mut decoder = resume_from(bytes, checkpoint).unwrap();
+        let mut combined = decode_all(decoder.by_ref().take(split));
+        let json = serde_json::to_string(decoder.checkpoint()).unwrap();
+        drop(decoder);
+
+        let checkpoint: DecodeCheckpoint = serde_json::from_str(&json).unwrap();
+        let offset = checkpoint.offset as usize;
+        combined.extend(decode_all(
+            resume_from(&bytes[offset..], checkpoint).unwrap(),
+        ));
+
+        assert!(split < single_pass.len());
+        assert_eq!(combined, single_pass);
+    }
+
+    #[rstest]
+    fn test_resume_from_checkpoint_mbo() {
+        let metadata = load_metadata("mbo.dbn.zst");
+        let msgs = load_records::<dbn::MboMsg>("mbo.dbn.zst");
+        let bytes = to_bytes(&msgs);
+        let mut checkpoint = DecodeCheckpoint::new(&metadata, Venue::from("GLBX"), 2, true);
+        checkpoint
+            .instrument_map
+            .insert(msgs[0].hd.instrument_id, InstrumentId::from("ESM4.GLBX"));
+
+        let count = resume_from(bytes.as_slice(), checkpoint.clone())
+            .unwrap()
+            .count();
+
+        decode_with_checkpoint(&bytes, checkpoint, count / 2);
+    }
+
+    #[rstest]
+    fn test_resume_from_checkpoint_uses_instrument_map() {
+        let metadata = load_metadata("trades.dbn.zst");
+        let trades = load_records::<dbn::TradeMsg>("trades.dbn.zst");
+        let trade_instrument_id = trades[0].hd.instrument_id;
+
+        // Prepend a definition for the traded instrument
+        let mut definitions = load_records::<InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        definitions.truncate(1);
+        definitions[0].hd.instrument_id = trade_instrument_id;
+        let mut bytes = to_bytes(&definitions);
+        bytes.extend(to_bytes(&trades));
+
+        let checkpoint = DecodeCheckpoint::new(&metadata, Venue::from("GLBX"), 2, true);
+
+        // Checkpoint after the definition and first trade, so the resumed
+        // trades only decode with the checkpointed instrument map
+        let mut decoder = resume_from(bytes.as_slice(), checkpoint.clone()).unwrap();
+        decoder.by_ref().take(2).for_each(drop);
+        let resumed = decoder.checkpoint().clone();
+        assert_eq!(
+            resumed.instrument_map.get(&trade_instrument_id),
+            Some(&InstrumentId::from("ESM3.GLBX"))
+        );
+        assert!(resumed.sequences.contains_key(&trade_instrument_id));
+
+        decode_with_checkpoint(&bytes, checkpoint, 2);
+    }
+
+    #[rstest]
+    fn test_resume_from_checkpoint_unmapped_instrument() {
+        let metadata = load_metadata("trades.dbn.zst");
+        let bytes = to_bytes(&load_records::<dbn::TradeMsg>("trades.dbn.zst"));
+        let checkpoint = DecodeCheckpoint::new(&metadata, Venue::from("GLBX"), 2, true);
+
+        let mut decoder = resume_from(bytes.as_slice(), checkpoint).unwrap();
+        let err = decoder.next().unwrap().unwrap_err();
+
+        assert!(err.to_string().starts_with("No instrument ID mapped"));
+        assert_eq!(
+            decoder.checkpoint().offset,
+            std::mem::size_of::<dbn::TradeMsg>() as u64
+        );
+    }
+}
@@ -13,9 +13,12 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use databento::historical::DateTimeRange;
 use nautilus_core::time::UnixNanos;
+use nautilus_model::identifiers::venue::Venue;
 use time::OffsetDateTime;
 
 pub const DATABENTO: &str = "DATABENTO";
@@ -32,3 +35,95 @@ pub fn get_date_time_range(start: UnixNanos, end: Option<UnixNanos>) -> Result<D
         )),
     }
 }
+
+/// Returns the exchange timezone for the given `venue`, or `None` if unknown.
+#[must_use]
+pub fn venue_timezone(venue: &Venue) -> Option<Tz> {
+    let tz = match venue.value.as_str() {
+        "GLBX" | "XCHI" | "XCBO" | "C2OX" => Tz::America__Chicago,
+        "XNAS" | "XBOS" | "XPSX" | "BATS" | "BATY" | "EDGA" | "EDGX" | "XNYS" | "XCIS" | "XASE"
+        | "MEMX" | "EPRL" | "FINN" | "FINC" | "FINY" | "AMXO" | "XBOX" | "EMLD" | "EDGO"
+        | "GMNI" | "XISX" | "MCRY" | "XMIO" | "ARCO" | "OPRA" | "MPRL" | "XNDQ" | "XBXO"
+        | "XPHL" | "BATO" | "MXOP" | "IEXG" | "ARCX" | "DBEQ" | "SPHR" => Tz::America__New_York,
+        "IFEU" => Tz::Europe__London,
+        "NDEX" => Tz::Europe__Amsterdam,
+        _ => return None,
+    };
+    Some(tz)
+}
+
+/// Returns the given UNIX timestamp (nanoseconds) as a datetime in the exchange timezone `tz`.
+#[must_use]
+pub fn to_exchange_local(ts: UnixNanos, tz: Tz) -> DateTime<Tz> {
+    Utc.timestamp_nanos(ts as i64).with_timezone(&tz)
+}
+
+/// Returns the UNIX timestamp (nanoseconds) of the session close for the daily bar
+/// opening at `ts_event`, with the `session_close` in exchange local time.
+///
+/// Daily bars open at midnight UTC of the trading date, so the session close is on
+/// that same date in the exchange timezone `tz`.
+pub fn decode_session_close(
+    ts_event: UnixNanos,
+    tz: Tz,
+    session_close: NaiveTime,
+) -> Result<UnixNanos> {
+    let date = Utc.timestamp_nanos(ts_event as i64).date_naive();
+    let close = tz
+        .from_local_datetime(&date.and_time(session_close))
+        .single()
+        .ok_or_else(|| anyhow!("Ambiguous session close {session_close} on {date} for {tz}"))?;
+    let nanos = close
+        .timestamp_nanos_opt()
+        .ok_or_else(|| anyhow!("Session close on {date} out of range"))?;
+    Ok(nanos as UnixNanos)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::datetime::NANOSECONDS_IN_SECOND;
+    use rstest::rstest;
+
+    use super::*;
+
+    const NANOSECONDS_IN_HOUR: u64 = NANOSECONDS_IN_SECOND * 60 * 60;
+
+    #[rstest]
+    #[case("GLBX", Some(Tz::America__Chicago))]
+    #[case("XNAS", Some(Tz::America__New_York))]
+    #[case("IFEU", Some(Tz::Europe__London))]
+    #[case("SIM", None)]
+    fn test_venue_timezone(#[case] venue: &str, #[case] expected: Option<Tz>) {
+        assert_eq!(venue_timezone(&Venue::from(venue)), expected);
+    }
+
+    #[rstest]
+    fn test_to_exchange_local() {
+        let ts = 1_704_232_800_000_000_000; // 2024-01-02T22:00:00Z
+
+        let local = to_exchange_local(ts, Tz::America__Chicago);
+
+        assert_eq!(local.to_rfc3339(), "2024-01-02T16:00:00-06:00");
+    }
+
+    #[rstest]
+    #[case(1_704_153_600_000_000_000, 22)] // 2024-01-02 CST (UTC-6)
+    #[case(1_719_792_000_000_000_000, 21)] // 2024-07-01 CDT (UTC-5)
+    fn test_decode_session_close_vs_utc_close(#[case] ts_event: UnixNanos, #[case] hours: u64) {
+        let tz = venue_timezone(&Venue::from("GLBX")).unwrap();
+        let utc_close = ts_event + 24 * NANOSECONDS_IN_HOUR;
+
+        let local_close =
+            decode_session_close(ts_event, tz, NaiveTime::from_hms_opt(16, 0, 0).unwrap()).unwrap();
+
+        assert_eq!(local_close, ts_event + hours * NANOSECONDS_IN_HOUR);
+        assert!(local_close < utc_close);
+        assert_eq!(
+            to_exchange_local(local_close, tz).time(),
+            NaiveTime::from_hms_opt(16, 0, 0).unwrap()
+        );
+    }
+}
@@ -14,38 +14,58 @@
 // -------------------------------------------------------------------------------------------------
 
 use std::{
-    cmp,
-    ffi::{c_char, CStr},
-    i64,
-    str::FromStr,
+    cell::RefCell, cmp, collections::HashMap, ffi::c_char, i64, mem, slice, str, str::FromStr,
 };
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, Result};
 use databento::dbn::Record;
-use nautilus_core::{datetime::NANOSECONDS_IN_SECOND, time::UnixNanos};
+use indexmap::IndexMap;
+use nautilus_core::{
+    datetime::NANOSECONDS_IN_SECOND,
+    time::{get_atomic_clock_realtime, UnixNanos},
+};
 use nautilus_model::{
     data::{
         bar::{Bar, BarSpecification, BarType},
         delta::OrderBookDelta,
         depth::{OrderBookDepth10, DEPTH10_LEN},
-        order::BookOrder,
+        order::{BookOrder, NULL_ORDER},
         quote::QuoteTick,
         trade::TradeTick,
         Data,
     },
     enums::{
-        AggregationSource, AggressorSide, AssetClass, BarAggregation, BookAction, InstrumentClass,
-        OptionKind, OrderSide, PriceType,
+        AggregationSource, AggressorSide, AssetClass, BarAggregation, BookAction, ExerciseStyle,
+        HaltReason, InstrumentClass, MarketStatus, OptionKind, OrderSide, PriceType,
     },
-    identifiers::{instrument_id::InstrumentId, trade_id::TradeId},
+    identifiers::{instrument_id::InstrumentId, symbol::Symbol, trade_id::TradeId},
     instruments::{
-        equity::Equity, futures_contract::FuturesContract, futures_spread::FuturesSpread,
-        options_contract::OptionsContract, options_spread::OptionsSpread, Instrument,
+        bond::Bond, currency_pair::CurrencyPair, equity::Equity, futures_contract::FuturesContract,
+        futures_spread::FuturesSpread, index::IndexInstrument, options_contract::OptionsContract,
+        options_spread::OptionsSpread, Instrument,
+    },
+    orderbook::{book_mbo::OrderBookMbo, level::Level},
+    types::{
+        currency::Currency,
+        fixed::{check_fixed_precision, FIXED_PRECISION, FIXED_SCALAR},
+        money::Money,
+        price::Price,
+        quantity::Quantity,
     },
-    types::{currency::Currency, fixed::FIXED_SCALAR, price::Price, quantity::Quantity},
 };
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use ustr::Ustr;
 
+use super::{
+    symbology::{venue_from_publisher_id, DatabentoSymbolMap},
+    types::{
+        DatabentoBookAction, DatabentoBookLevel, DatabentoData, DatabentoImbalance,
+        DatabentoInstrumentStatus, DatabentoLatency, DatabentoOrderBookDelta,
+        DatabentoOrderBookDepth, DatabentoStatisticType, DatabentoStatistics, PublisherId,
+        RecordFlags,
+    },
+};
+
 const BAR_SPEC_1S: BarSpecification = BarSpecification {
     step: 1,
     aggregation: BarAggregation::Second,
@@ -66,12 +86,98 @@ const BAR_SPEC_1D: BarSpecification = BarSpecification {
     aggregation: BarAggregation::Day,
     price_type: PriceType::Last,
 };
+// End-of-day bars are a daily aggregation, so share the daily specification
+const BAR_SPEC_EOD: BarSpecification = BAR_SPEC_1D;
+
+/// The DBN `display_factor` representing a multiplier of 1 (fixed-point with 1e-9 units).
+pub const DISPLAY_FACTOR_ONE: i64 = 1_000_000_000;
+
+/// The divisor for DBN OHLCV prices, which are reported at 100 times the fixed-point
+/// scale of other prices (applied after the instrument `display_factor`, if any).
+pub const OHLCV_PRICE_DIVISOR: i64 = 100;
 
 const BAR_CLOSE_ADJUSTMENT_1S: u64 = NANOSECONDS_IN_SECOND;
 const BAR_CLOSE_ADJUSTMENT_1M: u64 = NANOSECONDS_IN_SECOND * 60;
 const BAR_CLOSE_ADJUSTMENT_1H: u64 = NANOSECONDS_IN_SECOND * 60 * 60;
 const BAR_CLOSE_ADJUSTMENT_1D: u64 = NANOSECONDS_IN_SECOND * 60 * 60 * 24;
+// End-of-day bars open at midnight UTC of the trading date, so close a full day
+// later rather than at the exchange session close (which would need a calendar)
+const BAR_CLOSE_ADJUSTMENT_EOD: u64 = BAR_CLOSE_ADJUSTMENT_1D;
+
+const NANOSECONDS_IN_YEAR_ACT365: u64 = NANOSECONDS_IN_SECOND * 60 * 60 * 24 * 365;
+const NANOSECONDS_IN_DAY: u64 = NANOSECONDS_IN_SECOND * 60 * 60 * 24;
+
+const OPRA_DEFAULT_MULTIPLIER: f64 = 100.0;
+
+/// The maximum number of levels per side of a book built by [`MboDepthBuilder`].
+pub const MAX_BOOK_DEPTH: usize = 32;
+
+const DBN_LENGTH_MULTIPLIER: usize = 4;
+
+/// Represents an error decoding DBN records into Nautilus types.
+#[derive(thiserror::Error, Debug)]
+pub enum DatabentoDecodeError {
+    #[error("RType {0:#04x} is not currently supported")]
+    UnsupportedRType(u8),
+    #[error("Invalid `BookAction`, was '{0}'")]
+    InvalidBookAction(char),
+    #[error("Invalid `OptionKind`, was '{0}'")]
+    InvalidOptionKind(char),
+    #[error("Invalid CFI string '{0}'")]
+    InvalidCfi(String),
+    #[error("Unknown currency '{0}'")]
+    UnknownCurrency(String),
+    #[error("Unsupported `instrument_class` '{0}'")]
+    UnsupportedInstrumentClass(char),
+    #[error("Unsupported DBN version {0}, expected version 1 or 2")]
+    UnsupportedVersion(u8),
+    #[error("Crossed quote for {instrument_id}, bid {bid_price} above ask {ask_price}")]
+    CrossedQuote {
+        instrument_id: InstrumentId,
+        bid_price: Price,
+        ask_price: Price,
+    },
+    #[error("Invalid bar for {bar_type}, {reason}")]
+    InvalidBar { bar_type: BarType, reason: String },
+    #[error("Incomplete record, {needed} bytes needed but {available} available")]
+    Incomplete { needed: usize, available: usize },
+    #[error("Invalid record for RType {rtype:#04x} with length {length}")]
+    InvalidRecord { rtype: u8, length: usize },
+    #[error("UTF-8 error: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+    #[error("DBN error: {0}")]
+    Dbn(#[from] dbn::Error),
+    #[error("{context}: {inner}")]
+    Context {
+        context: String,
+        inner: Box<DatabentoDecodeError>,
+    },
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl DatabentoDecodeError {
+    /// Wraps the error with the given `context`, e.g. identifying the record.
+    #[must_use]
+    pub fn context(self, context: String) -> Self {
+        Self::Context {
+            context,
+            inner: Box::new(self),
+        }
+    }
+
+    /// Returns the underlying error, unwrapping any added context.
+    #[must_use]
+    pub fn root(&self) -> &Self {
+        match self {
+            Self::Context { inner, .. } => inner.root(),
+            _ => self,
+        }
+    }
+}
 
+/// Parses the DBN `side` char, which is the single source of truth for both the book
+/// order side and the trade aggressor side ('A' ask, 'B' bid, 'N' none).
 #[must_use]
 pub fn parse_order_side(c: c_char) -> OrderSide {
     match c as u8 as char {
@@ -81,283 +187,1118 @@ pub fn parse_order_side(c: c_char) -> OrderSide {
     }
 }
 
+/// Parses the DBN `side` char as a trade aggressor side.
+///
+/// This is derived from [`parse_order_side`] so the two mappings cannot diverge,
+/// i.e. a trade with a sell side was aggressed by the seller.
 #[must_use]
 pub fn parse_aggressor_side(c: c_char) -> AggressorSide {
+    match parse_order_side(c) {
+        OrderSide::Sell => AggressorSide::Seller,
+        OrderSide::Buy => AggressorSide::Buyer,
+        OrderSide::NoOrderSide => AggressorSide::NoAggressor,
+    }
+}
+
+/// Parses the DBN `side` char as a trade aggressor side, mapping `'N'` to no aggressor
+/// and returning an error for any other unrecognized char.
+pub fn try_parse_aggressor_side(c: c_char) -> Result<AggressorSide, DatabentoDecodeError> {
     match c as u8 as char {
-        'A' => AggressorSide::Seller,
-        'B' => AggressorSide::Buyer,
-        _ => AggressorSide::NoAggressor,
+        'A' => Ok(AggressorSide::Seller),
+        'B' => Ok(AggressorSide::Buyer),
+        'N' => Ok(AggressorSide::NoAggressor),
+        _ => Err(anyhow!("Invalid aggressor side '{}'", c as u8 as char).into()),
+    }
+}
+
+/// Decodes the DBN `side` char as a trade aggressor side, with invalid chars rejected
+/// if `strict` (see [`try_parse_aggressor_side`]), otherwise mapped to no aggressor.
+pub fn decode_aggressor_side(
+    c: c_char,
+    strict: bool,
+) -> Result<AggressorSide, DatabentoDecodeError> {
+    if strict {
+        try_parse_aggressor_side(c)
+    } else {
+        Ok(parse_aggressor_side(c))
     }
 }
 
-pub fn parse_book_action(c: c_char) -> Result<BookAction> {
+/// Parses the raw DBN record `flags` into a [`RecordFlags`].
+#[must_use]
+pub const fn parse_record_flags(flags: u8) -> RecordFlags {
+    RecordFlags::new(flags)
+}
+
+/// Returns whether the raw DBN record `flags` mark the record as part of a snapshot.
+#[must_use]
+pub const fn is_snapshot(flags: u8) -> bool {
+    parse_record_flags(flags).is_snapshot()
+}
+
+/// Parses the DBN `action` char into the coarse `BookAction`, with both fills and
+/// modifies mapped to `BookAction::Update`.
+pub fn parse_book_action(c: c_char) -> Result<BookAction, DatabentoDecodeError> {
+    parse_databento_book_action(c).map(BookAction::from)
+}
+
+/// Parses the DBN `action` char into a [`DatabentoBookAction`], keeping fills
+/// distinct from modifies.
+pub fn parse_databento_book_action(c: c_char) -> Result<DatabentoBookAction, DatabentoDecodeError> {
     match c as u8 as char {
-        'A' => Ok(BookAction::Add),
-        'C' => Ok(BookAction::Delete),
-        'F' => Ok(BookAction::Update),
-        'M' => Ok(BookAction::Update),
-        'R' => Ok(BookAction::Clear),
-        _ => bail!("Invalid `BookAction`, was '{c}'"),
+        'A' => Ok(DatabentoBookAction::Add),
+        'C' => Ok(DatabentoBookAction::Cancel),
+        'F' => Ok(DatabentoBookAction::Fill),
+        'M' => Ok(DatabentoBookAction::Modify),
+        'R' => Ok(DatabentoBookAction::Clear),
+        _ => Err(DatabentoDecodeError::InvalidBookAction(c as u8 as char)),
     }
 }
 
-pub fn parse_option_kind(c: c_char) -> Result<OptionKind> {
+pub fn parse_option_kind(c: c_char) -> Result<OptionKind, DatabentoDecodeError> {
     match c as u8 as char {
         'C' => Ok(OptionKind::Call),
         'P' => Ok(OptionKind::Put),
-        _ => bail!("Invalid `OptionKind`, was '{c}'"),
+        _ => Err(DatabentoDecodeError::InvalidOptionKind(c as u8 as char)),
     }
 }
 
-pub fn parse_cfi_iso10926(value: &str) -> Result<(Option<AssetClass>, Option<InstrumentClass>)> {
+/// Parses the asset class and instrument class from the given ISO 10962 CFI code.
+///
+/// Codes are six characters (category, group and four attributes), where the
+/// trailing attributes may be omitted, but the category, group and first attribute
+/// are required.
+pub fn parse_cfi_iso10926(
+    value: &str,
+) -> Result<(Option<AssetClass>, Option<InstrumentClass>), DatabentoDecodeError> {
     let chars: Vec<char> = value.chars().collect();
     if chars.len() < 3 {
-        bail!("Value string is too short");
+        return Err(DatabentoDecodeError::InvalidCfi(value.to_string()));
     }
 
     let cfi_category = chars[0];
     let cfi_group = chars[1];
     let cfi_attribute1 = chars[2];
-    // let cfi_attribute2 = value[3];
+    let cfi_attribute2 = chars.get(3).copied().unwrap_or('X');
     // let cfi_attribute3 = value[4];
     // let cfi_attribute4 = value[5];
 
-    let mut asset_class = match cfi_category {
-        'D' => Some(AssetClass::Debt),
-        'E' => Some(AssetClass::Equity),
-        'S' => None,
-        _ => None,
+    let result = match (cfi_category, cfi_group) {
+        ('E', _) => (Some(AssetClass::Equity), Some(InstrumentClass::Spot)),
+        // Collective investment vehicles such as ETFs and REITs trade as equities
+        ('C', _) => (Some(AssetClass::Equity), Some(InstrumentClass::Spot)),
+        ('D', _) => (Some(AssetClass::Debt), Some(InstrumentClass::Bond)),
+        // Exchanges such as CME use the miscellaneous group for spreads
+        ('F', 'M') => (
+            parse_cfi_underlying(cfi_attribute1),
+            Some(InstrumentClass::FutureSpread),
+        ),
+        ('F', 'C') => (Some(AssetClass::Commodity), Some(InstrumentClass::Future)),
+        ('F', 'F') => (
+            parse_cfi_underlying(cfi_attribute1),
+            Some(InstrumentClass::Future),
+        ),
+        ('F', _) => (parse_cfi_underlying(cfi_attribute1), None),
+        ('O', 'M') => (
+            parse_cfi_underlying(cfi_attribute2),
+            Some(InstrumentClass::OptionSpread),
+        ),
+        ('O', _) => (
+            parse_cfi_underlying(cfi_attribute2),
+            Some(InstrumentClass::Option),
+        ),
+        ('H', group) => (parse_cfi_group(group), Some(InstrumentClass::Option)),
+        ('R', 'W') => (
+            parse_cfi_underlying(cfi_attribute1),
+            Some(InstrumentClass::Warrant),
+        ),
+        ('R', _) => (None, Some(InstrumentClass::Warrant)),
+        ('I', group) => (parse_cfi_group(group), Some(InstrumentClass::Spot)),
+        ('J', group) => (parse_cfi_group(group), Some(InstrumentClass::Forward)),
+        ('S', group) => (parse_cfi_group(group), Some(InstrumentClass::Swap)),
+        ('K', group) => (parse_cfi_group(group), Some(InstrumentClass::FutureSpread)),
+        _ => (None, None),
     };
 
-    let instrument_class = match cfi_group {
-        'I' => Some(InstrumentClass::Future),
+    Ok(result)
+}
+
+/// Parses the exercise style from the given ISO 10962 CFI code.
+///
+/// Only listed call and put options (`OC`/`OP`) carry the exercise style in the
+/// first attribute, any other code or an unknown attribute returns `None`.
+#[must_use]
+pub fn parse_cfi_exercise_style(value: &str) -> Option<ExerciseStyle> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some('O'), Some('C' | 'P'), Some('A')) => Some(ExerciseStyle::American),
+        (Some('O'), Some('C' | 'P'), Some('E')) => Some(ExerciseStyle::European),
+        (Some('O'), Some('C' | 'P'), Some('B')) => Some(ExerciseStyle::Bermudan),
         _ => None,
+    }
+}
+
+/// The sub-classification of equity instruments, from the ISO 10962 CFI code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EquityClass {
+    /// Common or ordinary shares (`ES`).
+    CommonStock,
+    /// Preferred or preference shares (`EP`/`EF`).
+    PreferredStock,
+    /// Depositary receipts such as ADRs (`ED`).
+    DepositaryReceipt,
+    /// Exchange traded funds (`CE`).
+    Etf,
+    /// Real estate investment trusts (`CR`).
+    Reit,
+    /// Standard investment funds (`CI`).
+    Fund,
+    /// Any other equity or collective investment vehicle.
+    Other,
+}
+
+/// Parses the equity sub-classification from the given ISO 10962 CFI code.
+///
+/// Returns `None` if the code is not for an equity (`E`) or collective investment
+/// vehicle (`C`), or is empty as for many equity venue definitions.
+#[must_use]
+pub fn parse_cfi_equity_class(value: &str) -> Option<EquityClass> {
+    let mut chars = value.chars();
+    let class = match (chars.next()?, chars.next()?) {
+        ('E', 'S') => EquityClass::CommonStock,
+        ('E', 'P' | 'F') => EquityClass::PreferredStock,
+        ('E', 'D') => EquityClass::DepositaryReceipt,
+        ('C', 'E') => EquityClass::Etf,
+        ('C', 'R') => EquityClass::Reit,
+        ('C', 'I') => EquityClass::Fund,
+        ('E' | 'C', _) => EquityClass::Other,
+        _ => return None,
     };
+    Some(class)
+}
+
+/// Parses the asset class from a CFI underlying asset attribute.
+fn parse_cfi_underlying(c: char) -> Option<AssetClass> {
+    match c {
+        'S' => Some(AssetClass::Equity),
+        'D' | 'N' => Some(AssetClass::Debt),
+        'T' => Some(AssetClass::Commodity),
+        'C' => Some(AssetClass::FX),
+        'I' => Some(AssetClass::Index),
+        _ => None,
+    }
+}
+
+/// Parses the asset class from a CFI group for the spot, forward, swap, strategy
+/// and non-listed option categories.
+fn parse_cfi_group(c: char) -> Option<AssetClass> {
+    match c {
+        'E' => Some(AssetClass::Equity),
+        'R' | 'C' => Some(AssetClass::Debt),
+        'T' => Some(AssetClass::Commodity),
+        'F' => Some(AssetClass::FX),
+        _ => None,
+    }
+}
+
+/// Returns the asset class for the definition of the given instrument.
+///
+/// The `asset_class_override` table is consulted first, allowing known misclassifications
+/// to be corrected, then OPRA instruments are equities, otherwise the asset class is
+/// inferred from the `cfi` code.
+pub fn decode_asset_class(
+    instrument_id: InstrumentId,
+    cfi: &str,
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<Option<AssetClass>, DatabentoDecodeError> {
+    if let Some(asset_class) = asset_class_override.and_then(|map| map.get(&instrument_id)) {
+        return Ok(Some(*asset_class));
+    }
+
+    match instrument_id.venue.value.as_str() {
+        "OPRA" => Ok(Some(AssetClass::Equity)),
+        _ => {
+            let (asset_class, _) = parse_cfi_iso10926(cfi)?;
+            Ok(asset_class)
+        }
+    }
+}
+
+/// Scales the DBN fixed-point `raw` price (1e-9 units) by the fixed-point `display_factor`
+/// (for instruments quoted in sub-units), returning a `Price` with the given `precision`.
+///
+/// The scaled price is rounded half away from zero to the `precision`, so negative
+/// prices (such as for spreads) round symmetrically. The undefined price sentinel is
+/// returned unscaled. A `display_factor` of [`DISPLAY_FACTOR_ONE`] leaves the price
+/// unscaled.
+pub fn scale_price(
+    raw: i64,
+    display_factor: i64,
+    precision: u8,
+) -> Result<Price, DatabentoDecodeError> {
+    scale_price_with_divisor(raw, display_factor, 1, precision)
+}
 
-    if cfi_attribute1 == 'I' {
-        asset_class = Some(AssetClass::Index);
+/// Scales the DBN OHLCV `raw` price as per [`scale_price`], further dividing by
+/// [`OHLCV_PRICE_DIVISOR`] so bars decode to the same scale with or without a definition.
+pub fn scale_ohlcv_price(
+    raw: i64,
+    display_factor: i64,
+    precision: u8,
+) -> Result<Price, DatabentoDecodeError> {
+    scale_price_with_divisor(raw, display_factor, OHLCV_PRICE_DIVISOR, precision)
+}
+
+fn scale_price_with_divisor(
+    raw: i64,
+    display_factor: i64,
+    divisor: i64,
+    precision: u8,
+) -> Result<Price, DatabentoDecodeError> {
+    check_fixed_precision(precision)?;
+    if raw == dbn::UNDEF_PRICE {
+        return Ok(Price::from_raw(raw, precision)?);
     }
 
-    Ok((asset_class, instrument_class))
+    let increment = 10i128.pow(u32::from(FIXED_PRECISION - precision));
+    let numerator = i128::from(raw) * i128::from(display_factor);
+    let denominator = i128::from(DISPLAY_FACTOR_ONE) * i128::from(divisor) * increment;
+    let half = denominator / 2;
+    let units = if numerator >= 0 {
+        (numerator + half) / denominator
+    } else {
+        (numerator - half) / denominator
+    };
+    let scaled = i64::try_from(units * increment).map_err(|_| {
+        anyhow!("Price raw {raw} overflowed when applying `display_factor` {display_factor}")
+    })?;
+
+    Ok(Price::from_raw(scaled, precision)?)
+}
+
+/// Decodes the DBN `strike_price`, returning an error if the strike is undefined
+/// (as for some OPRA spread legs), since an options contract requires a strike.
+pub fn decode_strike_price(
+    value: i64,
+    precision: u8,
+    instrument_id: InstrumentId,
+) -> Result<Price, DatabentoDecodeError> {
+    match value {
+        dbn::UNDEF_PRICE => {
+            Err(anyhow!("Undefined `strike_price` for options contract {instrument_id}").into())
+        }
+        raw => scale_price(raw, DISPLAY_FACTOR_ONE, precision),
+    }
 }
 
-pub fn decode_min_price_increment(value: i64, currency: Currency) -> Result<Price> {
+/// Decodes the DBN `min_price_increment`, falling back to the smallest increment of
+/// the `currency` if undefined.
+///
+/// The increment is always positive, as spread definitions (which can trade at
+/// negative prices) may carry a negative increment.
+pub fn decode_min_price_increment(
+    value: i64,
+    currency: Currency,
+) -> Result<Price, DatabentoDecodeError> {
     match value {
-        0 | i64::MAX => Price::new(
+        0 | i64::MAX => Ok(Price::new(
             10f64.powi(-i32::from(currency.precision)),
             currency.precision,
-        ),
-        _ => Price::from_raw(value, currency.precision),
+        )?),
+        _ => {
+            let value = value.saturating_abs();
+            scale_price(
+                value,
+                DISPLAY_FACTOR_ONE,
+                precision_from_raw_increment(value),
+            )
+        }
+    }
+}
+
+/// Returns the price precision implied by the DBN `min_price_increment`, falling back
+/// to the `currency` precision if the increment is undefined.
+#[must_use]
+pub fn decode_price_precision(min_price_increment: i64, currency: Currency) -> u8 {
+    match min_price_increment {
+        0 | i64::MAX => currency.precision,
+        raw => precision_from_raw_increment(raw),
+    }
+}
+
+/// Parses the DBN `currency` field, falling back to USD if it is empty or unknown.
+#[must_use]
+pub fn parse_currency_or_usd_default(value: &str) -> Currency {
+    if value.is_empty() {
+        return Currency::USD();
+    }
+    parse_currency(value).unwrap_or_else(|_| Currency::USD())
+}
+
+/// Parses the DBN `settl_currency` field, returning `None` if it is empty.
+pub fn parse_settlement_currency(value: &str) -> Result<Option<Currency>, DatabentoDecodeError> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(parse_currency(value)?))
+}
+
+thread_local! {
+    // Caches the currencies parsed on each thread, avoiding the global `CURRENCY_MAP` lock
+    // (and its contention) when decoding many definitions with the same currency
+    static CURRENCY_CACHE: RefCell<HashMap<String, Currency>> = RefCell::new(HashMap::new());
+}
+
+/// Parses the given currency `value`, returning an error if the currency is unknown.
+///
+/// Parsed currencies are cached per thread, so repeated values resolve without
+/// re-parsing, and the cache is safe to use from decoders on any thread.
+pub fn parse_currency(value: &str) -> Result<Currency, DatabentoDecodeError> {
+    CURRENCY_CACHE.with(|cache| {
+        if let Some(currency) = cache.borrow().get(value) {
+            return Ok(*currency);
+        }
+
+        let currency = Currency::from_str(value)
+            .map_err(|_| DatabentoDecodeError::UnknownCurrency(value.to_string()))?;
+        cache.borrow_mut().insert(value.to_string(), currency);
+        Ok(currency)
+    })
+}
+
+/// Decodes the contract multiplier from the DBN fixed-point `unit_of_measure_qty`,
+/// falling back to the `contract_multiplier` and then to 1 if neither is defined.
+pub fn decode_multiplier(
+    unit_of_measure_qty: i64,
+    contract_multiplier: i32,
+) -> Result<Quantity, DatabentoDecodeError> {
+    if unit_of_measure_qty > 0 && unit_of_measure_qty != i64::MAX {
+        return Ok(Quantity::from_raw(
+            unit_of_measure_qty as u64,
+            precision_from_raw_increment(unit_of_measure_qty),
+        )?);
+    }
+
+    match contract_multiplier {
+        value if value > 0 && value != i32::MAX => Ok(Quantity::new(f64::from(value), 0)?),
+        _ => Ok(Quantity::new(1.0, 0)?),
+    }
+}
+
+/// Decodes the options contract multiplier as per [`decode_multiplier`], defaulting
+/// to the standard 100 shares per contract for OPRA equity options if undefined.
+pub fn decode_options_multiplier(
+    unit_of_measure_qty: i64,
+    contract_multiplier: i32,
+    publisher_id: PublisherId,
+) -> Result<Quantity, DatabentoDecodeError> {
+    let is_undefined = |value: i64, max: i64| value <= 0 || value == max;
+    if is_opra_publisher(publisher_id)
+        && is_undefined(unit_of_measure_qty, i64::MAX)
+        && is_undefined(i64::from(contract_multiplier), i64::from(i32::MAX))
+    {
+        return Ok(Quantity::new(OPRA_DEFAULT_MULTIPLIER, 0)?);
+    }
+
+    decode_multiplier(unit_of_measure_qty, contract_multiplier)
+}
+
+/// Returns whether the given `publisher_id` is for the OPRA.PILLAR dataset.
+const fn is_opra_publisher(publisher_id: PublisherId) -> bool {
+    matches!(publisher_id, 20..=37 | 61)
+}
+
+/// Decodes the lot size from the DBN `min_lot_size_round_lot`, falling back to 1 if
+/// it is undefined.
+pub fn decode_lot_size(min_lot_size_round_lot: i32) -> Result<Quantity, DatabentoDecodeError> {
+    match min_lot_size_round_lot {
+        value if value > 0 && value != i32::MAX => Ok(Quantity::new(f64::from(value), 0)?),
+        _ => Ok(Quantity::new(1.0, 0)?),
+    }
+}
+
+/// Decodes the DBN trade volume limit `value`, returning `None` if it is undefined.
+pub fn decode_optional_quantity(value: u32) -> Result<Option<Quantity>, DatabentoDecodeError> {
+    match value {
+        u32::MAX => Ok(None),
+        value => Ok(Some(Quantity::new(f64::from(value), 0)?)),
+    }
+}
+
+/// Decodes an optional price (such as the daily `high_limit_price` band or an
+/// imbalance `ref_price`), returning `None` if it is undefined.
+pub fn decode_optional_price(
+    value: i64,
+    precision: u8,
+) -> Result<Option<Price>, DatabentoDecodeError> {
+    match value {
+        dbn::UNDEF_PRICE => Ok(None),
+        raw => Ok(Some(scale_price(raw, DISPLAY_FACTOR_ONE, precision)?)),
+    }
+}
+
+/// Decodes the bond face value from the DBN fixed-point `unit_of_measure_qty`, falling
+/// back to 100 (prices quoted as a percentage of par) if it is undefined.
+pub fn decode_face_value(unit_of_measure_qty: i64) -> Result<Quantity, DatabentoDecodeError> {
+    match unit_of_measure_qty {
+        value if value > 0 && value != i64::MAX => Ok(Quantity::from_raw(
+            value as u64,
+            precision_from_raw_increment(value),
+        )?),
+        _ => Ok(Quantity::new(100.0, 0)?),
+    }
+}
+
+/// Decodes the DBN `timestamp`, returning `None` if it is undefined.
+#[must_use]
+pub fn decode_optional_timestamp(timestamp: UnixNanos) -> Option<UnixNanos> {
+    match timestamp {
+        dbn::UNDEF_TIMESTAMP => None,
+        _ => Some(timestamp),
+    }
+}
+
+/// Decodes the DBN contract `activation`, with an undefined activation decoded as the
+/// UNIX epoch (i.e. active since inception) rather than the far-future sentinel.
+#[must_use]
+pub fn decode_activation(activation: UnixNanos) -> UnixNanos {
+    decode_optional_timestamp(activation).unwrap_or_default()
+}
+
+/// Decodes the DBN contract `expiration`, returning `None` if it is undefined (such as for
+/// perpetual and continuous contracts) so the caller can choose how to treat it.
+#[must_use]
+pub fn decode_expiration(expiration: UnixNanos) -> Option<UnixNanos> {
+    decode_optional_timestamp(expiration)
+}
+
+/// Returns the precision (number of significant decimal places) of the given
+/// `increment`, such as 2 for a tick size of 0.01 and 0 for whole-number ticks.
+#[must_use]
+pub fn precision_from_increment(increment: f64) -> u8 {
+    precision_from_raw_increment((increment * FIXED_SCALAR).round() as i64)
+}
+
+/// Returns the number of decimal places required to represent the DBN fixed-point
+/// `raw` increment.
+#[must_use]
+pub fn precision_from_raw_increment(raw: i64) -> u8 {
+    let mut raw = raw;
+    let mut precision = FIXED_PRECISION;
+    while precision > 0 && raw % 10 == 0 {
+        raw /= 10;
+        precision -= 1;
+    }
+    precision
+}
+
+/// Represents a change in the minimum price increment (tick size) of an instrument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TickSizeChange {
+    pub instrument_id: InstrumentId,
+    /// The previous price increment, `None` if it was previously undefined.
+    pub old_price_increment: Option<Price>,
+    pub new_price_increment: Price,
+    pub ts_event: UnixNanos,
+}
+
+/// Provides detection of tick size changes across re-sent instrument definitions.
+#[derive(Clone, Debug, Default)]
+pub struct TickSizeMonitor {
+    increments: HashMap<InstrumentId, Option<i64>>,
+}
+
+impl TickSizeMonitor {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the cached `min_price_increment` for the instrument, returning a change
+    /// event if it differs from the previously cached value.
+    ///
+    /// The first definition seen for an instrument is cached without a change event.
+    /// An undefined increment never replaces a defined one, whereas an undefined to
+    /// defined transition produces a change event with no old price increment.
+    pub fn update(
+        &mut self,
+        instrument_id: InstrumentId,
+        min_price_increment: i64,
+        ts_event: UnixNanos,
+    ) -> Result<Option<TickSizeChange>, DatabentoDecodeError> {
+        let new_raw = match min_price_increment {
+            0 | i64::MAX => None,
+            raw => Some(raw),
+        };
+
+        let old_raw = match self.increments.get(&instrument_id) {
+            Some(old_raw) => *old_raw,
+            None => {
+                self.increments.insert(instrument_id, new_raw);
+                return Ok(None);
+            }
+        };
+
+        let Some(new_raw) = new_raw else {
+            return Ok(None); // Keep any defined increment
+        };
+
+        if old_raw == Some(new_raw) {
+            return Ok(None);
+        }
+
+        self.increments.insert(instrument_id, Some(new_raw));
+
+        let old_price_increment = match old_raw {
+            Some(raw) => Some(Price::from_raw(raw, precision_from_raw_increment(raw))?),
+            None => None,
+        };
+
+        Ok(Some(TickSizeChange {
+            instrument_id,
+            old_price_increment,
+            new_price_increment: Price::from_raw(new_raw, precision_from_raw_increment(new_raw))?,
+            ts_event,
+        }))
     }
 }
 
-/// # Safety
+/// Returns the time to expiry in years (ACT/365) from `now` until `expiration`.
 ///
-/// - Assumes `ptr` is a valid C string pointer.
-pub unsafe fn raw_ptr_to_string(ptr: *const c_char) -> Result<String> {
-    let c_str: &CStr = unsafe { CStr::from_ptr(ptr) };
-    let str_slice: &str = c_str.to_str().map_err(|e| anyhow!(e))?;
-    Ok(str_slice.to_owned())
+/// Returns `None` if `expiration` is the DBN undefined timestamp sentinel, and zero
+/// if the contract has already expired as at `now`.
+#[must_use]
+pub fn time_to_expiry(expiration: UnixNanos, now: UnixNanos) -> Option<f64> {
+    if expiration == dbn::UNDEF_TIMESTAMP {
+        return None;
+    }
+
+    Some(expiration.saturating_sub(now) as f64 / NANOSECONDS_IN_YEAR_ACT365 as f64)
 }
 
-/// # Safety
+/// Returns the monetary value of a single tick of the futures `contract`, being the
+/// price increment multiplied by the contract multiplier (point value), in the quote
+/// currency.
 ///
-/// - Assumes `ptr` is a valid C string pointer.
-pub unsafe fn raw_ptr_to_ustr(ptr: *const c_char) -> Result<Ustr> {
-    let c_str: &CStr = unsafe { CStr::from_ptr(ptr) };
-    let str_slice: &str = c_str.to_str().map_err(|e| anyhow!(e))?;
-    Ok(Ustr::from(str_slice))
+/// The product is computed exactly, with an error returned if it cannot be represented
+/// at the precision of the quote currency (e.g. a 0.015625 tick with a 1000 multiplier
+/// in USD) rather than silently rounding it.
+pub fn tick_value(contract: &FuturesContract) -> Result<Money, DatabentoDecodeError> {
+    let currency = contract.quote_currency();
+    let value = contract
+        .price_increment()
+        .as_decimal()
+        .checked_mul(contract.multiplier().as_decimal())
+        .ok_or_else(|| anyhow!("Tick value overflow for contract {}", contract.id))?
+        .normalize();
+
+    if value.scale() > u32::from(currency.precision) {
+        return Err(anyhow!(
+            "Tick value {value} for contract {} is not representable in {} with precision {}",
+            contract.id,
+            currency.code,
+            currency.precision,
+        )
+        .into());
+    }
+
+    let raw = value
+        .checked_mul(Decimal::from(10_i64.pow(u32::from(FIXED_PRECISION))))
+        .and_then(|raw| raw.to_i64())
+        .ok_or_else(|| anyhow!("Tick value overflow for contract {}", contract.id))?;
+    Ok(Money::from_raw(raw, currency))
+}
+
+/// Returns the string of the given fixed-size DBN field, bounded to the length of
+/// the array and trimmed at the first NUL (if any).
+pub fn fixed_bytes_to_str<const N: usize>(
+    value: &[c_char; N],
+) -> Result<&str, DatabentoDecodeError> {
+    // SAFETY: `c_char` is either `i8` or `u8`, with the same size and alignment as `u8`
+    let bytes: &[u8] = unsafe { slice::from_raw_parts(value.as_ptr().cast::<u8>(), N) };
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(N);
+    Ok(str::from_utf8(&bytes[..len])?)
+}
+
+/// The DBN encoding versions supported by the decoders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SupportedVersion {
+    V1,
+    V2,
+}
+
+/// Checks the DBN version of the given `metadata` is supported by the decoders.
+pub fn check_dbn_version(
+    metadata: &dbn::Metadata,
+) -> Result<SupportedVersion, DatabentoDecodeError> {
+    match metadata.version {
+        1 => Ok(SupportedVersion::V1),
+        2 => Ok(SupportedVersion::V2),
+        version => Err(DatabentoDecodeError::UnsupportedVersion(version)),
+    }
+}
+
+/// The policy for instrument definitions with an empty `raw_symbol` field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptySymbolPolicy {
+    /// Return an error for the definition.
+    #[default]
+    Error,
+    /// Fall back to the DBN numeric instrument ID as the symbol.
+    UseInstrumentId,
+}
+
+/// Decodes the given `raw_symbol`, applying the `policy` if it is empty.
+pub fn decode_raw_symbol(
+    raw_symbol: &str,
+    instrument_id: u32,
+    policy: EmptySymbolPolicy,
+) -> Result<Ustr, DatabentoDecodeError> {
+    if !raw_symbol.is_empty() {
+        return Ok(Ustr::from(raw_symbol));
+    }
+
+    match policy {
+        EmptySymbolPolicy::Error => Err(anyhow!(
+            "Empty `raw_symbol` for definition with instrument ID {instrument_id}"
+        )
+        .into()),
+        EmptySymbolPolicy::UseInstrumentId => {
+            Ok(Ustr::from(itoa::Buffer::new().format(instrument_id)))
+        }
+    }
 }
 
 pub fn decode_equity_v1(
     msg: &dbn::compat::InstrumentDefMsgV1,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<Equity> {
-    let currency = Currency::USD(); // TODO: Temporary hard coding of US equities for now
+) -> Result<Equity, DatabentoDecodeError> {
+    decode_equity_with_class_v1(msg, instrument_id, ts_init).map(|(equity, _)| equity)
+}
+
+/// Decodes the equity as per [`decode_equity_v1`], along with its sub-classification
+/// (e.g. common stock or ETF) parsed from the definition CFI code, which the
+/// Nautilus `Equity` does not carry.
+pub fn decode_equity_with_class_v1(
+    msg: &dbn::compat::InstrumentDefMsgV1,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+) -> Result<(Equity, Option<EquityClass>), DatabentoDecodeError> {
+    let equity_class = parse_cfi_equity_class(fixed_bytes_to_str(&msg.cfi)?);
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
 
-    Equity::new(
+    let equity = Equity::new(
         instrument_id,
         instrument_id.symbol,
-        None, // No ISIN available yet
+        None, // No ISIN field in DBN 0.15.1 definitions
         currency,
-        currency.precision,
+        price_precision,
         decode_min_price_increment(msg.min_price_increment, currency)?,
         Some(Quantity::new(msg.min_lot_size_round_lot.into(), 0)?),
-        None,        // TBD
-        None,        // TBD
-        None,        // TBD
-        None,        // TBD
+        None, // TBD
+        None, // TBD
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
         msg.ts_recv, // More accurate and reliable timestamp
         ts_init,
-    )
+    )?;
+
+    Ok((equity, equity_class))
 }
 
 pub fn decode_futures_contract_v1(
     msg: &dbn::compat::InstrumentDefMsgV1,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<FuturesContract> {
-    let currency = Currency::USD(); // TODO: Temporary hard coding of US futures for now
-    let cfi_str = unsafe { raw_ptr_to_string(msg.cfi.as_ptr())? };
-    let underlying = unsafe { raw_ptr_to_ustr(msg.asset.as_ptr())? };
-    let (asset_class, _) = parse_cfi_iso10926(&cfi_str)?;
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<FuturesContract, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let settl_currency_str = fixed_bytes_to_str(&msg.settl_currency)?;
+    let cfi_str = fixed_bytes_to_str(&msg.cfi)?;
+    let underlying = Ustr::from(fixed_bytes_to_str(&msg.asset)?);
+    let asset_class = decode_asset_class(instrument_id, cfi_str, asset_class_override)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
 
-    FuturesContract::new(
+    Ok(FuturesContract::new(
         instrument_id,
         instrument_id.symbol,
         asset_class.unwrap_or(AssetClass::Commodity),
         underlying,
-        msg.activation,
-        msg.expiration,
+        decode_activation(msg.activation),
+        decode_expiration(msg.expiration).unwrap_or(UnixNanos::MAX), // Never expires
         currency,
-        currency.precision,
+        parse_settlement_currency(settl_currency_str)?,
+        price_precision,
         decode_min_price_increment(msg.min_price_increment, currency)?,
-        Quantity::new(1.0, 0)?, // TBD
-        Quantity::new(1.0, 0)?, // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        msg.ts_recv,            // More accurate and reliable timestamp
+        decode_multiplier(msg.unit_of_measure_qty, msg.contract_multiplier)?,
+        decode_lot_size(msg.min_lot_size_round_lot)?,
+        decode_optional_quantity(msg.max_trade_vol)?,
+        decode_optional_quantity(msg.min_trade_vol)?,
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
         ts_init,
-    )
+    )?)
 }
 
 pub fn decode_futures_spread_v1(
     msg: &dbn::compat::InstrumentDefMsgV1,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<FuturesSpread> {
-    let currency = Currency::USD(); // TODO: Temporary hard coding of US futures for now
-    let cfi_str = unsafe { raw_ptr_to_string(msg.cfi.as_ptr())? };
-    let underlying = unsafe { raw_ptr_to_ustr(msg.asset.as_ptr())? };
-    let strategy_type = unsafe { raw_ptr_to_ustr(msg.secsubtype.as_ptr())? };
-    let (asset_class, _) = parse_cfi_iso10926(&cfi_str)?;
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<FuturesSpread, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let cfi_str = fixed_bytes_to_str(&msg.cfi)?;
+    let underlying = Ustr::from(fixed_bytes_to_str(&msg.asset)?);
+    let strategy_type = Ustr::from(fixed_bytes_to_str(&msg.secsubtype)?);
+    let asset_class = decode_asset_class(instrument_id, cfi_str, asset_class_override)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
 
-    FuturesSpread::new(
+    Ok(FuturesSpread::new(
         instrument_id,
         instrument_id.symbol,
         asset_class.unwrap_or(AssetClass::Commodity),
         underlying,
         strategy_type,
-        msg.activation,
-        msg.expiration,
+        decode_activation(msg.activation),
+        decode_expiration(msg.expiration).unwrap_or(UnixNanos::MAX), // Never expires
         currency,
-        currency.precision,
+        price_precision,
         decode_min_price_increment(msg.min_price_increment, currency)?,
         Quantity::new(1.0, 0)?, // TBD
         Quantity::new(1.0, 0)?, // TBD
         None,                   // TBD
         None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        msg.ts_recv,            // More accurate and reliable timestamp
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
         ts_init,
-    )
+    )?)
 }
 
 pub fn decode_options_contract_v1(
     msg: &dbn::compat::InstrumentDefMsgV1,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<OptionsContract> {
-    let currency_str = unsafe { raw_ptr_to_string(msg.currency.as_ptr())? };
-    let cfi_str = unsafe { raw_ptr_to_string(msg.cfi.as_ptr())? };
-    let asset_class_opt = match instrument_id.venue.value.as_str() {
-        "OPRA" => Some(AssetClass::Equity),
-        _ => {
-            let (asset_class, _) = parse_cfi_iso10926(&cfi_str)?;
-            asset_class
-        }
-    };
-    let underlying = unsafe { raw_ptr_to_ustr(msg.underlying.as_ptr())? };
-    let currency = Currency::from_str(&currency_str)?;
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<OptionsContract, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let cfi_str = fixed_bytes_to_str(&msg.cfi)?;
+    let asset_class = decode_asset_class(instrument_id, cfi_str, asset_class_override)?;
+    let underlying = Ustr::from(fixed_bytes_to_str(&msg.underlying)?);
+    let currency = parse_currency(currency_str)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
 
-    OptionsContract::new(
+    Ok(OptionsContract::new(
         instrument_id,
         instrument_id.symbol,
-        asset_class_opt.unwrap_or(AssetClass::Commodity),
+        asset_class.unwrap_or(AssetClass::Commodity),
         underlying,
         parse_option_kind(msg.instrument_class)?,
+        parse_cfi_exercise_style(cfi_str),
         msg.activation,
         msg.expiration,
-        Price::from_raw(msg.strike_price, currency.precision)?,
+        decode_strike_price(msg.strike_price, price_precision, instrument_id)?,
         currency,
-        currency.precision,
+        price_precision,
         decode_min_price_increment(msg.min_price_increment, currency)?,
-        Quantity::new(1.0, 0)?, // TBD
-        Quantity::new(1.0, 0)?, // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        msg.ts_recv,            // More accurate and reliable timestamp
+        decode_options_multiplier(
+            msg.unit_of_measure_qty,
+            msg.contract_multiplier,
+            msg.hd.publisher_id,
+        )?,
+        decode_lot_size(msg.min_lot_size_round_lot)?,
+        None, // TBD
+        None, // TBD
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
         ts_init,
-    )
+    )?)
 }
 
 pub fn decode_options_spread_v1(
     msg: &dbn::compat::InstrumentDefMsgV1,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<OptionsSpread> {
-    let currency_str = unsafe { raw_ptr_to_string(msg.currency.as_ptr())? };
-    let cfi_str = unsafe { raw_ptr_to_string(msg.cfi.as_ptr())? };
-    let asset_class_opt = match instrument_id.venue.value.as_str() {
-        "OPRA" => Some(AssetClass::Equity),
-        _ => {
-            let (asset_class, _) = parse_cfi_iso10926(&cfi_str)?;
-            asset_class
-        }
-    };
-    let underlying = unsafe { raw_ptr_to_ustr(msg.underlying.as_ptr())? };
-    let strategy_type = unsafe { raw_ptr_to_ustr(msg.secsubtype.as_ptr())? };
-    let currency = Currency::from_str(&currency_str)?;
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<OptionsSpread, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let cfi_str = fixed_bytes_to_str(&msg.cfi)?;
+    let asset_class = decode_asset_class(instrument_id, cfi_str, asset_class_override)?;
+    let underlying = Ustr::from(fixed_bytes_to_str(&msg.underlying)?);
+    let strategy_type = Ustr::from(fixed_bytes_to_str(&msg.secsubtype)?);
+    let currency = parse_currency(currency_str)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
 
-    OptionsSpread::new(
+    Ok(OptionsSpread::new(
         instrument_id,
         instrument_id.symbol,
-        asset_class_opt.unwrap_or(AssetClass::Commodity),
+        asset_class.unwrap_or(AssetClass::Commodity),
         underlying,
         strategy_type,
         msg.activation,
         msg.expiration,
         currency,
-        currency.precision,
+        price_precision,
         decode_min_price_increment(msg.min_price_increment, currency)?,
-        Quantity::new(1.0, 0)?, // TBD
-        Quantity::new(1.0, 0)?, // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        msg.ts_recv,            // More accurate and reliable timestamp
+        decode_options_multiplier(
+            msg.unit_of_measure_qty,
+            msg.contract_multiplier,
+            msg.hd.publisher_id,
+        )?,
+        decode_lot_size(msg.min_lot_size_round_lot)?,
+        None, // TBD
+        None, // TBD
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
         ts_init,
-    )
-}
-
-#[must_use]
-pub fn is_trade_msg(order_side: OrderSide, action: c_char) -> bool {
-    order_side == OrderSide::NoOrderSide || action as u8 as char == 'T'
+    )?)
 }
 
-pub fn decode_mbo_msg(
-    msg: &dbn::MboMsg,
+/// Decodes an index definition (such as SPX) into an `IndexInstrument`, which has
+/// no lot size as indexes are not directly traded.
+pub fn decode_index_instrument_v1(
+    msg: &dbn::compat::InstrumentDefMsgV1,
     instrument_id: InstrumentId,
-    price_precision: u8,
     ts_init: UnixNanos,
-    include_trades: bool,
-) -> Result<(Option<OrderBookDelta>, Option<TradeTick>)> {
-    let side = parse_order_side(msg.side);
-    if is_trade_msg(side, msg.action) {
-        if include_trades {
-            let trade = TradeTick::new(
+) -> Result<IndexInstrument, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
+
+    Ok(IndexInstrument::new(
+        instrument_id,
+        instrument_id.symbol,
+        currency,
+        price_precision,
+        decode_min_price_increment(msg.min_price_increment, currency)?,
+        msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    )?)
+}
+
+pub fn decode_bond_v1(
+    msg: &dbn::compat::InstrumentDefMsgV1,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+) -> Result<Bond, DatabentoDecodeError> {
+    if msg.expiration == dbn::UNDEF_TIMESTAMP {
+        return Err(anyhow!("Undefined `expiration` for bond maturity of {instrument_id}").into());
+    }
+
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
+
+    Ok(Bond::new(
+        instrument_id,
+        instrument_id.symbol,
+        None, // No ISIN field in DBN 0.15.1 definitions
+        currency,
+        None, // No coupon available
+        decode_optional_timestamp(msg.activation),
+        msg.expiration,
+        decode_face_value(msg.unit_of_measure_qty)?,
+        price_precision,
+        decode_min_price_increment(msg.min_price_increment, currency)?,
+        Some(decode_lot_size(msg.min_lot_size_round_lot)?),
+        decode_optional_quantity(msg.max_trade_vol)?,
+        decode_optional_quantity(msg.min_trade_vol)?,
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    )?)
+}
+
+/// Decodes an FX spot definition into a `CurrencyPair`, with the base currency parsed
+/// from the `asset` field and the quote currency from the `currency` field.
+pub fn decode_currency_pair_v1(
+    msg: &dbn::compat::InstrumentDefMsgV1,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+) -> Result<CurrencyPair, DatabentoDecodeError> {
+    let base_currency_str = fixed_bytes_to_str(&msg.asset)?;
+    let base_currency = parse_currency(base_currency_str)?;
+    let quote_currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let quote_currency = parse_currency(quote_currency_str)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, quote_currency);
+
+    Ok(CurrencyPair::new(
+        instrument_id,
+        instrument_id.symbol,
+        base_currency,
+        quote_currency,
+        price_precision,
+        0,
+        decode_min_price_increment(msg.min_price_increment, quote_currency)?,
+        Quantity::new(1.0, 0)?,
+        Decimal::ZERO, // No fees available
+        Decimal::ZERO, // No fees available
+        Decimal::ZERO, // No margins available
+        Decimal::ZERO, // No margins available
+        Some(decode_lot_size(msg.min_lot_size_round_lot)?),
+        decode_optional_quantity(msg.max_trade_vol)?,
+        decode_optional_quantity(msg.min_trade_vol)?,
+        None, // TBD
+        None, // TBD
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    )?)
+}
+
+/// Returns whether the MBO record is a trade, being a 'T' action or having no side.
+///
+/// The clear ('R') and none ('N') actions can also have no side, but are not trades.
+#[must_use]
+pub fn is_trade_msg(order_side: OrderSide, action: c_char) -> bool {
+    match action as u8 as char {
+        'T' => true,
+        'R' | 'N' => false,
+        _ => order_side == OrderSide::NoOrderSide,
+    }
+}
+
+/// Returns whether the MBO record has the none ('N') action, carrying no book or trade update.
+#[must_use]
+pub fn is_none_action(action: c_char) -> bool {
+    action as u8 as char == 'N'
+}
+
+/// The policy for composing the `TradeId` of decoded trades.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TradeIdPolicy {
+    /// The `sequence` only, which can collide across sessions and instruments.
+    #[default]
+    Sequence,
+    /// The `ts_recv` and `sequence`, as `{ts_recv}-{sequence}`.
+    TsRecvSequence,
+    /// The `publisher_id` and `sequence`, as `{publisher_id}-{sequence}`.
+    PublisherSequence,
+}
+
+/// Returns the `TradeId` for the given trade fields, composed as per the `policy`.
+pub fn decode_trade_id(
+    sequence: u32,
+    ts_recv: UnixNanos,
+    publisher_id: u16,
+    policy: TradeIdPolicy,
+) -> Result<TradeId, DatabentoDecodeError> {
+    let trade_id = match policy {
+        TradeIdPolicy::Sequence => TradeId::new(itoa::Buffer::new().format(sequence))?,
+        TradeIdPolicy::TsRecvSequence => TradeId::new(&format!("{ts_recv}-{sequence}"))?,
+        TradeIdPolicy::PublisherSequence => TradeId::new(&format!("{publisher_id}-{sequence}"))?,
+    };
+
+    Ok(trade_id)
+}
+
+/// Provides the options for decoding DBN records, beyond the instrument ID, price
+/// precision and `ts_init` given per record.
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeOptions {
+    /// The size precision of decoded quantities, for venues which allow fractional lots.
+    pub size_precision: u8,
+    /// The multiplier for sizes, for venues which report sizes in round lots rather than shares.
+    pub size_multiplier: u64,
+    /// The fixed-point DBN `display_factor` of the instrument if known, otherwise prices
+    /// are decoded with [`DISPLAY_FACTOR_ONE`].
+    pub display_factor: Option<i64>,
+    /// The policy for composing the `TradeId` of decoded trades.
+    pub trade_id_policy: TradeIdPolicy,
+    /// If an unknown trade aggressor side is an error, rather than `NoAggressor`.
+    pub strict_aggressor_side: bool,
+    /// The policy for MBP-1 quotes with a bid price above the ask price.
+    pub crossed_quote_policy: CrossedQuotePolicy,
+    /// If the price levels of MBP-10 records are checked to be in order.
+    pub validate_level_ordering: bool,
+    /// If bar timestamps are at the close rather than the open of the bar.
+    pub bar_close_timestamps: bool,
+    /// The policy for deriving the `ts_init` of decoded bars.
+    pub bar_ts_init_policy: BarTsInitPolicy,
+    /// The policy for bars with undefined prices.
+    pub empty_bar_policy: EmptyBarPolicy,
+    /// The close of the prior bar of the instrument, for [`EmptyBarPolicy::CarryForward`].
+    pub prior_close: Option<Price>,
+    /// The policy for bars with inconsistent prices.
+    pub bar_validation_policy: BarValidationPolicy,
+    /// The optional bar spec for bars resampled to a custom step of the rtype's base
+    /// aggregation (see [`decode_bar_type_with_override`]).
+    pub bar_spec_override: Option<BarSpecification>,
+    /// The optional band of sane prices for decoded data.
+    pub price_bounds: Option<PriceBounds>,
+    /// The optional maximum `ts_event` for records, beyond which they are rejected.
+    pub max_ts_event: Option<UnixNanos>,
+}
+
+impl DecodeOptions {
+    // The display factor for prices, with OHLCV prices also scaled by `OHLCV_PRICE_DIVISOR`
+    fn price_display_factor(&self) -> i64 {
+        self.display_factor.unwrap_or(DISPLAY_FACTOR_ONE)
+    }
+}
+
+impl Default for DecodeOptions {
+    /// Creates a new default [`DecodeOptions`] instance.
+    fn default() -> Self {
+        Self {
+            size_precision: 0,
+            size_multiplier: 1,
+            display_factor: None,
+            trade_id_policy: TradeIdPolicy::default(),
+            strict_aggressor_side: false,
+            crossed_quote_policy: CrossedQuotePolicy::default(),
+            validate_level_ordering: false,
+            bar_close_timestamps: false,
+            bar_ts_init_policy: BarTsInitPolicy::default(),
+            empty_bar_policy: EmptyBarPolicy::default(),
+            prior_close: None,
+            bar_validation_policy: BarValidationPolicy::default(),
+            bar_spec_override: None,
+            price_bounds: None,
+            max_ts_event: None,
+        }
+    }
+}
+
+pub fn decode_mbo_msg(
+    msg: &dbn::MboMsg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+    include_trades: bool,
+    options: &DecodeOptions,
+) -> Result<(Option<OrderBookDelta>, Option<TradeTick>), DatabentoDecodeError> {
+    let display_factor = options.price_display_factor();
+    let size_precision = options.size_precision;
+
+    if is_none_action(msg.action) {
+        return Ok((None, None));
+    }
+
+    let side = parse_order_side(msg.side);
+    if is_trade_msg(side, msg.action) {
+        if include_trades {
+            let trade = TradeTick::new(
                 instrument_id,
-                Price::from_raw(msg.price, price_precision)?,
-                Quantity::from_raw(u64::from(msg.size) * FIXED_SCALAR as u64, 0)?,
-                parse_aggressor_side(msg.side),
-                TradeId::new(itoa::Buffer::new().format(msg.sequence))?,
+                scale_price(msg.price, display_factor, price_precision)?,
+                decode_scaled_size(msg.size, 1, size_precision)?,
+                decode_aggressor_side(msg.side, options.strict_aggressor_side)?,
+                decode_trade_id(
+                    msg.sequence,
+                    msg.ts_recv,
+                    msg.hd.publisher_id,
+                    options.trade_id_policy,
+                )?,
                 msg.ts_recv,
                 ts_init,
             );
@@ -367,16 +1308,23 @@ pub fn decode_mbo_msg(
         }
     };
 
-    let order = BookOrder::new(
-        side,
-        Price::from_raw(msg.price, price_precision)?,
-        Quantity::from_raw(u64::from(msg.size) * FIXED_SCALAR as u64, 0)?,
-        msg.order_id,
-    );
+    let action = parse_book_action(msg.action)?;
+
+    // A clear resets the book (such as at the start of a snapshot, indicated by the
+    // `F_SNAPSHOT` flag) so carries no order, and its price is undefined
+    let order = match action {
+        BookAction::Clear => NULL_ORDER,
+        _ => BookOrder::new(
+            side,
+            scale_price(msg.price, display_factor, price_precision)?,
+            decode_scaled_size(msg.size, 1, size_precision)?,
+            msg.order_id,
+        ),
+    };
 
     let delta = OrderBookDelta::new(
         instrument_id,
-        parse_book_action(msg.action)?,
+        action,
         order,
         msg.flags,
         msg.sequence.into(),
@@ -387,18 +1335,106 @@ pub fn decode_mbo_msg(
     Ok((Some(delta), None))
 }
 
+/// Decodes the given MBO `msg` as per `decode_mbo_msg`, preserving the raw DBN `action`
+/// char on the returned delta (the 'F' and 'M' actions both map to `BookAction::Update`),
+/// along with the distinct [`DatabentoBookAction`].
+pub fn decode_mbo_msg_with_raw_action(
+    msg: &dbn::MboMsg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+    include_trades: bool,
+    options: &DecodeOptions,
+) -> Result<(Option<DatabentoOrderBookDelta>, Option<TradeTick>), DatabentoDecodeError> {
+    let (delta, trade) = decode_mbo_msg(
+        msg,
+        instrument_id,
+        price_precision,
+        ts_init,
+        include_trades,
+        options,
+    )?;
+    let delta = match delta {
+        Some(delta) => Some(DatabentoOrderBookDelta {
+            delta,
+            raw_action: msg.action,
+            action: parse_databento_book_action(msg.action)?,
+        }),
+        None => None,
+    };
+    Ok((delta, trade))
+}
+
+/// Decodes the DBN `size` to a `Quantity`, scaled by the `size_multiplier` for venues
+/// which report sizes in round lots rather than shares.
+///
+/// Returns an error if the scaled size overflows the raw quantity, rather than wrapping.
+pub fn decode_size(size: u32, size_multiplier: u64) -> Result<Quantity, DatabentoDecodeError> {
+    decode_scaled_size(size, size_multiplier, 0)
+}
+
+/// Decodes the DBN `size` as per [`decode_size`], for venues which allow fractional
+/// lots with the size in units of the smallest lot (e.g. 150 is 1.50 for a
+/// `size_precision` of 2).
+pub fn decode_scaled_size(
+    size: u32,
+    size_multiplier: u64,
+    size_precision: u8,
+) -> Result<Quantity, DatabentoDecodeError> {
+    check_fixed_precision(size_precision)?;
+    let scalar = 10u64.pow(u32::from(FIXED_PRECISION - size_precision));
+    let raw = u64::from(size)
+        .checked_mul(scalar)
+        .and_then(|raw| raw.checked_mul(size_multiplier))
+        .ok_or_else(|| {
+            anyhow!("Size {size} overflowed when applying `size_multiplier` {size_multiplier}")
+        })?;
+
+    Ok(Quantity::from_raw(raw, size_precision)?)
+}
+
+/// Decodes the DBN book level `raw` price, substituting zero for an undefined (empty) level.
+pub fn decode_level_price(
+    raw: i64,
+    precision: u8,
+    display_factor: i64,
+) -> Result<Price, DatabentoDecodeError> {
+    match raw {
+        dbn::UNDEF_PRICE => Ok(Price::from_raw(0, precision)?),
+        _ => scale_price(raw, display_factor, precision),
+    }
+}
+
+/// Decodes the DBN book level `size`, substituting zero for an undefined (empty) level.
+pub fn decode_level_size(
+    size: u32,
+    size_multiplier: u64,
+    size_precision: u8,
+) -> Result<Quantity, DatabentoDecodeError> {
+    match size {
+        dbn::UNDEF_ORDER_SIZE => Ok(Quantity::from_raw(0, size_precision)?),
+        _ => decode_scaled_size(size, size_multiplier, size_precision),
+    }
+}
+
 pub fn decode_trade_msg(
     msg: &dbn::TradeMsg,
     instrument_id: InstrumentId,
     price_precision: u8,
     ts_init: UnixNanos,
-) -> Result<TradeTick> {
+    options: &DecodeOptions,
+) -> Result<TradeTick, DatabentoDecodeError> {
     let trade = TradeTick::new(
         instrument_id,
-        Price::from_raw(msg.price, price_precision)?,
-        Quantity::from_raw(u64::from(msg.size) * FIXED_SCALAR as u64, 0)?,
-        parse_aggressor_side(msg.side),
-        TradeId::new(itoa::Buffer::new().format(msg.sequence))?,
+        scale_price(msg.price, options.price_display_factor(), price_precision)?,
+        decode_scaled_size(msg.size, options.size_multiplier, options.size_precision)?,
+        decode_aggressor_side(msg.side, options.strict_aggressor_side)?,
+        decode_trade_id(
+            msg.sequence,
+            msg.ts_recv,
+            msg.hd.publisher_id,
+            options.trade_id_policy,
+        )?,
         msg.ts_recv,
         ts_init,
     );
@@ -406,31 +1442,146 @@ pub fn decode_trade_msg(
     Ok(trade)
 }
 
+/// Returns the latency for the given gateway `ts_recv` and `ts_in_delta`.
+#[must_use]
+pub fn decode_latency(ts_recv: UnixNanos, ts_in_delta: i32) -> DatabentoLatency {
+    DatabentoLatency {
+        ts_exchange: ts_recv.saturating_add_signed(-i64::from(ts_in_delta)),
+        ts_recv,
+        ts_in_delta,
+    }
+}
+
+/// Decodes the DBN trade message as per [`decode_trade_msg`], along with its latency.
+pub fn decode_trade_msg_with_latency(
+    msg: &dbn::TradeMsg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+    options: &DecodeOptions,
+) -> Result<(TradeTick, DatabentoLatency), DatabentoDecodeError> {
+    let trade = decode_trade_msg(msg, instrument_id, price_precision, ts_init, options)?;
+
+    Ok((trade, decode_latency(msg.ts_recv, msg.ts_in_delta)))
+}
+
+/// Returns the size of the trade in raw fixed-point units, signed by the aggressor
+/// side (positive for a buyer, negative for a seller, and zero for no aggressor).
+#[must_use]
+pub fn signed_size(trade: &TradeTick) -> i64 {
+    let size = trade.size.raw as i64;
+    match trade.aggressor_side {
+        AggressorSide::Buyer => size,
+        AggressorSide::Seller => -size,
+        AggressorSide::NoAggressor => 0,
+    }
+}
+
+/// Provides a running volume delta (cumulative signed size) over decoded trades.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VolumeDeltaAccumulator {
+    delta: i64,
+}
+
+impl VolumeDeltaAccumulator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulates the signed size of the `trade`, returning the updated delta.
+    pub fn update(&mut self, trade: &TradeTick) -> i64 {
+        self.delta = self.delta.saturating_add(signed_size(trade));
+        self.delta
+    }
+
+    /// Returns the current volume delta in raw fixed-point units.
+    #[must_use]
+    pub fn delta(&self) -> i64 {
+        self.delta
+    }
+
+    pub fn reset(&mut self) {
+        self.delta = 0;
+    }
+}
+
+/// The policy for decoded quotes with a bid price above the ask price.
+///
+/// A consolidated BBO across venues is frequently crossed (e.g. the bid on one venue
+/// above the ask on another), which is legitimate rather than corrupt.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CrossedQuotePolicy {
+    /// Pass the crossed quote through unchanged.
+    #[default]
+    Allow,
+    /// Normalize to a zero spread by setting the ask price to the bid price.
+    Normalize,
+    /// Return an error for the record.
+    Reject,
+}
+
+/// Applies the crossed quote `policy` to the given `quote`, ignoring empty sides (zero size).
+pub fn apply_crossed_quote_policy(
+    quote: &mut QuoteTick,
+    policy: CrossedQuotePolicy,
+) -> Result<(), DatabentoDecodeError> {
+    let is_crossed =
+        quote.bid_size.raw != 0 && quote.ask_size.raw != 0 && quote.bid_price > quote.ask_price;
+    if !is_crossed {
+        return Ok(());
+    }
+
+    match policy {
+        CrossedQuotePolicy::Allow => Ok(()),
+        CrossedQuotePolicy::Normalize => {
+            quote.ask_price = quote.bid_price;
+            Ok(())
+        }
+        CrossedQuotePolicy::Reject => Err(DatabentoDecodeError::CrossedQuote {
+            instrument_id: quote.instrument_id,
+            bid_price: quote.bid_price,
+            ask_price: quote.ask_price,
+        }),
+    }
+}
+
 pub fn decode_mbp1_msg(
     msg: &dbn::Mbp1Msg,
     instrument_id: InstrumentId,
     price_precision: u8,
     ts_init: UnixNanos,
     include_trades: bool,
-) -> Result<(QuoteTick, Option<TradeTick>)> {
+    options: &DecodeOptions,
+) -> Result<(QuoteTick, Option<TradeTick>), DatabentoDecodeError> {
+    let display_factor = options.price_display_factor();
+    let size_multiplier = options.size_multiplier;
+    let size_precision = options.size_precision;
+
     let top_level = &msg.levels[0];
-    let quote = QuoteTick::new(
+    let mut quote = QuoteTick::new(
         instrument_id,
-        Price::from_raw(top_level.bid_px, price_precision)?,
-        Price::from_raw(top_level.ask_px, price_precision)?,
-        Quantity::from_raw(u64::from(top_level.bid_sz) * FIXED_SCALAR as u64, 0)?,
-        Quantity::from_raw(u64::from(top_level.ask_sz) * FIXED_SCALAR as u64, 0)?,
+        decode_level_price(top_level.bid_px, price_precision, display_factor)?,
+        decode_level_price(top_level.ask_px, price_precision, display_factor)?,
+        decode_level_size(top_level.bid_sz, size_multiplier, size_precision)?,
+        decode_level_size(top_level.ask_sz, size_multiplier, size_precision)?,
         msg.ts_recv,
         ts_init,
     )?;
+    apply_crossed_quote_policy(&mut quote, options.crossed_quote_policy)?;
 
     let maybe_trade = if include_trades && msg.action as u8 as char == 'T' {
         Some(TradeTick::new(
             instrument_id,
-            Price::from_raw(msg.price, price_precision)?,
-            Quantity::from_raw(u64::from(msg.size) * FIXED_SCALAR as u64, 0)?,
-            parse_aggressor_side(msg.side),
-            TradeId::new(itoa::Buffer::new().format(msg.sequence))?,
+            scale_price(msg.price, display_factor, price_precision)?,
+            decode_scaled_size(msg.size, size_multiplier, size_precision)?,
+            decode_aggressor_side(msg.side, options.strict_aggressor_side)?,
+            decode_trade_id(
+                msg.sequence,
+                msg.ts_recv,
+                msg.hd.publisher_id,
+                options.trade_id_policy,
+            )?,
             msg.ts_recv,
             ts_init,
         ))
@@ -441,43 +1592,146 @@ pub fn decode_mbp1_msg(
     Ok((quote, maybe_trade))
 }
 
+/// Decodes the DBN MBP-1 message as per [`decode_mbp1_msg`], along with its latency.
+pub fn decode_mbp1_msg_with_latency(
+    msg: &dbn::Mbp1Msg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+    include_trades: bool,
+    options: &DecodeOptions,
+) -> Result<(QuoteTick, Option<TradeTick>, DatabentoLatency), DatabentoDecodeError> {
+    let (quote, maybe_trade) = decode_mbp1_msg(
+        msg,
+        instrument_id,
+        price_precision,
+        ts_init,
+        include_trades,
+        options,
+    )?;
+
+    Ok((
+        quote,
+        maybe_trade,
+        decode_latency(msg.ts_recv, msg.ts_in_delta),
+    ))
+}
+
+/// Decodes the DBN MBP-1 message as per [`decode_mbp1_msg`], along with the pair of
+/// (bid, ask) deltas updating the top level of an L1 book to the quote.
+pub fn decode_mbp1_msg_with_deltas(
+    msg: &dbn::Mbp1Msg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+    include_trades: bool,
+    options: &DecodeOptions,
+) -> Result<(QuoteTick, Option<TradeTick>, [OrderBookDelta; 2]), DatabentoDecodeError> {
+    let (quote, maybe_trade) = decode_mbp1_msg(
+        msg,
+        instrument_id,
+        price_precision,
+        ts_init,
+        include_trades,
+        options,
+    )?;
+
+    // Only the ask delta ends the event, as the bid and ask update together
+    let top_delta = |side: OrderSide, flags: u8| {
+        OrderBookDelta::new(
+            instrument_id,
+            BookAction::Update,
+            BookOrder::from_quote_tick(&quote, side),
+            flags,
+            msg.sequence.into(),
+            msg.ts_recv,
+            ts_init,
+        )
+    };
+    let deltas = [
+        top_delta(OrderSide::Buy, msg.flags & !RecordFlags::F_LAST),
+        top_delta(OrderSide::Sell, msg.flags),
+    ];
+
+    Ok((quote, maybe_trade, deltas))
+}
+
+/// Checks that bid prices are strictly descending and ask prices strictly ascending
+/// through the levels of the given `msg`, ignoring undefined (empty) levels.
+pub fn validate_mbp10_level_ordering(msg: &dbn::Mbp10Msg) -> Result<(), DatabentoDecodeError> {
+    let mut last_bid_px: Option<i64> = None;
+    let mut last_ask_px: Option<i64> = None;
+
+    for (i, level) in msg.levels.iter().enumerate() {
+        if level.bid_px != dbn::UNDEF_PRICE {
+            if let Some(last_px) = last_bid_px {
+                if level.bid_px >= last_px {
+                    return Err(anyhow!(
+                        "Bid level {i} out of order, price {} not below previous level {last_px}",
+                        level.bid_px
+                    )
+                    .into());
+                }
+            }
+            last_bid_px = Some(level.bid_px);
+        }
+
+        if level.ask_px != dbn::UNDEF_PRICE {
+            if let Some(last_px) = last_ask_px {
+                if level.ask_px <= last_px {
+                    return Err(anyhow!(
+                        "Ask level {i} out of order, price {} not above previous level {last_px}",
+                        level.ask_px
+                    )
+                    .into());
+                }
+            }
+            last_ask_px = Some(level.ask_px);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn decode_mbp10_msg(
     msg: &dbn::Mbp10Msg,
     instrument_id: InstrumentId,
     price_precision: u8,
     ts_init: UnixNanos,
-) -> Result<OrderBookDepth10> {
-    let mut bids = Vec::with_capacity(DEPTH10_LEN);
-    let mut asks = Vec::with_capacity(DEPTH10_LEN);
-    let mut bid_counts = Vec::with_capacity(DEPTH10_LEN);
-    let mut ask_counts = Vec::with_capacity(DEPTH10_LEN);
+    include_trades: bool,
+    options: &DecodeOptions,
+) -> Result<(OrderBookDepth10, Option<TradeTick>), DatabentoDecodeError> {
+    if options.validate_level_ordering {
+        validate_mbp10_level_ordering(msg)?;
+    }
+
+    let display_factor = options.price_display_factor();
+    let size_multiplier = options.size_multiplier;
+    let size_precision = options.size_precision;
 
-    for level in &msg.levels {
-        let bid_order = BookOrder::new(
+    // Decode directly into fixed-size arrays to avoid allocating per message
+    let mut bids = [NULL_ORDER; DEPTH10_LEN];
+    let mut asks = [NULL_ORDER; DEPTH10_LEN];
+    let mut bid_counts = [0u32; DEPTH10_LEN];
+    let mut ask_counts = [0u32; DEPTH10_LEN];
+
+    for (i, level) in msg.levels.iter().enumerate() {
+        bids[i] = BookOrder::new(
             OrderSide::Buy,
-            Price::from_raw(level.bid_px, price_precision)?,
-            Quantity::from_raw(u64::from(level.bid_sz) * FIXED_SCALAR as u64, 0)?,
+            decode_level_price(level.bid_px, price_precision, display_factor)?,
+            decode_level_size(level.bid_sz, size_multiplier, size_precision)?,
             0,
         );
-
-        let ask_order = BookOrder::new(
+        asks[i] = BookOrder::new(
             OrderSide::Sell,
-            Price::from_raw(level.ask_px, price_precision)?,
-            Quantity::from_raw(u64::from(level.ask_sz) * FIXED_SCALAR as u64, 0)?,
+            decode_level_price(level.ask_px, price_precision, display_factor)?,
+            decode_level_size(level.ask_sz, size_multiplier, size_precision)?,
             0,
         );
-
-        bids.push(bid_order);
-        asks.push(ask_order);
-        bid_counts.push(level.bid_ct);
-        ask_counts.push(level.ask_ct);
+        bid_counts[i] = level.bid_ct;
+        ask_counts[i] = level.ask_ct;
     }
 
-    let bids: [BookOrder; DEPTH10_LEN] = bids.try_into().expect("`bids` length != 10");
-    let asks: [BookOrder; DEPTH10_LEN] = asks.try_into().expect("`asks` length != 10");
-    let bid_counts: [u32; DEPTH10_LEN] = bid_counts.try_into().expect("`bid_counts` length != 10");
-    let ask_counts: [u32; DEPTH10_LEN] = ask_counts.try_into().expect("`ask_counts` length != 10");
-
     let depth = OrderBookDepth10::new(
         instrument_id,
         bids,
@@ -490,10 +1744,186 @@ pub fn decode_mbp10_msg(
         ts_init,
     );
 
-    Ok(depth)
+    let maybe_trade = if include_trades && msg.action as u8 as char == 'T' {
+        Some(TradeTick::new(
+            instrument_id,
+            scale_price(msg.price, display_factor, price_precision)?,
+            decode_scaled_size(msg.size, size_multiplier, size_precision)?,
+            decode_aggressor_side(msg.side, options.strict_aggressor_side)?,
+            decode_trade_id(
+                msg.sequence,
+                msg.ts_recv,
+                msg.hd.publisher_id,
+                options.trade_id_policy,
+            )?,
+            msg.ts_recv,
+            ts_init,
+        ))
+    } else {
+        None
+    };
+
+    Ok((depth, maybe_trade))
+}
+
+/// Decodes the DBN MBP-10 message as a batch of deltas for a generic book engine,
+/// being a clear followed by an add for each non-empty bid then ask level.
+///
+/// As MBP levels carry no orders, each add has a synthetic order ID of its level
+/// (bids from 1 and asks from 11). Only the last delta carries the `F_LAST` flag.
+pub fn decode_mbp10_as_deltas(
+    msg: &dbn::Mbp10Msg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+    options: &DecodeOptions,
+) -> Result<Vec<OrderBookDelta>, DatabentoDecodeError> {
+    let display_factor = options.price_display_factor();
+    let flags = msg.flags & !RecordFlags::F_LAST;
+    let sequence = msg.sequence.into();
+    let delta = |action: BookAction, order: BookOrder| {
+        OrderBookDelta::new(
+            instrument_id,
+            action,
+            order,
+            flags,
+            sequence,
+            msg.ts_recv,
+            ts_init,
+        )
+    };
+
+    let mut deltas = Vec::with_capacity(1 + 2 * DEPTH10_LEN);
+    deltas.push(delta(BookAction::Clear, NULL_ORDER));
+
+    let level_delta = |side: OrderSide, price: i64, size: u32, order_id: u64| {
+        if price == dbn::UNDEF_PRICE || size == dbn::UNDEF_ORDER_SIZE {
+            return Ok(None);
+        }
+        let order = BookOrder::new(
+            side,
+            scale_price(price, display_factor, price_precision)?,
+            decode_scaled_size(size, options.size_multiplier, options.size_precision)?,
+            order_id,
+        );
+        Ok::<_, DatabentoDecodeError>(Some(delta(BookAction::Add, order)))
+    };
+
+    for (i, level) in msg.levels.iter().enumerate() {
+        deltas.extend(level_delta(
+            OrderSide::Buy,
+            level.bid_px,
+            level.bid_sz,
+            i as u64 + 1,
+        )?);
+    }
+    for (i, level) in msg.levels.iter().enumerate() {
+        deltas.extend(level_delta(
+            OrderSide::Sell,
+            level.ask_px,
+            level.ask_sz,
+            (DEPTH10_LEN + i) as u64 + 1,
+        )?);
+    }
+
+    if let Some(last) = deltas.last_mut() {
+        last.flags = msg.flags;
+    }
+
+    Ok(deltas)
+}
+
+/// Coalesces each run of consecutive snapshot depths into the last depth of the run.
+///
+/// Each MBP-10 snapshot record is a full view of the book, so a replayed snapshot
+/// is superseded by the next one and would otherwise duplicate the initial book.
+#[must_use]
+pub fn coalesce_depth10_snapshots(
+    depths: impl IntoIterator<Item = OrderBookDepth10>,
+) -> Vec<OrderBookDepth10> {
+    let mut coalesced: Vec<OrderBookDepth10> = Vec::new();
+    let mut prior_is_snapshot = false;
+
+    for depth in depths {
+        let depth_is_snapshot = is_snapshot(depth.flags);
+        if depth_is_snapshot && prior_is_snapshot {
+            // Replace the prior snapshot of the run
+            coalesced.pop();
+        }
+        prior_is_snapshot = depth_is_snapshot;
+        coalesced.push(depth);
+    }
+
+    coalesced
+}
+
+/// Provides a builder of books deeper than `OrderBookDepth10` (which is all MBP-10
+/// provides), by accumulating the deltas decoded from MBO records.
+#[derive(Clone, Debug)]
+pub struct MboDepthBuilder {
+    book: OrderBookMbo,
+}
+
+impl MboDepthBuilder {
+    #[must_use]
+    pub fn new(instrument_id: InstrumentId) -> Self {
+        Self {
+            book: OrderBookMbo::new(instrument_id),
+        }
+    }
+
+    /// Applies the MBO `delta` to the book.
+    pub fn apply(&mut self, delta: OrderBookDelta) {
+        self.book.apply_delta(delta);
+    }
+
+    /// Returns the top `depth` levels of each side of the book, with fewer levels for
+    /// a side which has fewer.
+    ///
+    /// Returns an error if `depth` exceeds [`MAX_BOOK_DEPTH`].
+    pub fn depth(&self, depth: usize) -> Result<DatabentoOrderBookDepth, DatabentoDecodeError> {
+        if depth > MAX_BOOK_DEPTH {
+            return Err(anyhow!(
+                "Book depth {depth} exceeds the maximum supported depth {MAX_BOOK_DEPTH}"
+            )
+            .into());
+        }
+
+        let book_level = |level: &Level| {
+            let size_precision = level.first().map_or(0, |order| order.size.precision);
+            Ok::<_, DatabentoDecodeError>(DatabentoBookLevel {
+                price: level.price.value,
+                size: Quantity::from_raw(level.size_raw(), size_precision)?,
+                count: level.len() as u32,
+            })
+        };
+
+        Ok(DatabentoOrderBookDepth {
+            instrument_id: self.book.instrument_id,
+            bids: self
+                .book
+                .bids()
+                .filter(|level| !level.is_empty())
+                .take(depth)
+                .map(book_level)
+                .collect::<Result<_, _>>()?,
+            asks: self
+                .book
+                .asks()
+                .filter(|level| !level.is_empty())
+                .take(depth)
+                .map(book_level)
+                .collect::<Result<_, _>>()?,
+            sequence: self.book.sequence,
+            ts_event: self.book.ts_last,
+        })
+    }
 }
 
-pub fn decode_bar_type(msg: &dbn::OhlcvMsg, instrument_id: InstrumentId) -> Result<BarType> {
+pub fn decode_bar_type(
+    msg: &dbn::OhlcvMsg,
+    instrument_id: InstrumentId,
+) -> Result<BarType, DatabentoDecodeError> {
     let bar_type = match msg.hd.rtype {
         32 => {
             // ohlcv-1s
@@ -511,16 +1941,50 @@ pub fn decode_bar_type(msg: &dbn::OhlcvMsg, instrument_id: InstrumentId) -> Resu
             // ohlcv-1d
             BarType::new(instrument_id, BAR_SPEC_1D, AggregationSource::External)
         }
-        _ => bail!(
-            "`rtype` is not a supported bar aggregation, was {}",
-            msg.hd.rtype
-        ),
+        36 => {
+            // ohlcv-eod
+            BarType::new(instrument_id, BAR_SPEC_EOD, AggregationSource::External)
+        }
+        _ => return Err(DatabentoDecodeError::UnsupportedRType(msg.hd.rtype)),
     };
 
     Ok(bar_type)
 }
 
-pub fn decode_ts_event_adjustment(msg: &dbn::OhlcvMsg) -> Result<UnixNanos> {
+/// Decodes the bar type as per [`decode_bar_type`], using the `bar_spec_override` if given.
+///
+/// The override must have the same aggregation and price type as the base spec of the
+/// record's rtype, e.g. a 5-minute spec for `ohlcv-1m` records.
+pub fn decode_bar_type_with_override(
+    msg: &dbn::OhlcvMsg,
+    instrument_id: InstrumentId,
+    bar_spec_override: Option<BarSpecification>,
+) -> Result<BarType, DatabentoDecodeError> {
+    let bar_type = decode_bar_type(msg, instrument_id)?;
+    let Some(spec) = bar_spec_override else {
+        return Ok(bar_type);
+    };
+
+    let base_spec = bar_type.spec;
+    if spec.step == 0
+        || spec.aggregation != base_spec.aggregation
+        || spec.price_type != base_spec.price_type
+    {
+        return Err(anyhow!(
+            "Invalid bar spec override {spec} for base spec {base_spec} of RType {:#04x}",
+            msg.hd.rtype,
+        )
+        .into());
+    }
+
+    Ok(BarType::new(
+        instrument_id,
+        spec,
+        bar_type.aggregation_source,
+    ))
+}
+
+pub fn decode_ts_event_adjustment(msg: &dbn::OhlcvMsg) -> Result<UnixNanos, DatabentoDecodeError> {
     let adjustment = match msg.hd.rtype {
         32 => {
             // ohlcv-1s
@@ -538,35 +2002,187 @@ pub fn decode_ts_event_adjustment(msg: &dbn::OhlcvMsg) -> Result<UnixNanos> {
             // ohlcv-1d
             BAR_CLOSE_ADJUSTMENT_1D
         }
-        _ => bail!(
-            "`rtype` is not a supported bar aggregation, was {}",
-            msg.hd.rtype
-        ),
+        36 => {
+            // ohlcv-eod
+            BAR_CLOSE_ADJUSTMENT_EOD
+        }
+        _ => return Err(DatabentoDecodeError::UnsupportedRType(msg.hd.rtype)),
     };
 
     Ok(adjustment)
 }
 
-pub fn decode_ohlcv_msg(
-    msg: &dbn::OhlcvMsg,
-    instrument_id: InstrumentId,
-    price_precision: u8,
-    ts_init: UnixNanos,
-) -> Result<Bar> {
-    let bar_type = decode_bar_type(msg, instrument_id)?;
-    let ts_event_adjustment = decode_ts_event_adjustment(msg)?;
+/// The policy for deriving the `ts_init` of decoded bars.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BarTsInitPolicy {
+    /// The later of `ts_init` and the bar open, adjusted to the bar close.
+    #[default]
+    MaxOfBoth,
+    /// The given `ts_init` as is.
+    TsInitOnly,
+    /// The bar close, regardless of the given `ts_init`.
+    BarClose,
+}
+
+/// The policy for decoding bars with sentinel (undefined) prices, such as bars
+/// for intervals with no trades.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptyBarPolicy {
+    /// Skip the bar.
+    #[default]
+    Skip,
+    /// Emit the bar with all prices equal to the prior close, or skip the bar
+    /// if there is no prior close.
+    CarryForward,
+}
+
+/// The policy for decoded bars with inconsistent prices, such as a high below the low.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BarValidationPolicy {
+    /// Pass the bar through unvalidated.
+    #[default]
+    Off,
+    /// Return an error for the record.
+    Reject,
+    /// Log a warning and skip the bar.
+    Skip,
+}
+
+/// Validates that the `high` of the given `bar` is at or above the `low`, and
+/// that the `open` and `close` are within that range.
+pub fn validate_bar(bar: &Bar) -> Result<(), DatabentoDecodeError> {
+    let reason = if bar.high < bar.low {
+        format!("high {} below low {}", bar.high, bar.low)
+    } else if bar.open < bar.low || bar.open > bar.high {
+        format!(
+            "open {} outside range [{}, {}]",
+            bar.open, bar.low, bar.high
+        )
+    } else if bar.close < bar.low || bar.close > bar.high {
+        format!(
+            "close {} outside range [{}, {}]",
+            bar.close, bar.low, bar.high
+        )
+    } else {
+        return Ok(());
+    };
+
+    Err(DatabentoDecodeError::InvalidBar {
+        bar_type: bar.bar_type,
+        reason,
+    })
+}
+
+/// Applies the bar validation `policy` to the given `bar`, returning `None` if the
+/// bar is skipped.
+pub fn apply_bar_validation_policy(
+    bar: Bar,
+    policy: BarValidationPolicy,
+) -> Result<Option<Bar>, DatabentoDecodeError> {
+    match policy {
+        BarValidationPolicy::Off => Ok(Some(bar)),
+        BarValidationPolicy::Reject => validate_bar(&bar).map(|()| Some(bar)),
+        BarValidationPolicy::Skip => match validate_bar(&bar) {
+            Ok(()) => Ok(Some(bar)),
+            Err(e) => {
+                log::warn!("Skipping bar: {e}");
+                Ok(None)
+            }
+        },
+    }
+}
+
+/// Returns whether any of the bar prices are the DBN undefined price sentinel.
+#[must_use]
+pub fn is_empty_bar(msg: &dbn::OhlcvMsg) -> bool {
+    [msg.open, msg.high, msg.low, msg.close].contains(&dbn::UNDEF_PRICE)
+}
+
+// The open to close adjustment for the bar, scaled by the step of its spec
+fn decode_bar_ts_event_adjustment(
+    msg: &dbn::OhlcvMsg,
+    bar_type: &BarType,
+) -> Result<UnixNanos, DatabentoDecodeError> {
+    Ok(decode_ts_event_adjustment(msg)? * bar_type.spec.step as u64)
+}
+
+fn decode_bar_ts_event(
+    msg: &dbn::OhlcvMsg,
+    ts_event_adjustment: UnixNanos,
+    bar_close_timestamps: bool,
+) -> UnixNanos {
+    if bar_close_timestamps {
+        // Adjust `ts_event` from open to close of bar
+        msg.hd.ts_event + ts_event_adjustment
+    } else {
+        msg.hd.ts_event
+    }
+}
 
-    // Adjust `ts_event` from open to close of bar
+fn decode_bar_ts_init(
+    msg: &dbn::OhlcvMsg,
+    ts_init: UnixNanos,
+    ts_event_adjustment: UnixNanos,
+    ts_init_policy: BarTsInitPolicy,
+) -> UnixNanos {
+    // Adjust from open to close of bar
     let ts_event = msg.hd.ts_event;
-    let ts_init = cmp::max(ts_init, ts_event) + ts_event_adjustment;
+    match ts_init_policy {
+        BarTsInitPolicy::MaxOfBoth => cmp::max(ts_init, ts_event) + ts_event_adjustment,
+        BarTsInitPolicy::TsInitOnly => ts_init,
+        BarTsInitPolicy::BarClose => ts_event + ts_event_adjustment,
+    }
+}
+
+/// Decodes the DBN bar `volume` as per [`decode_scaled_size`], in units of the smallest
+/// lot for the `size_precision` (e.g. for fractional crypto volumes).
+pub fn decode_bar_volume(
+    volume: u64,
+    size_multiplier: u64,
+    size_precision: u8,
+) -> Result<Quantity, DatabentoDecodeError> {
+    check_fixed_precision(size_precision)?;
+    let scalar = 10u64.pow(u32::from(FIXED_PRECISION - size_precision));
+    let raw = volume
+        .checked_mul(scalar)
+        .and_then(|raw| raw.checked_mul(size_multiplier))
+        .ok_or_else(|| {
+            anyhow!("Volume {volume} overflowed when applying `size_multiplier` {size_multiplier}")
+        })?;
+
+    Ok(Quantity::from_raw(raw, size_precision)?)
+}
+
+/// Decodes the DBN OHLCV message, with `ts_event` at the bar close if the
+/// `bar_close_timestamps` option is set, otherwise at the bar open.
+///
+/// The bar type uses the `bar_spec_override` option if given, for bars resampled to a
+/// custom step of the rtype's base aggregation (see [`decode_bar_type_with_override`]).
+pub fn decode_ohlcv_msg(
+    msg: &dbn::OhlcvMsg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+    options: &DecodeOptions,
+) -> Result<Bar, DatabentoDecodeError> {
+    let bar_type = decode_bar_type_with_override(msg, instrument_id, options.bar_spec_override)?;
+    let ts_event_adjustment = decode_bar_ts_event_adjustment(msg, &bar_type)?;
+    let ts_event = decode_bar_ts_event(msg, ts_event_adjustment, options.bar_close_timestamps);
+    let ts_init = decode_bar_ts_init(
+        msg,
+        ts_init,
+        ts_event_adjustment,
+        options.bar_ts_init_policy,
+    );
+    let display_factor = options.price_display_factor();
 
     let bar = Bar::new(
         bar_type,
-        Price::from_raw(msg.open / 100, price_precision)?, // TODO(adjust for display factor)
-        Price::from_raw(msg.high / 100, price_precision)?, // TODO(adjust for display factor)
-        Price::from_raw(msg.low / 100, price_precision)?,  // TODO(adjust for display factor)
-        Price::from_raw(msg.close / 100, price_precision)?, // TODO(adjust for display factor)
-        Quantity::from_raw(msg.volume * FIXED_SCALAR as u64, 0)?, // TODO(adjust for display factor)
+        scale_ohlcv_price(msg.open, display_factor, price_precision)?,
+        scale_ohlcv_price(msg.high, display_factor, price_precision)?,
+        scale_ohlcv_price(msg.low, display_factor, price_precision)?,
+        scale_ohlcv_price(msg.close, display_factor, price_precision)?,
+        decode_bar_volume(msg.volume, options.size_multiplier, options.size_precision)?,
         ts_event,
         ts_init,
     );
@@ -574,316 +2190,5726 @@ pub fn decode_ohlcv_msg(
     Ok(bar)
 }
 
-pub fn decode_record(
+/// Decodes the DBN OHLCV message as per [`decode_ohlcv_msg`], applying the
+/// `empty_bar_policy` option if any of the bar prices are undefined.
+///
+/// The `bar_validation_policy` option is applied to bars with defined prices.
+///
+/// Returns `None` if the bar is skipped.
+pub fn decode_ohlcv_msg_with_empty_bar_policy(
+    msg: &dbn::OhlcvMsg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+    options: &DecodeOptions,
+) -> Result<Option<Bar>, DatabentoDecodeError> {
+    if !is_empty_bar(msg) {
+        let bar = decode_ohlcv_msg(msg, instrument_id, price_precision, ts_init, options)?;
+        return apply_bar_validation_policy(bar, options.bar_validation_policy);
+    }
+
+    let close = match (options.empty_bar_policy, options.prior_close) {
+        (EmptyBarPolicy::CarryForward, Some(close)) => close,
+        _ => return Ok(None),
+    };
+
+    let bar_type = decode_bar_type_with_override(msg, instrument_id, options.bar_spec_override)?;
+    let ts_event_adjustment = decode_bar_ts_event_adjustment(msg, &bar_type)?;
+    let ts_event = decode_bar_ts_event(msg, ts_event_adjustment, options.bar_close_timestamps);
+    let ts_init = decode_bar_ts_init(
+        msg,
+        ts_init,
+        ts_event_adjustment,
+        options.bar_ts_init_policy,
+    );
+
+    let bar = Bar::new(
+        bar_type,
+        close,
+        close,
+        close,
+        close,
+        decode_bar_volume(msg.volume, options.size_multiplier, options.size_precision)?,
+        ts_event,
+        ts_init,
+    );
+
+    Ok(Some(bar))
+}
+
+/// Decodes the DBN statistics message, returning `None` if the statistic type is
+/// not currently mapped.
+///
+/// Mapped `stat_type` codes:
+///  - 1 `OpeningPrice`
+///  - 3 `SettlementPrice`
+///  - 4 `TradingSessionLowPrice`
+///  - 5 `TradingSessionHighPrice`
+///  - 9 `OpenInterest`
+///  - 11 `ClosePrice`
+///
+/// Skipped `stat_type` codes:
+///  - 2 `IndicativeOpeningPrice`
+///  - 6 `ClearedVolume`
+///  - 7 `LowestOffer`
+///  - 8 `HighestBid`
+///  - 10 `FixingPrice`
+///  - 12 `NetChange`
+///  - Any code unknown to DBN
+pub fn decode_statistics_msg(
+    msg: &dbn::StatMsg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+) -> Result<Option<DatabentoStatistics>, DatabentoDecodeError> {
+    let stat_type = match dbn::StatType::try_from(msg.stat_type) {
+        Ok(dbn::StatType::OpeningPrice) => DatabentoStatisticType::OpeningPrice,
+        Ok(dbn::StatType::SettlementPrice) => DatabentoStatisticType::SettlementPrice,
+        Ok(dbn::StatType::TradingSessionLowPrice) => DatabentoStatisticType::TradingSessionLowPrice,
+        Ok(dbn::StatType::TradingSessionHighPrice) => {
+            DatabentoStatisticType::TradingSessionHighPrice
+        }
+        Ok(dbn::StatType::OpenInterest) => DatabentoStatisticType::OpenInterest,
+        Ok(dbn::StatType::ClosePrice) => DatabentoStatisticType::ClosePrice,
+        _ => return Ok(None),
+    };
+
+    let price = decode_optional_price(msg.price, price_precision)?;
+    let quantity = match msg.quantity {
+        dbn::UNDEF_STAT_QUANTITY => None,
+        value => Some(Quantity::new(f64::from(value), 0)?),
+    };
+
+    Ok(Some(DatabentoStatistics {
+        instrument_id,
+        stat_type,
+        price,
+        quantity,
+        sequence: msg.sequence,
+        ts_ref: msg.ts_ref,
+        ts_event: msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    }))
+}
+
+pub fn decode_imbalance_msg(
+    msg: &dbn::ImbalanceMsg,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: UnixNanos,
+) -> Result<DatabentoImbalance, DatabentoDecodeError> {
+    Ok(DatabentoImbalance {
+        instrument_id,
+        ref_price: decode_optional_price(msg.ref_price, price_precision)?,
+        cont_book_clr_price: decode_optional_price(msg.cont_book_clr_price, price_precision)?,
+        auct_interest_clr_price: decode_optional_price(
+            msg.auct_interest_clr_price,
+            price_precision,
+        )?,
+        paired_qty: decode_size(msg.paired_qty, 1)?,
+        total_imbalance_qty: decode_size(msg.total_imbalance_qty, 1)?,
+        side: parse_order_side(msg.side),
+        auction_type: msg.auction_type,
+        auction_status: msg.auction_status,
+        significant_imbalance: msg.significant_imbalance,
+        ts_event: msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    })
+}
+
+/// Parses the DBN status `trading_status` code into a market status.
+///
+/// Mapped codes:
+///  - 1 `PreOpen`, 2 `PreCross`, 3 `Quoting`, 6 `NewPriceIndication` -> `PRE_OPEN`
+///  - 4 `Cross`, 5 `Rotation`, 7 `Trading` -> `OPEN`
+///  - 8 `Halt`, 10 `Suspend` -> `HALT`
+///  - 9 `Pause` -> `PAUSE`
+///  - 11 `PreClose` -> `PRE_CLOSE`
+///  - 12 `Close`, 13 `PostClose`, 15 `NotAvailableForTrading` -> `CLOSED`
+pub fn parse_market_status(value: u8) -> Result<MarketStatus, DatabentoDecodeError> {
+    match value {
+        1 | 2 | 3 | 6 => Ok(MarketStatus::PreOpen),
+        4 | 5 | 7 => Ok(MarketStatus::Open),
+        8 | 10 => Ok(MarketStatus::Halt),
+        9 => Ok(MarketStatus::Pause),
+        11 => Ok(MarketStatus::PreClose),
+        12 | 13 | 15 => Ok(MarketStatus::Closed),
+        _ => Err(anyhow!("Unknown `trading_status` code {value}").into()),
+    }
+}
+
+/// Parses the DBN status `halt_reason` code into a halt reason.
+///
+/// Limit up-limit down pauses (50), order imbalances (40) and market-wide circuit
+/// breakers (120-123) are volatility halts, any other non-zero code is general.
+#[must_use]
+pub fn parse_halt_reason(value: u8) -> HaltReason {
+    match value {
+        0 => HaltReason::NotHalted,
+        40 | 50 | 120..=123 => HaltReason::Volatility,
+        _ => HaltReason::General,
+    }
+}
+
+pub fn decode_status_msg(
+    msg: &dbn::StatusMsg,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+) -> Result<DatabentoInstrumentStatus, DatabentoDecodeError> {
+    Ok(DatabentoInstrumentStatus {
+        instrument_id,
+        status: parse_market_status(msg.trading_status)?,
+        halt_reason: parse_halt_reason(msg.halt_reason),
+        trading_event: msg.trading_event,
+        ts_event: msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    })
+}
+
+/// Decodes the DBN symbol mapping message, returning the DBN instrument ID, the raw
+/// symbol (`stype_out_symbol`) and the `start_ts`/`end_ts` validity window.
+pub fn decode_symbol_mapping_msg(
+    msg: &dbn::SymbolMappingMsg,
+) -> Result<(u32, Ustr, UnixNanos, UnixNanos), DatabentoDecodeError> {
+    let raw_symbol = fixed_bytes_to_str(&msg.stype_out_symbol)?;
+    let raw_symbol = decode_raw_symbol(
+        raw_symbol,
+        msg.hd.instrument_id,
+        EmptySymbolPolicy::default(),
+    )?;
+
+    Ok((msg.hd.instrument_id, raw_symbol, msg.start_ts, msg.end_ts))
+}
+
+/// Decodes the DBN version 1 symbol mapping message, returning the DBN instrument ID,
+/// the raw symbol (`stype_out_symbol`) and the `start_ts`/`end_ts` validity window.
+pub fn decode_symbol_mapping_msg_v1(
+    msg: &dbn::compat::SymbolMappingMsgV1,
+) -> Result<(u32, Ustr, UnixNanos, UnixNanos), DatabentoDecodeError> {
+    let raw_symbol = fixed_bytes_to_str(&msg.stype_out_symbol)?;
+    let raw_symbol = decode_raw_symbol(
+        raw_symbol,
+        msg.hd.instrument_id,
+        EmptySymbolPolicy::default(),
+    )?;
+
+    Ok((msg.hd.instrument_id, raw_symbol, msg.start_ts, msg.end_ts))
+}
+
+/// The policy for decoded prices which fall outside the configured [`PriceBounds`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceBoundsPolicy {
+    /// Return an error for the record.
+    Reject,
+    /// Clamp the price to the nearest bound.
+    Clamp,
+}
+
+/// Represents an inclusive band of sane prices, used to catch corrupt records
+/// carrying prices orders of magnitude off.
+#[derive(Clone, Copy, Debug)]
+pub struct PriceBounds {
+    pub min: Price,
+    pub max: Price,
+    pub policy: PriceBoundsPolicy,
+}
+
+impl PriceBounds {
+    #[must_use]
+    pub fn new(min: Price, max: Price, policy: PriceBoundsPolicy) -> Self {
+        Self { min, max, policy }
+    }
+
+    /// Applies the bounds to the given `price`, returning the (possibly clamped) price.
+    ///
+    /// Undefined prices are returned unchanged, as they are not decoded values.
+    pub fn apply(
+        &self,
+        price: Price,
+        instrument_id: InstrumentId,
+    ) -> Result<Price, DatabentoDecodeError> {
+        if price.raw == dbn::UNDEF_PRICE || (self.min..=self.max).contains(&price) {
+            return Ok(price);
+        }
+
+        match self.policy {
+            PriceBoundsPolicy::Reject => Err(anyhow!(
+                "Price {price} for {instrument_id} outside bounds [{}, {}]",
+                self.min,
+                self.max
+            )
+            .into()),
+            PriceBoundsPolicy::Clamp => {
+                let bound = if price < self.min { self.min } else { self.max };
+                Ok(Price::from_raw(bound.raw, price.precision)?)
+            }
+        }
+    }
+}
+
+/// Applies the given `bounds` to every price held by the decoded `data`, skipping
+/// empty book levels (zero size).
+pub fn apply_price_bounds(
+    data: &mut Data,
+    bounds: &PriceBounds,
+) -> Result<(), DatabentoDecodeError> {
+    match data {
+        Data::Delta(delta) => {
+            if delta.action != BookAction::Clear {
+                delta.order.price = bounds.apply(delta.order.price, delta.instrument_id)?;
+            }
+        }
+        Data::Deltas(_) => {} // Not produced by the decoders
+        Data::Depth10(depth) => {
+            for order in depth.bids.iter_mut().chain(depth.asks.iter_mut()) {
+                if order.size.raw != 0 {
+                    order.price = bounds.apply(order.price, depth.instrument_id)?;
+                }
+            }
+        }
+        Data::Quote(quote) => {
+            if quote.bid_size.raw != 0 {
+                quote.bid_price = bounds.apply(quote.bid_price, quote.instrument_id)?;
+            }
+            if quote.ask_size.raw != 0 {
+                quote.ask_price = bounds.apply(quote.ask_price, quote.instrument_id)?;
+            }
+        }
+        Data::Trade(trade) => {
+            trade.price = bounds.apply(trade.price, trade.instrument_id)?;
+        }
+        Data::Bar(bar) => {
+            let instrument_id = bar.bar_type.instrument_id;
+            bar.open = bounds.apply(bar.open, instrument_id)?;
+            bar.high = bounds.apply(bar.high, instrument_id)?;
+            bar.low = bounds.apply(bar.low, instrument_id)?;
+            bar.close = bounds.apply(bar.close, instrument_id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Represents a sort key which gives decoded records a strict total order.
+///
+/// Records sharing a `ts_event` are ordered by the venue `sequence`, then by the
+/// original `index` of the record within its source (e.g. file order).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RecordOrderKey {
+    pub ts_event: UnixNanos,
+    pub sequence: u32,
+    pub index: u64,
+}
+
+pub fn decode_record_order_key(
+    rec_ref: &dbn::RecordRef,
+    index: u64,
+) -> Result<RecordOrderKey, DatabentoDecodeError> {
+    let rtype = rec_ref.rtype()?;
+    let (ts_event, sequence) = match rtype {
+        dbn::RType::Mbo => {
+            let msg = get_checked::<dbn::MboMsg>(rec_ref)?;
+            (msg.ts_recv, msg.sequence)
+        }
+        dbn::RType::Mbp0 => {
+            let msg = get_checked::<dbn::TradeMsg>(rec_ref)?;
+            (msg.ts_recv, msg.sequence)
+        }
+        dbn::RType::Mbp1 => {
+            let msg = get_checked::<dbn::Mbp1Msg>(rec_ref)?;
+            (msg.ts_recv, msg.sequence)
+        }
+        dbn::RType::Mbp10 => {
+            let msg = get_checked::<dbn::Mbp10Msg>(rec_ref)?;
+            (msg.ts_recv, msg.sequence)
+        }
+        dbn::RType::Ohlcv1S
+        | dbn::RType::Ohlcv1M
+        | dbn::RType::Ohlcv1H
+        | dbn::RType::Ohlcv1D
+        | dbn::RType::OhlcvEod => {
+            let msg = get_checked::<dbn::OhlcvMsg>(rec_ref)?;
+            (msg.hd.ts_event, 0) // Bars carry no sequence
+        }
+        _ => {
+            return Err(DatabentoDecodeError::UnsupportedRType(
+                rec_ref.header().rtype,
+            ))
+        }
+    };
+
+    Ok(RecordOrderKey {
+        ts_event,
+        sequence,
+        index,
+    })
+}
+
+fn decode_error_context(rtype: dbn::RType, instrument_id: InstrumentId, sequence: u32) -> String {
+    format!("Error decoding {rtype:?} record for {instrument_id}, sequence={sequence}")
+}
+
+// OHLCV, imbalance and status records carry no sequence, so are identified by `ts_event`
+fn decode_ts_event_error_context(
+    rtype: dbn::RType,
+    instrument_id: InstrumentId,
+    ts_event: UnixNanos,
+) -> String {
+    format!("Error decoding {rtype:?} record for {instrument_id}, ts_event={ts_event}")
+}
+
+/// Partitions the bars within the decoded `data` by bar type, so that each interval
+/// series of a multi-interval OHLCV file is separately accessible.
+///
+/// Bars retain their decoded order within each series, and non-bar data is ignored.
+pub fn group_bars_by_type(data: impl IntoIterator<Item = Data>) -> IndexMap<BarType, Vec<Bar>> {
+    let mut bars_map: IndexMap<BarType, Vec<Bar>> = IndexMap::new();
+    for item in data {
+        if let Data::Bar(bar) = item {
+            bars_map.entry(bar.bar_type).or_default().push(bar);
+        }
+    }
+    bars_map
+}
+
+/// The source of the `ts_init` timestamp assigned to decoded data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TsInitSource {
+    /// Use the Databento capture-server-received `ts_recv` (`ts_event` for records without
+    /// one, such as bars), suited to replay.
+    #[default]
+    Recv,
+    /// Use the matching-engine-received `ts_event`.
+    Event,
+    /// Use the local wall-clock time at decode, suited to live ingestion.
+    WallClock,
+    /// Use the `ts_init` provided by the caller.
+    Provided,
+}
+
+/// Resolves the `ts_init` for the given record from the `source`.
+///
+/// Returns an error if the source is [`TsInitSource::Provided`] and no `ts_init` was given.
+pub fn decode_ts_init(
+    rec_ref: &dbn::RecordRef,
+    source: TsInitSource,
+    ts_init: Option<UnixNanos>,
+) -> Result<UnixNanos, DatabentoDecodeError> {
+    let ts_init = match source {
+        TsInitSource::Recv => match rec_ref.rtype()? {
+            dbn::RType::Mbo => get_checked::<dbn::MboMsg>(rec_ref)?.ts_recv,
+            dbn::RType::Mbp0 => get_checked::<dbn::TradeMsg>(rec_ref)?.ts_recv,
+            dbn::RType::Mbp1 => get_checked::<dbn::Mbp1Msg>(rec_ref)?.ts_recv,
+            dbn::RType::Mbp10 => get_checked::<dbn::Mbp10Msg>(rec_ref)?.ts_recv,
+            dbn::RType::Statistics => get_checked::<dbn::StatMsg>(rec_ref)?.ts_recv,
+            dbn::RType::Imbalance => get_checked::<dbn::ImbalanceMsg>(rec_ref)?.ts_recv,
+            dbn::RType::Status => get_checked::<dbn::StatusMsg>(rec_ref)?.ts_recv,
+            _ => rec_ref.header().ts_event,
+        },
+        TsInitSource::Event => rec_ref.header().ts_event,
+        TsInitSource::WallClock => get_atomic_clock_realtime().get_time_ns(),
+        TsInitSource::Provided => {
+            ts_init.ok_or_else(|| anyhow!("No `ts_init` provided for `TsInitSource::Provided`"))?
+        }
+    };
+
+    Ok(ts_init)
+}
+
+/// Returns the venue `sequence` of the given record, to reconcile decoded data
+/// against the raw feed (e.g. for gap detection), or `None` if the record has none.
+///
+/// Nautilus quotes and trades carry no sequence, so it is decoded alongside them.
+pub fn decode_record_sequence(
+    rec_ref: &dbn::RecordRef,
+) -> Result<Option<u32>, DatabentoDecodeError> {
+    let sequence = match rec_ref.rtype()? {
+        dbn::RType::Mbo => get_checked::<dbn::MboMsg>(rec_ref)?.sequence,
+        dbn::RType::Mbp0 => get_checked::<dbn::TradeMsg>(rec_ref)?.sequence,
+        dbn::RType::Mbp1 => get_checked::<dbn::Mbp1Msg>(rec_ref)?.sequence,
+        dbn::RType::Mbp10 => get_checked::<dbn::Mbp10Msg>(rec_ref)?.sequence,
+        dbn::RType::Imbalance => get_checked::<dbn::ImbalanceMsg>(rec_ref)?.sequence,
+        dbn::RType::Statistics => get_checked::<dbn::StatMsg>(rec_ref)?.sequence,
+        _ => return Ok(None),
+    };
+
+    Ok(Some(sequence))
+}
+
+/// Returns the `channel_id` of the given record, identifying the feed partition to
+/// order records and detect gaps per channel, or `None` if the record has none.
+///
+/// Only MBO records carry a channel ID in this DBN version.
+pub fn decode_record_channel_id(
+    rec_ref: &dbn::RecordRef,
+) -> Result<Option<u8>, DatabentoDecodeError> {
+    match rec_ref.rtype()? {
+        dbn::RType::Mbo => Ok(Some(get_checked::<dbn::MboMsg>(rec_ref)?.channel_id)),
+        _ => Ok(None),
+    }
+}
+
+/// Represents the Nautilus data decoded from a single DBN record.
+#[derive(Clone, Debug)]
+pub enum DecodedRecord {
+    /// The record decoded to no data, such as an excluded trade or empty bar.
+    Empty,
+    /// The record decoded to a single data item.
+    One(Data),
+    /// The record decoded to a quote and the trade which caused it.
+    Two(Data, Data),
+    /// The record was a gateway heartbeat, which carries no message to decode.
+    Heartbeat,
+    /// The record was a gateway system message, with the message text.
+    System(String),
+    /// The record was a gateway error message, with the error text.
+    Error(String),
+    /// The record was a statistic, which is not Nautilus `Data`.
+    Statistics(DatabentoStatistics),
+    /// The record was an auction imbalance, which is not Nautilus `Data`.
+    Imbalance(DatabentoImbalance),
+    /// The record was a trading status update, which is not Nautilus `Data`.
+    Status(DatabentoInstrumentStatus),
+}
+
+impl From<DecodedRecord> for (Option<Data>, Option<Data>) {
+    fn from(value: DecodedRecord) -> Self {
+        match value {
+            DecodedRecord::Empty
+            | DecodedRecord::Heartbeat
+            | DecodedRecord::System(_)
+            | DecodedRecord::Error(_)
+            | DecodedRecord::Statistics(_)
+            | DecodedRecord::Imbalance(_)
+            | DecodedRecord::Status(_) => (None, None),
+            DecodedRecord::One(data) => (Some(data), None),
+            DecodedRecord::Two(first, second) => (Some(first), Some(second)),
+        }
+    }
+}
+
+/// Decodes the given gateway `SystemMsg` or `ErrorMsg` record into a diagnostic
+/// [`DecodedRecord`], with heartbeats decoded without copying the message text.
+pub fn decode_gateway_record(
+    rec_ref: &dbn::RecordRef,
+) -> Result<DecodedRecord, DatabentoDecodeError> {
+    match rec_ref.rtype()? {
+        dbn::RType::System => {
+            let msg = get_checked::<dbn::SystemMsg>(rec_ref)?;
+            if msg.is_heartbeat() {
+                return Ok(DecodedRecord::Heartbeat);
+            }
+            Ok(DecodedRecord::System(
+                fixed_bytes_to_str(&msg.msg)?.to_string(),
+            ))
+        }
+        dbn::RType::Error => {
+            let msg = get_checked::<dbn::ErrorMsg>(rec_ref)?;
+            Ok(DecodedRecord::Error(
+                fixed_bytes_to_str(&msg.err)?.to_string(),
+            ))
+        }
+        _ => Err(DatabentoDecodeError::UnsupportedRType(
+            rec_ref.header().rtype,
+        )),
+    }
+}
+
+/// Decodes the given record into Nautilus data, as a [`DecodedRecord`] making
+/// explicit whether none, one or two data items were decoded, or a record which
+/// is not `Data` (such as a statistic, imbalance or status update).
+pub fn decode_record_data(
     rec_ref: &dbn::RecordRef,
     instrument_id: InstrumentId,
     price_precision: u8,
     ts_init: Option<UnixNanos>,
     include_trades: bool,
-) -> Result<(Option<Data>, Option<Data>)> {
-    let rtype = rec_ref.rtype().expect("Invalid `rtype`");
-    let result = match rtype {
+    options: &DecodeOptions,
+) -> Result<DecodedRecord, DatabentoDecodeError> {
+    let rtype = rec_ref.rtype()?;
+    if let Some(max_ts_event) = options.max_ts_event {
+        check_max_ts_event(rec_ref, max_ts_event)?;
+    }
+
+    let mut result = match rtype {
         dbn::RType::Mbo => {
-            let msg = rec_ref.get::<dbn::MboMsg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::MboMsg>(rec_ref)?;
             let ts_init = match ts_init {
                 Some(ts_init) => ts_init,
                 None => msg.ts_recv,
             };
-            let result =
-                decode_mbo_msg(msg, instrument_id, price_precision, ts_init, include_trades)?;
+            let result = decode_mbo_msg(
+                msg,
+                instrument_id,
+                price_precision,
+                ts_init,
+                include_trades,
+                options,
+            )
+            .map_err(|e| e.context(decode_error_context(rtype, instrument_id, msg.sequence)))?;
             match result {
-                (Some(delta), None) => (Some(Data::Delta(delta)), None),
-                (None, Some(trade)) => (Some(Data::Trade(trade)), None),
-                (None, None) => (None, None),
-                _ => bail!("Invalid `MboMsg` parsing combination"),
+                (Some(delta), _) => DecodedRecord::One(Data::Delta(delta)),
+                (None, Some(trade)) => DecodedRecord::One(Data::Trade(trade)),
+                (None, None) => DecodedRecord::Empty,
             }
         }
         dbn::RType::Mbp0 => {
-            let msg = rec_ref.get::<dbn::TradeMsg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::TradeMsg>(rec_ref)?;
             let ts_init = match ts_init {
                 Some(ts_init) => ts_init,
                 None => msg.ts_recv,
             };
-            let trade = decode_trade_msg(msg, instrument_id, price_precision, ts_init)?;
-            (Some(Data::Trade(trade)), None)
+            let trade = decode_trade_msg(msg, instrument_id, price_precision, ts_init, options)
+                .map_err(|e| e.context(decode_error_context(rtype, instrument_id, msg.sequence)))?;
+            DecodedRecord::One(Data::Trade(trade))
         }
         dbn::RType::Mbp1 => {
-            let msg = rec_ref.get::<dbn::Mbp1Msg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::Mbp1Msg>(rec_ref)?;
             let ts_init = match ts_init {
                 Some(ts_init) => ts_init,
                 None => msg.ts_recv,
             };
-            let result =
-                decode_mbp1_msg(msg, instrument_id, price_precision, ts_init, include_trades)?;
+            let result = decode_mbp1_msg(
+                msg,
+                instrument_id,
+                price_precision,
+                ts_init,
+                include_trades,
+                options,
+            )
+            .map_err(|e| e.context(decode_error_context(rtype, instrument_id, msg.sequence)))?;
             match result {
-                (quote, None) => (Some(Data::Quote(quote)), None),
-                (quote, Some(trade)) => (Some(Data::Quote(quote)), Some(Data::Trade(trade))),
+                (quote, None) => DecodedRecord::One(Data::Quote(quote)),
+                (quote, Some(trade)) => DecodedRecord::Two(Data::Quote(quote), Data::Trade(trade)),
             }
         }
         dbn::RType::Mbp10 => {
-            let msg = rec_ref.get::<dbn::Mbp10Msg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::Mbp10Msg>(rec_ref)?;
             let ts_init = match ts_init {
                 Some(ts_init) => ts_init,
                 None => msg.ts_recv,
             };
-            let depth = decode_mbp10_msg(msg, instrument_id, price_precision, ts_init)?;
-            (Some(Data::Depth10(depth)), None)
+            let result = decode_mbp10_msg(
+                msg,
+                instrument_id,
+                price_precision,
+                ts_init,
+                include_trades,
+                options,
+            )
+            .map_err(|e| e.context(decode_error_context(rtype, instrument_id, msg.sequence)))?;
+            match result {
+                (depth, None) => DecodedRecord::One(Data::Depth10(depth)),
+                (depth, Some(trade)) => {
+                    DecodedRecord::Two(Data::Depth10(depth), Data::Trade(trade))
+                }
+            }
         }
         dbn::RType::Ohlcv1S
         | dbn::RType::Ohlcv1M
         | dbn::RType::Ohlcv1H
         | dbn::RType::Ohlcv1D
         | dbn::RType::OhlcvEod => {
-            let msg = rec_ref.get::<dbn::OhlcvMsg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::OhlcvMsg>(rec_ref)?;
             let ts_init = match ts_init {
                 Some(ts_init) => ts_init,
                 None => msg.hd.ts_event,
             };
-            let bar = decode_ohlcv_msg(msg, instrument_id, price_precision, ts_init)?;
-            (Some(Data::Bar(bar)), None)
+            let bar = decode_ohlcv_msg_with_empty_bar_policy(
+                msg,
+                instrument_id,
+                price_precision,
+                ts_init,
+                options,
+            )
+            .map_err(|e| {
+                e.context(decode_ts_event_error_context(
+                    rtype,
+                    instrument_id,
+                    msg.hd.ts_event,
+                ))
+            })?;
+            match bar {
+                Some(bar) => DecodedRecord::One(Data::Bar(bar)),
+                None => DecodedRecord::Empty,
+            }
+        }
+        dbn::RType::Statistics => {
+            let msg = get_checked::<dbn::StatMsg>(rec_ref)?;
+            let ts_init = match ts_init {
+                Some(ts_init) => ts_init,
+                None => msg.ts_recv,
+            };
+            let stats = decode_statistics_msg(msg, instrument_id, price_precision, ts_init)
+                .map_err(|e| e.context(decode_error_context(rtype, instrument_id, msg.sequence)))?;
+            match stats {
+                Some(stats) => DecodedRecord::Statistics(stats),
+                None => DecodedRecord::Empty,
+            }
+        }
+        dbn::RType::Imbalance => {
+            let msg = get_checked::<dbn::ImbalanceMsg>(rec_ref)?;
+            let ts_init = match ts_init {
+                Some(ts_init) => ts_init,
+                None => msg.ts_recv,
+            };
+            let imbalance = decode_imbalance_msg(msg, instrument_id, price_precision, ts_init)
+                .map_err(|e| {
+                    e.context(decode_ts_event_error_context(
+                        rtype,
+                        instrument_id,
+                        msg.hd.ts_event,
+                    ))
+                })?;
+            DecodedRecord::Imbalance(imbalance)
+        }
+        dbn::RType::Status => {
+            let msg = get_checked::<dbn::StatusMsg>(rec_ref)?;
+            let ts_init = match ts_init {
+                Some(ts_init) => ts_init,
+                None => msg.ts_recv,
+            };
+            let status = decode_status_msg(msg, instrument_id, ts_init).map_err(|e| {
+                e.context(decode_ts_event_error_context(
+                    rtype,
+                    instrument_id,
+                    msg.hd.ts_event,
+                ))
+            })?;
+            DecodedRecord::Status(status)
+        }
+        dbn::RType::System | dbn::RType::Error => decode_gateway_record(rec_ref)?,
+        _ => {
+            return Err(DatabentoDecodeError::UnsupportedRType(
+                rec_ref.header().rtype,
+            ))
         }
-        _ => bail!("RType {:?} is not currently supported", rtype),
     };
 
+    if let Some(bounds) = options.price_bounds {
+        match &mut result {
+            DecodedRecord::Empty
+            | DecodedRecord::Heartbeat
+            | DecodedRecord::System(_)
+            | DecodedRecord::Error(_)
+            | DecodedRecord::Statistics(_)
+            | DecodedRecord::Imbalance(_)
+            | DecodedRecord::Status(_) => {}
+            DecodedRecord::One(data) => apply_price_bounds(data, &bounds)?,
+            DecodedRecord::Two(first, second) => {
+                apply_price_bounds(first, &bounds)?;
+                apply_price_bounds(second, &bounds)?;
+            }
+        }
+    }
+
     Ok(result)
 }
 
-pub fn decode_instrument_def_msg_v1(
-    msg: &dbn::compat::InstrumentDefMsgV1,
+/// Decodes the given record as per [`decode_record_data`] with the default
+/// [`DecodeOptions`], returning the data as a pair of options.
+///
+/// Records which are not `Data` (such as statistics, imbalances and status updates)
+/// decode to `(None, None)`, so use [`decode_record_data`] to receive these.
+pub fn decode_record(
+    rec_ref: &dbn::RecordRef,
     instrument_id: InstrumentId,
-    ts_init: UnixNanos,
-) -> Result<Box<dyn Instrument>> {
-    match msg.instrument_class as u8 as char {
-        'K' => Ok(Box::new(decode_equity_v1(msg, instrument_id, ts_init)?)),
-        'F' => Ok(Box::new(decode_futures_contract_v1(
-            msg,
-            instrument_id,
-            ts_init,
-        )?)),
-        'S' => Ok(Box::new(decode_futures_spread_v1(
+    price_precision: u8,
+    ts_init: Option<UnixNanos>,
+    include_trades: bool,
+) -> Result<(Option<Data>, Option<Data>), DatabentoDecodeError> {
+    decode_record_data(
+        rec_ref,
+        instrument_id,
+        price_precision,
+        ts_init,
+        include_trades,
+        &DecodeOptions::default(),
+    )
+    .map(Into::into)
+}
+
+/// Decodes the given record as per [`decode_record`], with each data item carrying the
+/// numeric DBN `instrument_id` of the record (which the Nautilus instrument ID replaces).
+pub fn decode_record_with_raw_instrument_id(
+    rec_ref: &dbn::RecordRef,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: Option<UnixNanos>,
+    include_trades: bool,
+) -> Result<(Option<DatabentoData>, Option<DatabentoData>), DatabentoDecodeError> {
+    let raw_instrument_id = rec_ref.header().instrument_id;
+    let (data1, data2) = decode_record(
+        rec_ref,
+        instrument_id,
+        price_precision,
+        ts_init,
+        include_trades,
+    )?;
+    let with_raw_id = |data| DatabentoData {
+        data,
+        raw_instrument_id,
+    };
+
+    Ok((data1.map(with_raw_id), data2.map(with_raw_id)))
+}
+
+/// Decodes the given record as per [`decode_record`], resolving the Nautilus
+/// instrument ID from the `symbol_map`.
+pub fn decode_record_with_map(
+    rec_ref: &dbn::RecordRef,
+    symbol_map: &DatabentoSymbolMap,
+    price_precision: u8,
+    ts_init: Option<UnixNanos>,
+    include_trades: bool,
+) -> Result<(Option<Data>, Option<Data>), DatabentoDecodeError> {
+    let instrument_id = symbol_map.get_instrument_id_for_record(rec_ref)?;
+
+    decode_record(
+        rec_ref,
+        instrument_id,
+        price_precision,
+        ts_init,
+        include_trades,
+    )
+}
+
+/// Decodes the first DBN record in the given buffer of the record stream (after the
+/// metadata), returning the number of bytes consumed along with the decoded data.
+///
+/// The record is read in place and its instrument ID resolved from the `symbol_map`,
+/// which the caller keeps up to date from the symbol mappings of the session. Returns
+/// [`DatabentoDecodeError::Incomplete`] if the buffer does not yet hold the full record,
+/// signalling more bytes are needed.
+pub fn decode_dbn_record_bytes(
+    buf: &[u8],
+    symbol_map: &DatabentoSymbolMap,
+    price_precision: u8,
+    ts_init: Option<UnixNanos>,
+    include_trades: bool,
+) -> Result<(usize, DecodedRecord), DatabentoDecodeError> {
+    let header_size = mem::size_of::<dbn::RecordHeader>();
+    if buf.len() < header_size {
+        return Err(DatabentoDecodeError::Incomplete {
+            needed: header_size,
+            available: buf.len(),
+        });
+    }
+
+    // The first byte of the header is the record length in 32-bit words
+    let record_size = usize::from(buf[0]) * DBN_LENGTH_MULTIPLIER;
+    if record_size < header_size {
+        return Err(DatabentoDecodeError::InvalidRecord {
+            rtype: buf[1],
+            length: record_size,
+        });
+    }
+    if buf.len() < record_size {
+        return Err(DatabentoDecodeError::Incomplete {
+            needed: record_size,
+            available: buf.len(),
+        });
+    }
+
+    // SAFETY: The buffer begins with a record header and holds the full record length
+    let rec_ref = unsafe { dbn::RecordRef::new(&buf[..record_size]) };
+    let instrument_id = symbol_map.get_instrument_id_for_record(&rec_ref)?;
+
+    let decoded = decode_record_data(
+        &rec_ref,
+        instrument_id,
+        price_precision,
+        ts_init,
+        include_trades,
+        &DecodeOptions::default(),
+    )?;
+
+    Ok((record_size, decoded))
+}
+
+/// Decodes the given record as per [`decode_record`], looking up the price precision,
+/// size precision and display factor for the instrument from the `precision_map`.
+pub fn decode_record_with_precision_map(
+    rec_ref: &dbn::RecordRef,
+    instrument_id: InstrumentId,
+    precision_map: &PrecisionMap,
+    ts_init: Option<UnixNanos>,
+    include_trades: bool,
+) -> Result<(Option<Data>, Option<Data>), DatabentoDecodeError> {
+    decode_record_data(
+        rec_ref,
+        instrument_id,
+        precision_map.get_price_precision(&instrument_id),
+        ts_init,
+        include_trades,
+        &precision_map.options_for(&instrument_id, &DecodeOptions::default()),
+    )
+    .map(Into::into)
+}
+
+/// Decodes the given record as per [`decode_record`], with the `ts_init` resolved
+/// from the given `ts_init_source`.
+pub fn decode_record_with_ts_init_source(
+    rec_ref: &dbn::RecordRef,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: Option<UnixNanos>,
+    include_trades: bool,
+    ts_init_source: TsInitSource,
+) -> Result<(Option<Data>, Option<Data>), DatabentoDecodeError> {
+    let ts_init = decode_ts_init(rec_ref, ts_init_source, ts_init)?;
+
+    decode_record(
+        rec_ref,
+        instrument_id,
+        price_precision,
+        Some(ts_init),
+        include_trades,
+    )
+}
+
+/// Returns the maximum `ts_event` bound the given number of `years` (ACT/365) after `now`.
+#[must_use]
+pub fn max_ts_event_from(now: UnixNanos, years: u64) -> UnixNanos {
+    now.saturating_add(years.saturating_mul(NANOSECONDS_IN_YEAR_ACT365))
+}
+
+/// Checks the `ts_event` of the given record does not exceed `max_ts_event`, catching
+/// corrupt records with timestamps far in the future.
+pub fn check_max_ts_event(
+    rec_ref: &dbn::RecordRef,
+    max_ts_event: UnixNanos,
+) -> Result<(), DatabentoDecodeError> {
+    let header = rec_ref.header();
+    if header.ts_event > max_ts_event {
+        return Err(anyhow!(
+            "Record {:?} for instrument ID {} has `ts_event` {} beyond maximum {max_ts_event}",
+            rec_ref.rtype()?,
+            header.instrument_id,
+            header.ts_event,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Validates the given record by decoding it as per [`decode_record_data`] and
+/// discarding the result, so a dry run rejects exactly the records the decode would
+/// (including those outside the `max_ts_event` or price bounds of the `options`).
+pub fn validate_record(
+    rec_ref: &dbn::RecordRef,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    include_trades: bool,
+    options: &DecodeOptions,
+) -> Result<(), DatabentoDecodeError> {
+    decode_record_data(
+        rec_ref,
+        instrument_id,
+        price_precision,
+        None,
+        include_trades,
+        options,
+    )
+    .map(|_| ())
+}
+
+/// Returns the record as the given type, or an error if the `rtype` does not match
+/// or the record is shorter than the type (such as the last record of a partially
+/// written file).
+pub(crate) fn get_checked<'a, T: dbn::HasRType>(
+    rec_ref: &'a dbn::RecordRef,
+) -> Result<&'a T, DatabentoDecodeError> {
+    let invalid_record = || DatabentoDecodeError::InvalidRecord {
+        rtype: rec_ref.header().rtype,
+        length: rec_ref.record_size(),
+    };
+
+    if rec_ref.record_size() < mem::size_of::<T>() {
+        return Err(invalid_record());
+    }
+    rec_ref.get::<T>().ok_or_else(invalid_record)
+}
+
+/// Decodes the given `records` as per [`decode_record`], continuing past any records
+/// which fail to decode.
+///
+/// Returns the decoded data along with the errors, so that a loader can import the
+/// good data and still report a summary of what was rejected.
+pub fn decode_records_lenient(
+    records: &[dbn::RecordRef],
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: Option<UnixNanos>,
+    include_trades: bool,
+) -> (Vec<Data>, Vec<DatabentoDecodeError>) {
+    let mut data = Vec::with_capacity(records.len());
+    let mut errors = Vec::new();
+
+    for rec_ref in records {
+        match decode_record(
+            rec_ref,
+            instrument_id,
+            price_precision,
+            ts_init,
+            include_trades,
+        ) {
+            Ok((data1, data2)) => {
+                data.extend(data1);
+                data.extend(data2);
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    (data, errors)
+}
+
+/// Returns an iterator decoding the given `records` as per [`decode_record`], with the
+/// data of each record flattened into the stream (e.g. a quote followed by its trade).
+///
+/// Records which decode to no data (e.g. MBO trades when `include_trades` is false)
+/// are skipped, and no allocation is made per record.
+pub fn decode_records<'a>(
+    records: impl Iterator<Item = dbn::RecordRef<'a>>,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: Option<UnixNanos>,
+    include_trades: bool,
+) -> impl Iterator<Item = Result<Data, DatabentoDecodeError>> {
+    let mut records = records;
+    let mut pending: Option<Data> = None;
+
+    std::iter::from_fn(move || loop {
+        if let Some(data) = pending.take() {
+            return Some(Ok(data));
+        }
+
+        let rec_ref = records.next()?;
+        match decode_record(
+            &rec_ref,
+            instrument_id,
+            price_precision,
+            ts_init,
+            include_trades,
+        ) {
+            Ok((Some(data1), data2)) => {
+                pending = data2;
+                return Some(Ok(data1));
+            }
+            Ok((None, Some(data2))) => return Some(Ok(data2)),
+            Ok((None, None)) => continue,
+            Err(e) => return Some(Err(e)),
+        }
+    })
+}
+
+/// Represents decoded Nautilus data along with the UTC date of its `ts_event`,
+/// allowing catalog writers to route data directly to the date partition.
+#[derive(Clone, Debug)]
+pub struct PartitionedData {
+    pub data: Data,
+    /// The UTC date as the number of days since the UNIX epoch.
+    pub utc_day: u32,
+}
+
+impl PartitionedData {
+    #[must_use]
+    pub fn new(data: Data) -> Self {
+        let utc_day = utc_day(data_ts_event(&data));
+        Self { data, utc_day }
+    }
+}
+
+/// Returns the UTC date of the given timestamp as the number of days since the UNIX epoch.
+#[must_use]
+pub fn utc_day(ts: UnixNanos) -> u32 {
+    (ts / NANOSECONDS_IN_DAY) as u32
+}
+
+fn data_ts_event(data: &Data) -> UnixNanos {
+    match data {
+        Data::Delta(delta) => delta.ts_event,
+        Data::Deltas(deltas) => deltas.ts_event,
+        Data::Depth10(depth) => depth.ts_event,
+        Data::Quote(quote) => quote.ts_event,
+        Data::Trade(trade) => trade.ts_event,
+        Data::Bar(bar) => bar.ts_event,
+    }
+}
+
+/// Decodes the given record as per [`decode_record`], with each decoded data
+/// partitioned by the UTC date of its own `ts_event`.
+pub fn decode_record_partitioned(
+    rec_ref: &dbn::RecordRef,
+    instrument_id: InstrumentId,
+    price_precision: u8,
+    ts_init: Option<UnixNanos>,
+    include_trades: bool,
+) -> Result<(Option<PartitionedData>, Option<PartitionedData>), DatabentoDecodeError> {
+    let (data1, data2) = decode_record(
+        rec_ref,
+        instrument_id,
+        price_precision,
+        ts_init,
+        include_trades,
+    )?;
+
+    Ok((
+        data1.map(PartitionedData::new),
+        data2.map(PartitionedData::new),
+    ))
+}
+
+/// Provides the price precision and DBN `display_factor` per instrument, derived from
+/// the instrument definitions decoded so far, falling back to a default precision for
+/// instruments with no definition.
+///
+/// Size precisions are set explicitly per instrument (as DBN definitions carry none),
+/// falling back to a default size precision.
+#[derive(Clone, Debug)]
+pub struct PrecisionMap {
+    default_price_precision: u8,
+    default_size_precision: u8,
+    price_precisions: HashMap<InstrumentId, u8>,
+    size_precisions: HashMap<InstrumentId, u8>,
+    display_factors: HashMap<InstrumentId, i64>,
+}
+
+impl PrecisionMap {
+    #[must_use]
+    pub fn new(default_price_precision: u8, default_size_precision: u8) -> Self {
+        Self {
+            default_price_precision,
+            default_size_precision,
+            price_precisions: HashMap::new(),
+            size_precisions: HashMap::new(),
+            display_factors: HashMap::new(),
+        }
+    }
+
+    /// Sets the price precision for the given `instrument_id`.
+    pub fn insert(&mut self, instrument_id: InstrumentId, price_precision: u8) {
+        self.price_precisions.insert(instrument_id, price_precision);
+    }
+
+    /// Returns whether a price precision has been set for the given `instrument_id`.
+    #[must_use]
+    pub fn contains(&self, instrument_id: &InstrumentId) -> bool {
+        self.price_precisions.contains_key(instrument_id)
+    }
+
+    /// Returns the price precision for the given `instrument_id`, or the default
+    /// if no definition has been seen.
+    #[must_use]
+    pub fn get_price_precision(&self, instrument_id: &InstrumentId) -> u8 {
+        self.price_precisions
+            .get(instrument_id)
+            .copied()
+            .unwrap_or(self.default_price_precision)
+    }
+
+    /// Sets the size precision for the given `instrument_id`.
+    pub fn insert_size_precision(&mut self, instrument_id: InstrumentId, size_precision: u8) {
+        self.size_precisions.insert(instrument_id, size_precision);
+    }
+
+    /// Returns the size precision for the given `instrument_id`, or the default if
+    /// none has been set.
+    #[must_use]
+    pub fn get_size_precision(&self, instrument_id: &InstrumentId) -> u8 {
+        self.size_precisions
+            .get(instrument_id)
+            .copied()
+            .unwrap_or(self.default_size_precision)
+    }
+
+    /// Sets the DBN `display_factor` for the given `instrument_id`, ignoring an
+    /// undefined (non-positive) factor.
+    pub fn insert_display_factor(&mut self, instrument_id: InstrumentId, display_factor: i64) {
+        if display_factor > 0 {
+            self.display_factors.insert(instrument_id, display_factor);
+        }
+    }
+
+    /// Returns the DBN `display_factor` for the given `instrument_id`, or `None` if no
+    /// definition has been seen.
+    #[must_use]
+    pub fn get_display_factor(&self, instrument_id: &InstrumentId) -> Option<i64> {
+        self.display_factors.get(instrument_id).copied()
+    }
+
+    /// Returns the given `options` with the size precision and display factor of the
+    /// given `instrument_id`.
+    #[must_use]
+    pub fn options_for(
+        &self,
+        instrument_id: &InstrumentId,
+        options: &DecodeOptions,
+    ) -> DecodeOptions {
+        DecodeOptions {
+            size_precision: self.get_size_precision(instrument_id),
+            display_factor: self
+                .get_display_factor(instrument_id)
+                .or(options.display_factor),
+            ..*options
+        }
+    }
+}
+
+impl Default for PrecisionMap {
+    /// Creates a new default [`PrecisionMap`] instance, falling back to the USD precision
+    /// for prices and whole sizes.
+    fn default() -> Self {
+        Self::new(Currency::USD().precision, 0)
+    }
+}
+
+/// Decodes the instrument definition into the Nautilus instrument for its class.
+///
+/// DBN definitions carry no margin or fee fields, so decoded instruments use the
+/// `Instrument` defaults for `margin_init`, `margin_maint`, `maker_fee` and `taker_fee`.
+pub fn decode_instrument_def_msg_v1(
+    msg: &dbn::compat::InstrumentDefMsgV1,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<Box<dyn Instrument>, DatabentoDecodeError> {
+    match msg.instrument_class as u8 as char {
+        'K' => Ok(Box::new(decode_equity_v1(msg, instrument_id, ts_init)?)),
+        'F' => Ok(Box::new(decode_futures_contract_v1(
+            msg,
+            instrument_id,
+            ts_init,
+            asset_class_override,
+        )?)),
+        'S' => Ok(Box::new(decode_futures_spread_v1(
             msg,
             instrument_id,
             ts_init,
+            asset_class_override,
         )?)),
         'C' | 'P' => Ok(Box::new(decode_options_contract_v1(
             msg,
             instrument_id,
             ts_init,
+            asset_class_override,
         )?)),
         'T' | 'M' => Ok(Box::new(decode_options_spread_v1(
             msg,
             instrument_id,
             ts_init,
+            asset_class_override,
         )?)),
-        'B' => bail!("Unsupported `instrument_class` 'B' (BOND)"),
-        'X' => bail!("Unsupported `instrument_class` 'X' (FX_SPOT)"),
-        _ => bail!(
-            "Unsupported `instrument_class` '{}'",
-            msg.instrument_class as u8 as char
-        ),
+        'B' => Ok(Box::new(decode_bond_v1(msg, instrument_id, ts_init)?)),
+        'Y' => Ok(Box::new(decode_index_instrument_v1(
+            msg,
+            instrument_id,
+            ts_init,
+        )?)),
+        'X' => Ok(Box::new(decode_currency_pair_v1(
+            msg,
+            instrument_id,
+            ts_init,
+        )?)),
+        _ => Err(DatabentoDecodeError::UnsupportedInstrumentClass(
+            msg.instrument_class as u8 as char,
+        )),
+    }
+}
+
+/// Decodes the instrument definition as per [`decode_instrument_def_msg_v1`], along
+/// with the price precision derived from its `min_price_increment` for configuring
+/// the decoding of subsequent market data.
+pub fn decode_instrument_def_msg_with_precision_v1(
+    msg: &dbn::compat::InstrumentDefMsgV1,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<(Box<dyn Instrument>, u8), DatabentoDecodeError> {
+    let instrument =
+        decode_instrument_def_msg_v1(msg, instrument_id, ts_init, asset_class_override)?;
+    let price_precision = instrument.price_precision();
+    Ok((instrument, price_precision))
+}
+
+/// Decodes the instrument definition as per [`decode_instrument_def_msg_v1`], setting
+/// the price precision of the instrument in the `precision_map`.
+pub fn decode_instrument_def_msg_with_precision_map_v1(
+    msg: &dbn::compat::InstrumentDefMsgV1,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+    precision_map: &mut PrecisionMap,
+) -> Result<Box<dyn Instrument>, DatabentoDecodeError> {
+    let (instrument, price_precision) = decode_instrument_def_msg_with_precision_v1(
+        msg,
+        instrument_id,
+        ts_init,
+        asset_class_override,
+    )?;
+    precision_map.insert(instrument_id, price_precision);
+    precision_map.insert_display_factor(instrument_id, msg.display_factor);
+    Ok(instrument)
+}
+
+/// Decodes the instrument definition as per [`decode_instrument_def_msg_v1`], along
+/// with a reference quote for seeding a book if `include_reference_quote` is true.
+pub fn decode_instrument_def_msg_with_quote_v1(
+    msg: &dbn::compat::InstrumentDefMsgV1,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+    include_reference_quote: bool,
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<(Box<dyn Instrument>, Option<QuoteTick>), DatabentoDecodeError> {
+    let instrument =
+        decode_instrument_def_msg_v1(msg, instrument_id, ts_init, asset_class_override)?;
+    if !include_reference_quote {
+        return Ok((instrument, None));
     }
+
+    let quote = decode_reference_quote(
+        instrument_id,
+        msg.trading_reference_price,
+        instrument.price_precision(),
+        msg.ts_recv,
+        ts_init,
+    )?;
+
+    Ok((instrument, quote))
 }
 
+/// Decodes the instrument definition into the Nautilus instrument for its class.
+///
+/// DBN definitions carry no margin or fee fields, so decoded instruments use the
+/// `Instrument` defaults for `margin_init`, `margin_maint`, `maker_fee` and `taker_fee`.
 pub fn decode_instrument_def_msg(
     msg: &dbn::InstrumentDefMsg,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<Box<dyn Instrument>> {
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<Box<dyn Instrument>, DatabentoDecodeError> {
     match msg.instrument_class as u8 as char {
         'K' => Ok(Box::new(decode_equity(msg, instrument_id, ts_init)?)),
         'F' => Ok(Box::new(decode_futures_contract(
             msg,
             instrument_id,
             ts_init,
+            asset_class_override,
         )?)),
         'S' => Ok(Box::new(decode_futures_spread(
             msg,
             instrument_id,
             ts_init,
+            asset_class_override,
         )?)),
         'C' | 'P' => Ok(Box::new(decode_options_contract(
             msg,
             instrument_id,
             ts_init,
+            asset_class_override,
         )?)),
         'T' | 'M' => Ok(Box::new(decode_options_spread(
             msg,
             instrument_id,
             ts_init,
+            asset_class_override,
         )?)),
-        'B' => bail!("Unsupported `instrument_class` 'B' (BOND)"),
-        'X' => bail!("Unsupported `instrument_class` 'X' (FX_SPOT)"),
-        _ => bail!(
-            "Unsupported `instrument_class` '{}'",
-            msg.instrument_class as u8 as char
-        ),
+        'B' => Ok(Box::new(decode_bond(msg, instrument_id, ts_init)?)),
+        'Y' => Ok(Box::new(decode_index_instrument(
+            msg,
+            instrument_id,
+            ts_init,
+        )?)),
+        'X' => Ok(Box::new(decode_currency_pair(msg, instrument_id, ts_init)?)),
+        _ => Err(DatabentoDecodeError::UnsupportedInstrumentClass(
+            msg.instrument_class as u8 as char,
+        )),
     }
 }
 
-pub fn decode_equity(
+/// Decodes the instrument definition as per [`decode_instrument_def_msg`], along
+/// with the price precision derived from its `min_price_increment` for configuring
+/// the decoding of subsequent market data.
+pub fn decode_instrument_def_msg_with_precision(
     msg: &dbn::InstrumentDefMsg,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<Equity> {
-    let currency = Currency::USD(); // TODO: Temporary hard coding of US equities for now
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<(Box<dyn Instrument>, u8), DatabentoDecodeError> {
+    let instrument = decode_instrument_def_msg(msg, instrument_id, ts_init, asset_class_override)?;
+    let price_precision = instrument.price_precision();
+    Ok((instrument, price_precision))
+}
 
-    Equity::new(
+/// Decodes the instrument definition as per [`decode_instrument_def_msg`], setting
+/// the price precision of the instrument in the `precision_map`.
+pub fn decode_instrument_def_msg_with_precision_map(
+    msg: &dbn::InstrumentDefMsg,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+    precision_map: &mut PrecisionMap,
+) -> Result<Box<dyn Instrument>, DatabentoDecodeError> {
+    let (instrument, price_precision) = decode_instrument_def_msg_with_precision(
+        msg,
         instrument_id,
-        instrument_id.symbol,
-        None, // No ISIN available yet
-        currency,
-        currency.precision,
-        decode_min_price_increment(msg.min_price_increment, currency)?,
-        Some(Quantity::new(msg.min_lot_size_round_lot.into(), 0)?),
-        None,        // TBD
-        None,        // TBD
-        None,        // TBD
-        None,        // TBD
-        msg.ts_recv, // More accurate and reliable timestamp
         ts_init,
-    )
+        asset_class_override,
+    )?;
+    precision_map.insert(instrument_id, price_precision);
+    precision_map.insert_display_factor(instrument_id, msg.display_factor);
+    Ok(instrument)
 }
 
-pub fn decode_futures_contract(
+/// Decodes the instrument definition as per [`decode_instrument_def_msg`], along with
+/// a reference quote for seeding a book if `include_reference_quote` is true.
+pub fn decode_instrument_def_msg_with_quote(
     msg: &dbn::InstrumentDefMsg,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<FuturesContract> {
-    let currency = Currency::USD(); // TODO: Temporary hard coding of US futures for now
-    let cfi_str = unsafe { raw_ptr_to_string(msg.cfi.as_ptr())? };
-    let underlying = unsafe { raw_ptr_to_ustr(msg.asset.as_ptr())? };
-    let (asset_class, _) = parse_cfi_iso10926(&cfi_str)?;
+    include_reference_quote: bool,
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<(Box<dyn Instrument>, Option<QuoteTick>), DatabentoDecodeError> {
+    let instrument = decode_instrument_def_msg(msg, instrument_id, ts_init, asset_class_override)?;
+    if !include_reference_quote {
+        return Ok((instrument, None));
+    }
 
-    FuturesContract::new(
+    let quote = decode_reference_quote(
         instrument_id,
-        instrument_id.symbol,
-        asset_class.unwrap_or(AssetClass::Commodity),
-        underlying,
-        msg.activation,
-        msg.expiration,
-        currency,
-        currency.precision,
-        decode_min_price_increment(msg.min_price_increment, currency)?,
-        Quantity::new(1.0, 0)?, // TBD
-        Quantity::new(1.0, 0)?, // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        msg.ts_recv,            // More accurate and reliable timestamp
+        msg.trading_reference_price,
+        instrument.price_precision(),
+        msg.ts_recv,
         ts_init,
-    )
+    )?;
+
+    Ok((instrument, quote))
 }
 
-pub fn decode_futures_spread(
-    msg: &dbn::InstrumentDefMsg,
+/// Decodes a reference quote from the definition `trading_reference_price`, returning
+/// `None` if the price is undefined.
+///
+/// DBN definitions carry a single trading reference price rather than a two-sided BBO,
+/// so the quote has equal bid and ask prices with zero sizes.
+pub fn decode_reference_quote(
     instrument_id: InstrumentId,
+    trading_reference_price: i64,
+    price_precision: u8,
+    ts_event: UnixNanos,
     ts_init: UnixNanos,
-) -> Result<FuturesSpread> {
-    let currency = Currency::USD(); // TODO: Temporary hard coding of US futures for now
-    let cfi_str = unsafe { raw_ptr_to_string(msg.cfi.as_ptr())? };
-    let underlying = unsafe { raw_ptr_to_ustr(msg.asset.as_ptr())? };
-    let strategy_type = unsafe { raw_ptr_to_ustr(msg.secsubtype.as_ptr())? };
-    let (asset_class, _) = parse_cfi_iso10926(&cfi_str)?;
+) -> Result<Option<QuoteTick>, DatabentoDecodeError> {
+    if trading_reference_price == dbn::UNDEF_PRICE {
+        return Ok(None);
+    }
 
-    FuturesSpread::new(
+    let price = scale_price(trading_reference_price, DISPLAY_FACTOR_ONE, price_precision)?;
+    let size = Quantity::from_raw(0, 0)?;
+
+    Ok(Some(QuoteTick::new(
         instrument_id,
-        instrument_id.symbol,
-        asset_class.unwrap_or(AssetClass::Commodity),
-        underlying,
+        price,
+        price,
+        size,
+        size,
+        ts_event,
+        ts_init,
+    )?))
+}
+
+pub fn decode_equity(
+    msg: &dbn::InstrumentDefMsg,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+) -> Result<Equity, DatabentoDecodeError> {
+    decode_equity_with_class(msg, instrument_id, ts_init).map(|(equity, _)| equity)
+}
+
+/// Decodes the equity as per [`decode_equity`], along with its sub-classification
+/// (e.g. common stock or ETF) parsed from the definition CFI code, which the
+/// Nautilus `Equity` does not carry.
+pub fn decode_equity_with_class(
+    msg: &dbn::InstrumentDefMsg,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+) -> Result<(Equity, Option<EquityClass>), DatabentoDecodeError> {
+    let equity_class = parse_cfi_equity_class(fixed_bytes_to_str(&msg.cfi)?);
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
+
+    let equity = Equity::new(
+        instrument_id,
+        instrument_id.symbol,
+        None, // No ISIN field in DBN 0.15.1 definitions
+        currency,
+        price_precision,
+        decode_min_price_increment(msg.min_price_increment, currency)?,
+        Some(Quantity::new(msg.min_lot_size_round_lot.into(), 0)?),
+        None, // TBD
+        None, // TBD
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    )?;
+
+    Ok((equity, equity_class))
+}
+
+pub fn decode_futures_contract(
+    msg: &dbn::InstrumentDefMsg,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<FuturesContract, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let settl_currency_str = fixed_bytes_to_str(&msg.settl_currency)?;
+    let cfi_str = fixed_bytes_to_str(&msg.cfi)?;
+    let underlying = Ustr::from(fixed_bytes_to_str(&msg.asset)?);
+    let asset_class = decode_asset_class(instrument_id, cfi_str, asset_class_override)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
+
+    Ok(FuturesContract::new(
+        instrument_id,
+        instrument_id.symbol,
+        asset_class.unwrap_or(AssetClass::Commodity),
+        underlying,
+        decode_activation(msg.activation),
+        decode_expiration(msg.expiration).unwrap_or(UnixNanos::MAX), // Never expires
+        currency,
+        parse_settlement_currency(settl_currency_str)?,
+        price_precision,
+        decode_min_price_increment(msg.min_price_increment, currency)?,
+        decode_multiplier(msg.unit_of_measure_qty, msg.contract_multiplier)?,
+        decode_lot_size(msg.min_lot_size_round_lot)?,
+        decode_optional_quantity(msg.max_trade_vol)?,
+        decode_optional_quantity(msg.min_trade_vol)?,
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    )?)
+}
+
+pub fn decode_futures_spread(
+    msg: &dbn::InstrumentDefMsg,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<FuturesSpread, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let cfi_str = fixed_bytes_to_str(&msg.cfi)?;
+    let underlying = Ustr::from(fixed_bytes_to_str(&msg.asset)?);
+    let strategy_type = Ustr::from(fixed_bytes_to_str(&msg.secsubtype)?);
+    let asset_class = decode_asset_class(instrument_id, cfi_str, asset_class_override)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
+
+    Ok(FuturesSpread::new(
+        instrument_id,
+        instrument_id.symbol,
+        asset_class.unwrap_or(AssetClass::Commodity),
+        underlying,
         strategy_type,
-        msg.activation,
-        msg.expiration,
+        decode_activation(msg.activation),
+        decode_expiration(msg.expiration).unwrap_or(UnixNanos::MAX), // Never expires
         currency,
-        currency.precision,
+        price_precision,
         decode_min_price_increment(msg.min_price_increment, currency)?,
         Quantity::new(1.0, 0)?, // TBD
         Quantity::new(1.0, 0)?, // TBD
         None,                   // TBD
         None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        msg.ts_recv,            // More accurate and reliable timestamp
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
         ts_init,
-    )
+    )?)
 }
 
 pub fn decode_options_contract(
     msg: &dbn::InstrumentDefMsg,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<OptionsContract> {
-    let currency_str = unsafe { raw_ptr_to_string(msg.currency.as_ptr())? };
-    let cfi_str = unsafe { raw_ptr_to_string(msg.cfi.as_ptr())? };
-    let asset_class_opt = match instrument_id.venue.value.as_str() {
-        "OPRA" => Some(AssetClass::Equity),
-        _ => {
-            let (asset_class, _) = parse_cfi_iso10926(&cfi_str)?;
-            asset_class
-        }
-    };
-    let underlying = unsafe { raw_ptr_to_ustr(msg.underlying.as_ptr())? };
-    let currency = Currency::from_str(&currency_str)?;
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<OptionsContract, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let cfi_str = fixed_bytes_to_str(&msg.cfi)?;
+    let asset_class = decode_asset_class(instrument_id, cfi_str, asset_class_override)?;
+    let underlying = Ustr::from(fixed_bytes_to_str(&msg.underlying)?);
+    let currency = parse_currency(currency_str)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
 
-    OptionsContract::new(
+    Ok(OptionsContract::new(
         instrument_id,
         instrument_id.symbol,
-        asset_class_opt.unwrap_or(AssetClass::Commodity),
+        asset_class.unwrap_or(AssetClass::Commodity),
         underlying,
         parse_option_kind(msg.instrument_class)?,
+        parse_cfi_exercise_style(cfi_str),
         msg.activation,
         msg.expiration,
-        Price::from_raw(msg.strike_price, currency.precision)?,
+        decode_strike_price(msg.strike_price, price_precision, instrument_id)?,
         currency,
-        currency.precision,
+        price_precision,
         decode_min_price_increment(msg.min_price_increment, currency)?,
-        Quantity::new(1.0, 0)?, // TBD
-        Quantity::new(1.0, 0)?, // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        msg.ts_recv,            // More accurate and reliable timestamp
+        decode_options_multiplier(
+            msg.unit_of_measure_qty,
+            msg.contract_multiplier,
+            msg.hd.publisher_id,
+        )?,
+        decode_lot_size(msg.min_lot_size_round_lot)?,
+        None, // TBD
+        None, // TBD
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
         ts_init,
-    )
+    )?)
 }
 
 pub fn decode_options_spread(
     msg: &dbn::InstrumentDefMsg,
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
-) -> Result<OptionsSpread> {
-    let currency_str = unsafe { raw_ptr_to_string(msg.currency.as_ptr())? };
-    let cfi_str = unsafe { raw_ptr_to_string(msg.cfi.as_ptr())? };
-    let asset_class_opt = match instrument_id.venue.value.as_str() {
-        "OPRA" => Some(AssetClass::Equity),
-        _ => {
-            let (asset_class, _) = parse_cfi_iso10926(&cfi_str)?;
-            asset_class
-        }
-    };
-    let underlying = unsafe { raw_ptr_to_ustr(msg.underlying.as_ptr())? };
-    let strategy_type = unsafe { raw_ptr_to_ustr(msg.secsubtype.as_ptr())? };
-    let currency = Currency::from_str(&currency_str)?;
+    asset_class_override: Option<&HashMap<InstrumentId, AssetClass>>,
+) -> Result<OptionsSpread, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let cfi_str = fixed_bytes_to_str(&msg.cfi)?;
+    let asset_class = decode_asset_class(instrument_id, cfi_str, asset_class_override)?;
+    let underlying = Ustr::from(fixed_bytes_to_str(&msg.underlying)?);
+    let strategy_type = Ustr::from(fixed_bytes_to_str(&msg.secsubtype)?);
+    let currency = parse_currency(currency_str)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
 
-    OptionsSpread::new(
+    Ok(OptionsSpread::new(
         instrument_id,
         instrument_id.symbol,
-        asset_class_opt.unwrap_or(AssetClass::Commodity),
+        asset_class.unwrap_or(AssetClass::Commodity),
         underlying,
         strategy_type,
         msg.activation,
         msg.expiration,
         currency,
-        currency.precision,
+        price_precision,
         decode_min_price_increment(msg.min_price_increment, currency)?,
-        Quantity::new(1.0, 0)?, // TBD
-        Quantity::new(1.0, 0)?, // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        None,                   // TBD
-        msg.ts_recv,            // More accurate and reliable timestamp
+        decode_options_multiplier(
+            msg.unit_of_measure_qty,
+            msg.contract_multiplier,
+            msg.hd.publisher_id,
+        )?,
+        decode_lot_size(msg.min_lot_size_round_lot)?,
+        None, // TBD
+        None, // TBD
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
         ts_init,
-    )
+    )?)
+}
+
+/// Decodes an FX spot definition into a `CurrencyPair`, with the base currency parsed
+/// from the `asset` field and the quote currency from the `currency` field.
+pub fn decode_currency_pair(
+    msg: &dbn::InstrumentDefMsg,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+) -> Result<CurrencyPair, DatabentoDecodeError> {
+    let base_currency_str = fixed_bytes_to_str(&msg.asset)?;
+    let base_currency = parse_currency(base_currency_str)?;
+    let quote_currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let quote_currency = parse_currency(quote_currency_str)?;
+    let price_precision = decode_price_precision(msg.min_price_increment, quote_currency);
+
+    Ok(CurrencyPair::new(
+        instrument_id,
+        instrument_id.symbol,
+        base_currency,
+        quote_currency,
+        price_precision,
+        0,
+        decode_min_price_increment(msg.min_price_increment, quote_currency)?,
+        Quantity::new(1.0, 0)?,
+        Decimal::ZERO, // No fees available
+        Decimal::ZERO, // No fees available
+        Decimal::ZERO, // No margins available
+        Decimal::ZERO, // No margins available
+        Some(decode_lot_size(msg.min_lot_size_round_lot)?),
+        decode_optional_quantity(msg.max_trade_vol)?,
+        decode_optional_quantity(msg.min_trade_vol)?,
+        None, // TBD
+        None, // TBD
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    )?)
+}
+
+/// Decodes an index definition (such as SPX) into an `IndexInstrument`, which has
+/// no lot size as indexes are not directly traded.
+pub fn decode_index_instrument(
+    msg: &dbn::InstrumentDefMsg,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+) -> Result<IndexInstrument, DatabentoDecodeError> {
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
+
+    Ok(IndexInstrument::new(
+        instrument_id,
+        instrument_id.symbol,
+        currency,
+        price_precision,
+        decode_min_price_increment(msg.min_price_increment, currency)?,
+        msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    )?)
+}
+
+pub fn decode_bond(
+    msg: &dbn::InstrumentDefMsg,
+    instrument_id: InstrumentId,
+    ts_init: UnixNanos,
+) -> Result<Bond, DatabentoDecodeError> {
+    if msg.expiration == dbn::UNDEF_TIMESTAMP {
+        return Err(anyhow!("Undefined `expiration` for bond maturity of {instrument_id}").into());
+    }
+
+    let currency_str = fixed_bytes_to_str(&msg.currency)?;
+    let currency = parse_currency_or_usd_default(currency_str);
+    let price_precision = decode_price_precision(msg.min_price_increment, currency);
+
+    Ok(Bond::new(
+        instrument_id,
+        instrument_id.symbol,
+        None, // No ISIN field in DBN 0.15.1 definitions
+        currency,
+        None, // No coupon available
+        decode_optional_timestamp(msg.activation),
+        msg.expiration,
+        decode_face_value(msg.unit_of_measure_qty)?,
+        price_precision,
+        decode_min_price_increment(msg.min_price_increment, currency)?,
+        Some(decode_lot_size(msg.min_lot_size_round_lot)?),
+        decode_optional_quantity(msg.max_trade_vol)?,
+        decode_optional_quantity(msg.min_trade_vol)?,
+        decode_optional_price(msg.high_limit_price, price_precision)?,
+        decode_optional_price(msg.low_limit_price, price_precision)?,
+        msg.ts_recv, // More accurate and reliable timestamp
+        ts_init,
+    )?)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use dbn::decode::{dbn::Decoder, DbnMetadata, DecodeStream};
+    use nautilus_model::{identifiers::venue::Venue, orderbook::book_mbp::OrderBookMbp};
+    use rstest::rstest;
+    use streaming_iterator::StreamingIterator;
+
+    use super::*;
+
+    fn test_data_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/test_data/databento")
+    }
+
+    fn load_first_record<T>(filename: &str) -> T
+    where
+        T: dbn::Record + dbn::HasRType + Clone + 'static,
+    {
+        let decoder = Decoder::from_zstd_file(test_data_path().join(filename)).unwrap();
+        let mut dbn_stream = decoder.decode_stream::<T>();
+        dbn_stream.next().unwrap().clone()
+    }
+
+    fn load_records<T>(filename: &str) -> Vec<T>
+    where
+        T: dbn::Record + dbn::HasRType + Clone + 'static,
+    {
+        let decoder = Decoder::from_zstd_file(test_data_path().join(filename)).unwrap();
+        let mut dbn_stream = decoder.decode_stream::<T>();
+        let mut msgs = Vec::new();
+        while let Some(msg) = dbn_stream.next() {
+            msgs.push(msg.clone());
+        }
+        msgs
+    }
+
+    #[rstest]
+    fn test_decode_record_price_error_names_instrument_and_sequence() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        // Precision beyond `FIXED_PRECISION` forces `Price::from_raw` to fail
+        let err = decode_record(&rec_ref, instrument_id, 10, None, false).unwrap_err();
+        let message = err.to_string();
+
+        assert!(message.contains("ESH1.GLBX"));
+        assert!(message.contains(&format!("sequence={}", msg.sequence)));
+        assert!(format!("{err:#}").contains("`precision` was greater than the maximum"));
+    }
+
+    #[rstest]
+    fn test_decode_record_order_key_shared_timestamp() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let mut msg1 = msg.clone();
+        msg1.sequence = 5;
+        let mut msg2 = msg.clone();
+        msg2.sequence = 3;
+        let mut msg3 = msg.clone();
+        msg3.sequence = 3;
+
+        let mut keys: Vec<RecordOrderKey> = [&msg1, &msg2, &msg3]
+            .iter()
+            .enumerate()
+            .map(|(i, m)| decode_record_order_key(&dbn::RecordRef::from(*m), i as u64).unwrap())
+            .collect();
+        keys.sort();
+
+        assert!(keys.iter().all(|k| k.ts_event == msg.ts_recv));
+        let order: Vec<(u32, u64)> = keys.iter().map(|k| (k.sequence, k.index)).collect();
+        assert_eq!(order, vec![(3, 1), (3, 2), (5, 0)]);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(2)]
+    #[case(4)]
+    #[case(9)]
+    fn test_scale_price_unit_display_factor(#[case] precision: u8) {
+        let price = scale_price(3_720_250_000_000, DISPLAY_FACTOR_ONE, precision).unwrap();
+        assert_eq!(price.raw, 3_720_250_000_000);
+        assert_eq!(price.precision, precision);
+    }
+
+    #[rstest]
+    #[case(10_000_000, 37_200_000_000)] // 0.01, 37.2025 rounded
+    #[case(500_000_000, 1_860_130_000_000)] // 0.5, 1860.125 rounded half away from zero
+    #[case(100_000_000_000, 372_025_000_000_000)] // 100
+    fn test_scale_price_display_factor(#[case] display_factor: i64, #[case] expected: i64) {
+        let price = scale_price(3_720_250_000_000, display_factor, 2).unwrap();
+        assert_eq!(price.raw, expected);
+    }
+
+    #[rstest]
+    fn test_scale_price_negative() {
+        // -0.015 rounds half away from zero, symmetrically with positive prices
+        let price = scale_price(-1_500_000_000, 10_000_000, 2).unwrap();
+        assert_eq!(price.raw, -20_000_000);
+    }
+
+    #[rstest]
+    fn test_scale_price_rounds_to_precision() {
+        let price = scale_price(3_720_254_000_000, DISPLAY_FACTOR_ONE, 2).unwrap();
+        assert_eq!(price, Price::from("3720.25"));
+        assert_eq!(price.raw, 3_720_250_000_000);
+    }
+
+    #[rstest]
+    fn test_scale_price_undefined() {
+        let price = scale_price(dbn::UNDEF_PRICE, 10_000_000, 2).unwrap();
+        assert_eq!(price.raw, dbn::UNDEF_PRICE);
+    }
+
+    #[rstest]
+    fn test_scale_price_invalid_precision() {
+        assert!(scale_price(1_000_000_000, DISPLAY_FACTOR_ONE, 10).is_err());
+    }
+
+    #[rstest]
+    fn test_scale_price_display_factor_overflow() {
+        assert!(scale_price(i64::MAX / 2, 100_000_000_000, 2).is_err());
+    }
+
+    #[rstest]
+    fn test_time_to_expiry_90_days() {
+        let now = 1_704_067_200_000_000_000; // 2024-01-01T00:00:00Z
+        let expiration = now + 90 * BAR_CLOSE_ADJUSTMENT_1D;
+        let years = time_to_expiry(expiration, now).unwrap();
+        assert!((years - 90.0 / 365.0).abs() < 1e-12);
+    }
+
+    #[rstest]
+    fn test_time_to_expiry_expired() {
+        let now = 1_704_067_200_000_000_000; // 2024-01-01T00:00:00Z
+        let expiration = now - BAR_CLOSE_ADJUSTMENT_1D;
+        let years = time_to_expiry(expiration, now).unwrap();
+        assert!(years.abs() < f64::EPSILON);
+    }
+
+    #[rstest]
+    fn test_time_to_expiry_undefined() {
+        assert!(time_to_expiry(dbn::UNDEF_TIMESTAMP, 0).is_none());
+    }
+
+    #[rstest]
+    fn test_decode_mbp10_msg_validate_ordering_ok() {
+        let mut msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        msg.levels[9].bid_px = dbn::UNDEF_PRICE; // Empty levels are ignored
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (depth, _) = decode_mbp10_msg(
+            &msg,
+            instrument_id,
+            2,
+            msg.ts_recv,
+            false,
+            &DecodeOptions {
+                validate_level_ordering: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(depth.bids[0].price, Price::from("3720.25"));
+        assert_eq!(depth.asks[0].price, Price::from("3720.50"));
+    }
+
+    #[rstest]
+    fn test_decode_mbp10_msg_validate_ordering_mispriced_level() {
+        let mut msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        msg.levels[3].bid_px = msg.levels[1].bid_px + 1_000_000_000;
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        // Not validated by default
+        assert!(decode_mbp10_msg(
+            &msg,
+            instrument_id,
+            2,
+            msg.ts_recv,
+            false,
+            &DecodeOptions::default(),
+        )
+        .is_ok());
+
+        let err = decode_mbp10_msg(
+            &msg,
+            instrument_id,
+            2,
+            msg.ts_recv,
+            false,
+            &DecodeOptions {
+                validate_level_ordering: true,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("Bid level 3 out of order"));
+    }
+
+    #[rstest]
+    #[case('A', OrderSide::Sell, AggressorSide::Seller)]
+    #[case('B', OrderSide::Buy, AggressorSide::Buyer)]
+    #[case('N', OrderSide::NoOrderSide, AggressorSide::NoAggressor)]
+    fn test_parse_side_consistency(
+        #[case] side: char,
+        #[case] expected_order_side: OrderSide,
+        #[case] expected_aggressor_side: AggressorSide,
+    ) {
+        let c = side as c_char;
+        assert_eq!(parse_order_side(c), expected_order_side);
+        assert_eq!(parse_aggressor_side(c), expected_aggressor_side);
+    }
+
+    #[rstest]
+    #[case('A', AggressorSide::Seller)]
+    #[case('B', AggressorSide::Buyer)]
+    #[case('N', AggressorSide::NoAggressor)]
+    fn test_try_parse_aggressor_side(#[case] side: char, #[case] expected: AggressorSide) {
+        assert_eq!(try_parse_aggressor_side(side as c_char).unwrap(), expected);
+    }
+
+    #[rstest]
+    fn test_try_parse_aggressor_side_invalid() {
+        assert!(try_parse_aggressor_side('X' as c_char).is_err());
+        assert!(try_parse_aggressor_side(0).is_err());
+    }
+
+    #[rstest]
+    fn test_decode_trade_msg_strict_aggressor_side() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.side = 'X' as c_char;
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let lenient =
+            decode_trade_msg(&msg, instrument_id, 2, 0, &DecodeOptions::default()).unwrap();
+        let strict = decode_trade_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                strict_aggressor_side: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(lenient.aggressor_side, AggressorSide::NoAggressor);
+        assert!(strict.is_err());
+    }
+
+    #[rstest]
+    fn test_decode_mbo_msg_trade_strict_aggressor_side() {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.action = 'T' as c_char;
+        msg.side = 'X' as c_char;
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let strict_options = DecodeOptions {
+            strict_aggressor_side: true,
+            ..Default::default()
+        };
+
+        let (_, lenient) =
+            decode_mbo_msg(&msg, instrument_id, 2, 0, true, &DecodeOptions::default()).unwrap();
+        let strict = decode_mbo_msg(&msg, instrument_id, 2, 0, true, &strict_options);
+
+        assert_eq!(lenient.unwrap().aggressor_side, AggressorSide::NoAggressor);
+        assert!(strict.is_err());
+    }
+
+    #[rstest]
+    fn test_decode_mbp10_msg_trade_strict_aggressor_side() {
+        let mut msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        msg.action = 'T' as c_char;
+        msg.side = 'X' as c_char;
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let strict_options = DecodeOptions {
+            strict_aggressor_side: true,
+            ..Default::default()
+        };
+
+        let (_, lenient) =
+            decode_mbp10_msg(&msg, instrument_id, 2, 0, true, &DecodeOptions::default()).unwrap();
+        let strict = decode_mbp10_msg(&msg, instrument_id, 2, 0, true, &strict_options);
+
+        assert_eq!(lenient.unwrap().aggressor_side, AggressorSide::NoAggressor);
+        assert!(strict.is_err());
+    }
+
+    #[rstest]
+    fn test_decode_record_price_bounds_in_band() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let bounds = PriceBounds::new(
+            Price::from("3000.00"),
+            Price::from("4000.00"),
+            PriceBoundsPolicy::Reject,
+        );
+
+        let data = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions {
+                price_bounds: Some(bounds),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        match data {
+            DecodedRecord::One(Data::Trade(trade)) => {
+                assert_eq!(trade.price, Price::from("3720.25"))
+            }
+            _ => panic!("Expected `TradeTick`, was {data:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_record_price_bounds_out_of_band_reject() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.price *= 1_000;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let bounds = PriceBounds::new(
+            Price::from("3000.00"),
+            Price::from("4000.00"),
+            PriceBoundsPolicy::Reject,
+        );
+
+        let err = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions {
+                price_bounds: Some(bounds),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Price 3720250.00 for ESH1.GLBX outside bounds [3000.00, 4000.00]"
+        );
+    }
+
+    #[rstest]
+    fn test_decode_record_price_bounds_out_of_band_clamp() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.price *= 1_000;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let bounds = PriceBounds::new(
+            Price::from("3000.00"),
+            Price::from("4000.00"),
+            PriceBoundsPolicy::Clamp,
+        );
+
+        let data = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions {
+                price_bounds: Some(bounds),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        match data {
+            DecodedRecord::One(Data::Trade(trade)) => {
+                assert_eq!(trade.price, Price::from("4000.00"))
+            }
+            _ => panic!("Expected `TradeTick`, was {data:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_tick_size_monitor_change() {
+        let mut monitor = TickSizeMonitor::new();
+        let instrument_id = InstrumentId::from("AAPL.XNAS");
+
+        assert!(monitor
+            .update(instrument_id, 10_000_000, 1)
+            .unwrap()
+            .is_none());
+        assert!(monitor
+            .update(instrument_id, 10_000_000, 2)
+            .unwrap()
+            .is_none());
+
+        let change = monitor
+            .update(instrument_id, 5_000_000, 3)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(change.instrument_id, instrument_id);
+        assert_eq!(change.old_price_increment, Some(Price::from("0.01")));
+        assert_eq!(change.new_price_increment, Price::from("0.005"));
+        assert_eq!(change.new_price_increment.precision, 3);
+        assert_eq!(change.ts_event, 3);
+    }
+
+    #[rstest]
+    fn test_tick_size_monitor_sentinel_transitions() {
+        let mut monitor = TickSizeMonitor::new();
+        let instrument_id = InstrumentId::from("AAPL.XNAS");
+
+        assert!(monitor
+            .update(instrument_id, i64::MAX, 1)
+            .unwrap()
+            .is_none());
+
+        let change = monitor
+            .update(instrument_id, 10_000_000, 2)
+            .unwrap()
+            .unwrap();
+        assert_eq!(change.old_price_increment, None);
+        assert_eq!(change.new_price_increment, Price::from("0.01"));
+
+        // An undefined increment does not replace the defined one
+        assert!(monitor
+            .update(instrument_id, i64::MAX, 3)
+            .unwrap()
+            .is_none());
+        assert!(monitor
+            .update(instrument_id, 10_000_000, 4)
+            .unwrap()
+            .is_none());
+    }
+
+    #[rstest]
+    fn test_decode_raw_symbol() {
+        let raw_symbol = decode_raw_symbol("ESM4", 6819, EmptySymbolPolicy::Error).unwrap();
+        assert_eq!(raw_symbol, Ustr::from("ESM4"));
+    }
+
+    #[rstest]
+    fn test_decode_raw_symbol_empty_error() {
+        let mut msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        msg.raw_symbol = Default::default();
+        let raw_symbol = fixed_bytes_to_str(&msg.raw_symbol).unwrap();
+
+        let err = decode_raw_symbol(raw_symbol, msg.hd.instrument_id, EmptySymbolPolicy::Error)
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Empty `raw_symbol` for definition with instrument ID 6819"
+        );
+    }
+
+    #[rstest]
+    fn test_decode_raw_symbol_empty_use_instrument_id() {
+        let raw_symbol = decode_raw_symbol("", 6819, EmptySymbolPolicy::UseInstrumentId).unwrap();
+        assert_eq!(raw_symbol, Ustr::from("6819"));
+    }
+
+    #[rstest]
+    fn test_decode_trade_msg_size_multiplier() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let trade = decode_trade_msg(
+            &msg,
+            instrument_id,
+            2,
+            msg.ts_recv,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(trade.size, Quantity::from(5));
+
+        let trade = decode_trade_msg(
+            &msg,
+            instrument_id,
+            2,
+            msg.ts_recv,
+            &DecodeOptions {
+                size_multiplier: 100,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(trade.size, Quantity::from(500));
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_size_multiplier() {
+        let msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (quote, _) = decode_mbp1_msg(
+            &msg,
+            instrument_id,
+            2,
+            msg.ts_recv,
+            false,
+            &DecodeOptions {
+                size_multiplier: 100,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(quote.bid_size, Quantity::from(2_400));
+        assert_eq!(quote.ask_size, Quantity::from(1_100));
+    }
+
+    #[rstest]
+    fn test_scale_price_sub_units() {
+        // Quoted in cents, so a display factor of 0.01 gives the price in dollars
+        let price = scale_price(372_025_000_000_000, 10_000_000, 4).unwrap();
+        assert_eq!(price, Price::from("3720.2500"));
+    }
+
+    #[rstest]
+    fn test_decode_trade_msg_display_factor() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let trade = decode_trade_msg(
+            &msg,
+            instrument_id,
+            4,
+            msg.ts_recv,
+            &DecodeOptions {
+                display_factor: Some(10_000_000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(trade.price, Price::from("37.2025"));
+        assert_eq!(trade.size, Quantity::from(5));
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_display_factor() {
+        let msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let decode = |display_factor: i64| {
+            decode_mbp1_msg(
+                &msg,
+                instrument_id,
+                4,
+                msg.ts_recv,
+                false,
+                &DecodeOptions {
+                    display_factor: Some(display_factor),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .0
+        };
+
+        let unscaled = decode(DISPLAY_FACTOR_ONE);
+        let quote = decode(10_000_000); // 0.01
+
+        assert_eq!(quote.bid_price.raw, unscaled.bid_price.raw / 100);
+        assert_eq!(quote.ask_price.raw, unscaled.ask_price.raw / 100);
+        assert_eq!(quote.bid_price.precision, 4);
+        assert_eq!(quote.bid_size, unscaled.bid_size);
+        assert_eq!(quote.ask_size, unscaled.ask_size);
+    }
+
+    #[rstest]
+    fn test_group_bars_by_type() {
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let mut msgs = load_records::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msgs.extend(load_records::<dbn::OhlcvMsg>("ohlcv-1m.dbn.zst"));
+
+        let mut data = Vec::new();
+        for msg in &msgs {
+            let rec_ref = dbn::RecordRef::from(msg);
+            let (data1, _) = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap();
+            data.extend(data1);
+        }
+
+        let bars_map = group_bars_by_type(data);
+
+        let bar_type_1s = BarType::new(instrument_id, BAR_SPEC_1S, AggregationSource::External);
+        let bar_type_1m = BarType::new(instrument_id, BAR_SPEC_1M, AggregationSource::External);
+        assert_eq!(bars_map.len(), 2);
+        assert_eq!(bars_map[&bar_type_1s].len(), 2);
+        assert_eq!(bars_map[&bar_type_1m].len(), 2);
+        assert!(bars_map[&bar_type_1s]
+            .iter()
+            .all(|bar| bar.bar_type == bar_type_1s));
+        assert!(bars_map[&bar_type_1s][0].ts_event < bars_map[&bar_type_1s][1].ts_event);
+    }
+
+    fn str_to_c_chars<const N: usize>(value: &str) -> [c_char; N] {
+        let mut chars = [0 as c_char; N];
+        for (i, b) in value.bytes().enumerate() {
+            chars[i] = b as c_char;
+        }
+        chars
+    }
+
+    #[rstest]
+    fn test_decode_equity_v1_gbp_currency() {
+        let mut msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        msg.currency = str_to_c_chars("GBP");
+        let instrument_id = InstrumentId::from("VOD.XLON");
+
+        let equity = decode_equity_v1(&msg, instrument_id, 0).unwrap();
+
+        assert_eq!(equity.quote_currency(), Currency::GBP());
+        assert_eq!(equity.price_precision(), Currency::GBP().precision);
+        assert_eq!(
+            equity.price_increment().precision,
+            Currency::GBP().precision
+        );
+    }
+
+    #[rstest]
+    fn test_decode_equity_v1_empty_currency_defaults_to_usd() {
+        let msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        let instrument_id = InstrumentId::from("MSFT.XNAS");
+
+        let equity = decode_equity_v1(&msg, instrument_id, 0).unwrap();
+
+        assert_eq!(equity.quote_currency(), Currency::USD());
+    }
+
+    #[rstest]
+    fn test_parse_currency_or_usd_default_unknown() {
+        assert_eq!(parse_currency_or_usd_default("???"), Currency::USD());
+        assert_eq!(parse_currency_or_usd_default("JPY"), Currency::JPY());
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_price_precision_from_tick() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.price_precision(), 2);
+        assert_eq!(future.price_increment(), Price::from("0.25"));
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_price_precision_fx_tick() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.min_price_increment = 100_000; // 0.0001
+        let instrument_id = InstrumentId::from("6EM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.price_precision(), 4);
+        assert_eq!(future.price_increment(), Price::from("0.0001"));
+    }
+
+    #[rstest]
+    #[case(250_000_000, 2)]
+    #[case(100_000, 4)]
+    #[case(1_000_000_000, 0)]
+    #[case(0, 2)]
+    #[case(i64::MAX, 2)]
+    fn test_decode_price_precision(#[case] min_price_increment: i64, #[case] expected: u8) {
+        assert_eq!(
+            decode_price_precision(min_price_increment, Currency::USD()),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_decode_record_partitioned_either_side_of_midnight() {
+        let midnight = 1_609_200_000_000_000_000; // 2020-12-29T00:00:00Z
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let mut before = msg.clone();
+        before.ts_recv = midnight - 1;
+        let mut after = msg;
+        after.ts_recv = midnight;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (data_before, _) = decode_record_partitioned(
+            &dbn::RecordRef::from(&before),
+            instrument_id,
+            2,
+            Some(0),
+            false,
+        )
+        .unwrap();
+        let (data_after, _) = decode_record_partitioned(
+            &dbn::RecordRef::from(&after),
+            instrument_id,
+            2,
+            Some(0),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(data_before.unwrap().utc_day, 18_624);
+        assert_eq!(data_after.unwrap().utc_day, 18_625);
+    }
+
+    #[rstest]
+    fn test_decode_record_partitioned_bar_uses_bar_open() {
+        let msgs = load_records::<dbn::OhlcvMsg>("ohlcv-1h.dbn.zst");
+        let mut msg = msgs[0].clone();
+        msg.hd.ts_event = 1_609_196_400_000_000_000; // 2020-12-28T23:00:00Z
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (data, _) =
+            decode_record_partitioned(&dbn::RecordRef::from(&msg), instrument_id, 2, None, false)
+                .unwrap();
+
+        // Bar closes at midnight, but remains in the partition of its `ts_event`
+        let data = data.unwrap();
+        assert_eq!(data.utc_day, 18_624);
+        assert!(matches!(&data.data, Data::Bar(bar) if bar.ts_init == 1_609_200_000_000_000_000));
+    }
+
+    #[rstest]
+    fn test_utc_day() {
+        assert_eq!(utc_day(0), 0);
+        assert_eq!(utc_day(NANOSECONDS_IN_DAY - 1), 0);
+        assert_eq!(utc_day(NANOSECONDS_IN_DAY), 1);
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_default_display_factor() {
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let bar = decode_ohlcv_msg(&msg, instrument_id, 2, 0, &DecodeOptions::default()).unwrap();
+
+        assert_eq!(bar.open, Price::from("3720.25"));
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_display_factor() {
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let bar = decode_ohlcv_msg(
+            &msg,
+            instrument_id,
+            8,
+            0,
+            &DecodeOptions {
+                display_factor: Some(1_000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // Raw open of 372025000000000 scaled by a fixed-point display factor of 1e-6,
+        // then by the OHLCV price divisor
+        assert_eq!(bar.open.raw, 3_720_250);
+        assert_eq!(bar.open, Price::from("0.00372025"));
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_same_prices_with_and_without_definition() {
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let without_def =
+            decode_ohlcv_msg(&msg, instrument_id, 2, 0, &DecodeOptions::default()).unwrap();
+        let with_def = decode_ohlcv_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                display_factor: Some(DISPLAY_FACTOR_ONE),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(without_def.open, Price::from("3720.25"));
+        assert_eq!(with_def.open, without_def.open);
+        assert_eq!(with_def.high, without_def.high);
+        assert_eq!(with_def.low, without_def.low);
+        assert_eq!(with_def.close, without_def.close);
+    }
+
+    #[rstest]
+    fn test_scale_ohlcv_price_undefined() {
+        let price = scale_ohlcv_price(dbn::UNDEF_PRICE, DISPLAY_FACTOR_ONE, 2).unwrap();
+        assert_eq!(price.raw, dbn::UNDEF_PRICE);
+    }
+
+    #[rstest]
+    #[case(BarTsInitPolicy::MaxOfBoth, 0, 1_609_160_401_000_000_000)]
+    #[case(
+        BarTsInitPolicy::MaxOfBoth,
+        1_609_160_405_000_000_000,
+        1_609_160_406_000_000_000
+    )]
+    #[case(BarTsInitPolicy::TsInitOnly, 0, 0)]
+    #[case(
+        BarTsInitPolicy::TsInitOnly,
+        1_609_160_405_000_000_000,
+        1_609_160_405_000_000_000
+    )]
+    #[case(BarTsInitPolicy::BarClose, 0, 1_609_160_401_000_000_000)]
+    #[case(
+        BarTsInitPolicy::BarClose,
+        1_609_160_405_000_000_000,
+        1_609_160_401_000_000_000
+    )]
+    fn test_decode_ohlcv_msg_ts_init_policy(
+        #[case] policy: BarTsInitPolicy,
+        #[case] ts_init: UnixNanos,
+        #[case] expected: UnixNanos,
+    ) {
+        // 1-second bar opening at 1609160400000000000
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let bar = decode_ohlcv_msg(
+            &msg,
+            instrument_id,
+            2,
+            ts_init,
+            &DecodeOptions {
+                bar_ts_init_policy: policy,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(bar.ts_event, 1_609_160_400_000_000_000);
+        assert_eq!(bar.ts_init, expected);
+    }
+
+    #[rstest]
+    #[case(true, 1_609_160_460_000_000_000)]
+    #[case(false, 1_609_160_400_000_000_000)]
+    fn test_decode_ohlcv_msg_bar_close_timestamps(
+        #[case] bar_close_timestamps: bool,
+        #[case] expected: UnixNanos,
+    ) {
+        // 1-minute bar opening at 1609160400000000000
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.hd.rtype = dbn::rtype::OHLCV_1M;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let bar = decode_ohlcv_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                bar_ts_init_policy: BarTsInitPolicy::BarClose,
+                bar_close_timestamps,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(bar.ts_event, expected);
+        assert_eq!(bar.ts_init, 1_609_160_460_000_000_000);
+    }
+
+    #[rstest]
+    #[case(true, 1_609_160_460_000_000_000)]
+    #[case(false, 1_609_160_400_000_000_000)]
+    fn test_decode_record_bar_close_timestamps(
+        #[case] bar_close_timestamps: bool,
+        #[case] expected: UnixNanos,
+    ) {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.hd.rtype = dbn::rtype::OHLCV_1M;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let data = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions {
+                bar_close_timestamps,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        match data {
+            DecodedRecord::One(Data::Bar(bar)) => assert_eq!(bar.ts_event, expected),
+            _ => panic!("Expected `Bar`, was {data:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_record_ohlcv_eod() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1d.dbn.zst");
+        msg.hd.rtype = dbn::rtype::OHLCV_EOD;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let data = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions {
+                bar_close_timestamps: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        match data {
+            DecodedRecord::One(Data::Bar(bar)) => {
+                assert_eq!(bar.bar_type.spec, BAR_SPEC_1D);
+                assert_eq!(bar.ts_event, msg.hd.ts_event + NANOSECONDS_IN_DAY);
+                assert_eq!(bar.ts_init, msg.hd.ts_event + NANOSECONDS_IN_DAY);
+            }
+            _ => panic!("Expected `Bar`, was {data:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_empty_bar_skip() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.high = dbn::UNDEF_PRICE;
+        msg.low = dbn::UNDEF_PRICE;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let bar = decode_ohlcv_msg_with_empty_bar_policy(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                empty_bar_policy: EmptyBarPolicy::Skip,
+                prior_close: Some(Price::from("3720.00")),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(is_empty_bar(&msg));
+        assert!(bar.is_none());
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_empty_bar_carry_forward() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.high = dbn::UNDEF_PRICE;
+        msg.low = dbn::UNDEF_PRICE;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let prior_close = Price::from("3720.00");
+
+        let bar = decode_ohlcv_msg_with_empty_bar_policy(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                empty_bar_policy: EmptyBarPolicy::CarryForward,
+                prior_close: Some(prior_close),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(bar.open, prior_close);
+        assert_eq!(bar.high, prior_close);
+        assert_eq!(bar.low, prior_close);
+        assert_eq!(bar.close, prior_close);
+        assert_eq!(bar.ts_event, 1_609_160_400_000_000_000);
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_empty_bar_carry_forward_without_prior_close() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.high = dbn::UNDEF_PRICE;
+        msg.low = dbn::UNDEF_PRICE;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let bar = decode_ohlcv_msg_with_empty_bar_policy(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                empty_bar_policy: EmptyBarPolicy::CarryForward,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(bar.is_none());
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_with_empty_bar_policy_defined_bar() {
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let bar = decode_ohlcv_msg_with_empty_bar_policy(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                empty_bar_policy: EmptyBarPolicy::CarryForward,
+                prior_close: Some(Price::from("1.00")),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        assert!(!is_empty_bar(&msg));
+        assert_eq!(bar.open, Price::from("3720.25"));
+    }
+
+    #[rstest]
+    fn test_decode_statistics_msg_open_interest() {
+        let msg = load_first_record::<dbn::StatMsg>("statistics.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let stats = decode_statistics_msg(&msg, instrument_id, 2, 0)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(stats.stat_type, DatabentoStatisticType::OpenInterest);
+        assert_eq!(stats.price, None);
+        assert_eq!(stats.quantity, Some(Quantity::from(10_181)));
+        assert_eq!(stats.sequence, 32_068);
+        assert_eq!(stats.ts_event, msg.ts_recv);
+    }
+
+    #[rstest]
+    fn test_decode_statistics_msg_settlement_price() {
+        let mut msg = load_first_record::<dbn::StatMsg>("statistics.dbn.zst");
+        msg.stat_type = dbn::StatType::SettlementPrice as u16;
+        msg.price = 4_137_750_000_000;
+        msg.quantity = dbn::UNDEF_STAT_QUANTITY;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let stats = decode_statistics_msg(&msg, instrument_id, 2, 0)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(stats.stat_type, DatabentoStatisticType::SettlementPrice);
+        assert_eq!(stats.price, Some(Price::from("4137.75")));
+        assert_eq!(stats.price.unwrap().precision, 2);
+        assert_eq!(stats.quantity, None);
+    }
+
+    #[rstest]
+    #[case(dbn::StatType::OpeningPrice, DatabentoStatisticType::OpeningPrice)]
+    #[case(dbn::StatType::ClosePrice, DatabentoStatisticType::ClosePrice)]
+    fn test_decode_statistics_msg_reference_price(
+        #[case] dbn_stat_type: dbn::StatType,
+        #[case] expected: DatabentoStatisticType,
+    ) {
+        let mut msg = load_first_record::<dbn::StatMsg>("statistics.dbn.zst");
+        msg.stat_type = dbn_stat_type as u16;
+        msg.price = 4_130_125_000_000;
+        msg.quantity = dbn::UNDEF_STAT_QUANTITY;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let stats = decode_statistics_msg(&msg, instrument_id, 3, 0)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(stats.instrument_id, instrument_id);
+        assert_eq!(stats.stat_type, expected);
+        assert_eq!(stats.price, Some(Price::from("4130.125")));
+        assert_eq!(stats.price.unwrap().precision, 3);
+        assert_eq!(stats.quantity, None);
+    }
+
+    #[rstest]
+    fn test_decode_statistics_msg_skipped_stat_type() {
+        let mut msg = load_first_record::<dbn::StatMsg>("statistics.dbn.zst");
+        msg.stat_type = dbn::StatType::ClearedVolume as u16;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let stats = decode_statistics_msg(&msg, instrument_id, 2, 0).unwrap();
+
+        assert!(stats.is_none());
+    }
+
+    #[rstest]
+    #[case('B' as c_char, 5_000_000_000)]
+    #[case('A' as c_char, -5_000_000_000)]
+    #[case('N' as c_char, 0)]
+    fn test_signed_size(#[case] side: c_char, #[case] expected: i64) {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.side = side;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let trade = decode_trade_msg(&msg, instrument_id, 2, 0, &DecodeOptions::default()).unwrap();
+
+        assert_eq!(signed_size(&trade), expected);
+    }
+
+    #[rstest]
+    fn test_volume_delta_accumulator() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let mut accumulator = VolumeDeltaAccumulator::new();
+
+        let mut deltas = Vec::new();
+        for (side, size) in [('B', 5), ('A', 2), ('N', 7), ('A', 4)] {
+            let mut msg = msg.clone();
+            msg.side = side as c_char;
+            msg.size = size;
+            let trade =
+                decode_trade_msg(&msg, instrument_id, 2, 0, &DecodeOptions::default()).unwrap();
+            deltas.push(accumulator.update(&trade));
+        }
+
+        assert_eq!(
+            deltas,
+            vec![5_000_000_000, 3_000_000_000, 3_000_000_000, -1_000_000_000]
+        );
+        assert_eq!(accumulator.delta(), -1_000_000_000);
+
+        accumulator.reset();
+        assert_eq!(accumulator.delta(), 0);
+    }
+
+    #[rstest]
+    fn test_decode_record_data_statistics() {
+        let msg = load_first_record::<dbn::StatMsg>("statistics.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let result = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+        let (data1, data2) = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap();
+
+        match result {
+            DecodedRecord::Statistics(stats) => {
+                assert_eq!(stats.instrument_id, instrument_id);
+                assert_eq!(stats.stat_type, DatabentoStatisticType::OpenInterest);
+                assert_eq!(stats.ts_init, msg.ts_recv);
+            }
+            _ => panic!("Expected statistics"),
+        }
+        assert!(data1.is_none());
+        assert!(data2.is_none());
+    }
+
+    #[rstest]
+    fn test_decode_imbalance_msg() {
+        let msg = load_first_record::<dbn::ImbalanceMsg>("imbalance.dbn.zst");
+        let instrument_id = InstrumentId::from("SPY.XNAS");
+
+        let imbalance = decode_imbalance_msg(&msg, instrument_id, 2, 0).unwrap();
+
+        assert_eq!(imbalance.ref_price, Some(Price::from("522.51")));
+        assert_eq!(imbalance.cont_book_clr_price, Some(Price::from("0.00")));
+        assert_eq!(
+            imbalance.auct_interest_clr_price,
+            Some(Price::from("522.20"))
+        );
+        assert_eq!(imbalance.paired_qty, Quantity::from(70_526));
+        assert_eq!(imbalance.total_imbalance_qty, Quantity::from(6_981));
+        assert_eq!(imbalance.side, OrderSide::Sell);
+        assert_eq!(imbalance.significant_imbalance, 'L' as c_char);
+        assert_eq!(imbalance.ts_event, msg.ts_recv);
+    }
+
+    #[rstest]
+    fn test_decode_imbalance_msg_cross_auction_buy_side() {
+        let mut msg = load_first_record::<dbn::ImbalanceMsg>("imbalance.dbn.zst");
+        msg.auction_type = 'C' as c_char;
+        msg.side = 'B' as c_char;
+        msg.auction_status = 1;
+        let instrument_id = InstrumentId::from("SPY.XNAS");
+
+        let imbalance = decode_imbalance_msg(&msg, instrument_id, 2, 0).unwrap();
+
+        assert_eq!(imbalance.auction_type, 'C' as c_char);
+        assert_eq!(imbalance.side, OrderSide::Buy);
+        assert_eq!(imbalance.auction_status, 1);
+        assert_eq!(imbalance.total_imbalance_qty, Quantity::from(6_981));
+    }
+
+    #[rstest]
+    fn test_decode_imbalance_msg_undefined_ref_price() {
+        let mut msg = load_first_record::<dbn::ImbalanceMsg>("imbalance.dbn.zst");
+        msg.ref_price = dbn::UNDEF_PRICE;
+        let instrument_id = InstrumentId::from("SPY.XNAS");
+
+        let imbalance = decode_imbalance_msg(&msg, instrument_id, 2, 0).unwrap();
+
+        assert_eq!(imbalance.ref_price, None);
+    }
+
+    #[rstest]
+    fn test_decode_record_data_imbalance() {
+        let msg = load_first_record::<dbn::ImbalanceMsg>("imbalance.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("SPY.XNAS");
+
+        let result = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+
+        match result {
+            DecodedRecord::Imbalance(imbalance) => {
+                assert_eq!(imbalance.instrument_id, instrument_id);
+                assert_eq!(imbalance.ref_price, Some(Price::from("522.51")));
+                assert_eq!(imbalance.ts_init, msg.ts_recv);
+            }
+            _ => panic!("Expected imbalance"),
+        }
+    }
+
+    fn status_msg(trading_status: u8, halt_reason: u8) -> dbn::StatusMsg {
+        dbn::StatusMsg {
+            hd: dbn::RecordHeader::new::<dbn::StatusMsg>(
+                dbn::rtype::STATUS,
+                2,
+                6_819,
+                1_609_160_400_000_000_000,
+            ),
+            ts_recv: 1_609_160_400_000_100_000,
+            group: [0; 21],
+            trading_status,
+            halt_reason,
+            trading_event: 0,
+        }
+    }
+
+    #[rstest]
+    fn test_decode_status_msg_halt() {
+        let msg = status_msg(8, 50); // Halt for LULD pause
+        let instrument_id = InstrumentId::from("MSFT.XNAS");
+
+        let status = decode_status_msg(&msg, instrument_id, 0).unwrap();
+
+        assert_eq!(status.instrument_id, instrument_id);
+        assert_eq!(status.status, MarketStatus::Halt);
+        assert_eq!(status.halt_reason, HaltReason::Volatility);
+        assert_eq!(status.ts_event, msg.ts_recv);
+    }
+
+    #[rstest]
+    fn test_decode_record_data_status() {
+        let msg = status_msg(8, 50); // Halt for LULD pause
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("MSFT.XNAS");
+
+        let result = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+
+        match result {
+            DecodedRecord::Status(status) => {
+                assert_eq!(status.instrument_id, instrument_id);
+                assert_eq!(status.status, MarketStatus::Halt);
+                assert_eq!(status.ts_init, msg.ts_recv);
+            }
+            _ => panic!("Expected status"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_status_msg_resume() {
+        let msg = status_msg(7, 0); // Trading resumed
+        let instrument_id = InstrumentId::from("MSFT.XNAS");
+
+        let status = decode_status_msg(&msg, instrument_id, 0).unwrap();
+
+        assert_eq!(status.status, MarketStatus::Open);
+        assert_eq!(status.halt_reason, HaltReason::NotHalted);
+    }
+
+    #[rstest]
+    fn test_decode_status_msg_unknown_code() {
+        let msg = status_msg(200, 0);
+        let instrument_id = InstrumentId::from("MSFT.XNAS");
+
+        let result = decode_status_msg(&msg, instrument_id, 0);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Unknown `trading_status` code 200"
+        );
+    }
+
+    #[rstest]
+    fn test_validate_record_valid() {
+        let mbo = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        let trade = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let mbp1 = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        let mbp10 = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        let bar = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        let heartbeat = dbn::SystemMsg::heartbeat(0);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let options = DecodeOptions::default();
+
+        assert!(validate_record(
+            &dbn::RecordRef::from(&heartbeat),
+            instrument_id,
+            2,
+            true,
+            &options
+        )
+        .is_ok());
+        assert!(validate_record(
+            &dbn::RecordRef::from(&mbo),
+            instrument_id,
+            2,
+            true,
+            &options
+        )
+        .is_ok());
+        assert!(validate_record(
+            &dbn::RecordRef::from(&trade),
+            instrument_id,
+            2,
+            true,
+            &options
+        )
+        .is_ok());
+        assert!(validate_record(
+            &dbn::RecordRef::from(&mbp1),
+            instrument_id,
+            2,
+            true,
+            &options
+        )
+        .is_ok());
+        assert!(validate_record(
+            &dbn::RecordRef::from(&mbp10),
+            instrument_id,
+            2,
+            true,
+            &options
+        )
+        .is_ok());
+        assert!(validate_record(
+            &dbn::RecordRef::from(&bar),
+            instrument_id,
+            2,
+            true,
+            &options
+        )
+        .is_ok());
+    }
+
+    #[rstest]
+    fn test_validate_record_applies_options() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.price *= 1_000;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let bounds_options = DecodeOptions {
+            price_bounds: Some(PriceBounds::new(
+                Price::from("3000.00"),
+                Price::from("4000.00"),
+                PriceBoundsPolicy::Reject,
+            )),
+            ..Default::default()
+        };
+        let max_ts_options = DecodeOptions {
+            max_ts_event: Some(msg.hd.ts_event - 1),
+            ..Default::default()
+        };
+
+        let default_result =
+            validate_record(&rec_ref, instrument_id, 2, false, &DecodeOptions::default());
+        let bounds_result = validate_record(&rec_ref, instrument_id, 2, false, &bounds_options);
+        let max_ts_result = validate_record(&rec_ref, instrument_id, 2, false, &max_ts_options);
+
+        assert!(default_result.is_ok());
+        assert_eq!(
+            bounds_result.unwrap_err().to_string(),
+            decode_record_data(&rec_ref, instrument_id, 2, None, false, &bounds_options)
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(max_ts_result.is_err());
+    }
+
+    #[rstest]
+    fn test_validate_record_invalid_book_action() {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.action = 'Z' as c_char;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        assert!(decode_record(&rec_ref, instrument_id, 2, None, false).is_err());
+        assert!(
+            validate_record(&rec_ref, instrument_id, 2, false, &DecodeOptions::default()).is_err()
+        );
+    }
+
+    #[rstest]
+    fn test_validate_record_invalid_price_precision() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        assert!(decode_record(&rec_ref, instrument_id, 10, None, false).is_err());
+        assert!(validate_record(
+            &rec_ref,
+            instrument_id,
+            10,
+            false,
+            &DecodeOptions::default()
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    fn test_validate_record_unsupported_bar_rtype() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.hd.rtype = dbn::rtype::OHLCV_DEPRECATED;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        assert!(decode_record(&rec_ref, instrument_id, 2, None, false).is_err());
+        assert!(
+            validate_record(&rec_ref, instrument_id, 2, false, &DecodeOptions::default()).is_err()
+        );
+    }
+
+    #[rstest]
+    fn test_validate_record_unsupported_rtype() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        assert!(decode_record(&rec_ref, instrument_id, 2, None, false).is_err());
+        assert!(
+            validate_record(&rec_ref, instrument_id, 2, false, &DecodeOptions::default()).is_err()
+        );
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_settlement_currency_defaults_to_quote() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.settlement_currency(), Currency::USD());
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_settlement_currency_differs() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.settl_currency = str_to_c_chars("EUR");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.quote_currency(), Currency::USD());
+        assert_eq!(future.settlement_currency(), Currency::EUR());
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_quote_and_settlement_currency_differ() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.currency = str_to_c_chars("JPY");
+        msg.settl_currency = str_to_c_chars("USD");
+        let instrument_id = InstrumentId::from("NIYM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.quote_currency(), Currency::JPY());
+        assert_eq!(future.settlement_currency(), Currency::USD());
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_empty_currency_defaults_to_usd() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.currency = str_to_c_chars("");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.quote_currency(), Currency::USD());
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_undefined_bid() {
+        let mut msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        msg.levels[0].bid_px = dbn::UNDEF_PRICE;
+        msg.levels[0].bid_sz = dbn::UNDEF_ORDER_SIZE;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (quote, _) =
+            decode_mbp1_msg(&msg, instrument_id, 2, 0, false, &DecodeOptions::default()).unwrap();
+
+        assert_eq!(quote.bid_price, Price::from("0.00"));
+        assert_eq!(quote.bid_size, Quantity::from(0));
+        assert_eq!(quote.ask_size, Quantity::from(11));
+        assert_ne!(quote.ask_price, Price::from("0.00"));
+    }
+
+    #[rstest]
+    fn test_decode_mbp10_msg_undefined_levels() {
+        let mut msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        msg.levels[9].bid_px = dbn::UNDEF_PRICE;
+        msg.levels[9].bid_sz = dbn::UNDEF_ORDER_SIZE;
+        msg.levels[9].ask_px = dbn::UNDEF_PRICE;
+        msg.levels[9].ask_sz = 0;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (depth, _) = decode_mbp10_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            false,
+            &DecodeOptions {
+                validate_level_ordering: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(depth.bids[9].price, Price::from("0.00"));
+        assert_eq!(depth.bids[9].size, Quantity::from(0));
+        assert_eq!(depth.asks[9].price, Price::from("0.00"));
+        assert_eq!(depth.bids[0].price, Price::from("3720.25"));
+    }
+
+    #[rstest]
+    fn test_decode_record_price_bounds_skips_undefined_bid() {
+        let mut msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        msg.levels[0].bid_px = dbn::UNDEF_PRICE;
+        msg.levels[0].bid_sz = dbn::UNDEF_ORDER_SIZE;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let bounds = PriceBounds::new(
+            Price::from("1000.00"),
+            Price::from("10000.00"),
+            PriceBoundsPolicy::Reject,
+        );
+
+        let result = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            Some(0),
+            false,
+            &DecodeOptions {
+                price_bounds: Some(bounds),
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_multiplier_and_lot_size() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.multiplier(), Quantity::from(50));
+        assert_eq!(future.lot_size(), Some(Quantity::from(1)));
+        assert_eq!(future.min_quantity(), Some(Quantity::from(1)));
+        assert_eq!(future.max_quantity(), Some(Quantity::from(3_000)));
+    }
+
+    #[rstest]
+    fn test_tick_value_cme_future() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        let value = tick_value(&future).unwrap();
+
+        // E-mini S&P 500 is $50 per point with a 0.25 point tick
+        assert_eq!(value, Money::new(12.50, Currency::USD()).unwrap());
+    }
+
+    #[rstest]
+    fn test_tick_value_exact_fractional_tick() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+        let mut future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+        future.price_increment = Price::from("0.015625");
+        future.multiplier = Quantity::from(100_000);
+
+        let value = tick_value(&future).unwrap();
+
+        assert_eq!(value, Money::new(1562.50, Currency::USD()).unwrap());
+    }
+
+    #[rstest]
+    fn test_tick_value_not_representable() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let instrument_id = InstrumentId::from("ZNM3.GLBX");
+        let mut future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+        // 10-Year T-Note is $1,000 per point with a 1/64 point tick, i.e. $15.625
+        future.price_increment = Price::from("0.015625");
+        future.multiplier = Quantity::from(1_000);
+
+        let err = tick_value(&future).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Tick value 15.625 for contract ZNM3.GLBX is not representable"));
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_round_lot_size() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.min_lot_size_round_lot = 5;
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.lot_size(), Some(Quantity::from(5)));
+    }
+
+    #[rstest]
+    #[case(50_000_000_000, i32::MAX, 50)]
+    #[case(i64::MAX, 1_000, 1_000)]
+    #[case(0, 42_000, 42_000)]
+    #[case(i64::MAX, i32::MAX, 1)]
+    fn test_decode_multiplier(
+        #[case] unit_of_measure_qty: i64,
+        #[case] contract_multiplier: i32,
+        #[case] expected: i64,
+    ) {
+        let multiplier = decode_multiplier(unit_of_measure_qty, contract_multiplier).unwrap();
+        assert_eq!(multiplier, Quantity::from(expected));
+    }
+
+    #[rstest]
+    fn test_decode_record_max_ts_event_in_bound() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let max_ts_event = max_ts_event_from(msg.hd.ts_event, 1);
+
+        let result = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions {
+                max_ts_event: Some(max_ts_event),
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    fn test_decode_record_max_ts_event_far_future() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let max_ts_event = max_ts_event_from(msg.hd.ts_event, 1);
+        msg.hd.ts_event = max_ts_event_from(msg.hd.ts_event, 50);
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let err = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions {
+                max_ts_event: Some(max_ts_event),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Record Mbp0 for instrument ID 5482 has `ts_event` {} beyond maximum {max_ts_event}",
+                msg.hd.ts_event
+            )
+        );
+    }
+
+    #[rstest]
+    fn test_decode_instrument_def_msg_with_quote_v1() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let (instrument, quote) =
+            decode_instrument_def_msg_with_quote_v1(&msg, instrument_id, 1, true, None).unwrap();
+        let quote = quote.unwrap();
+
+        assert_eq!(instrument.id(), instrument_id);
+        assert_eq!(quote.instrument_id, instrument_id);
+        assert_eq!(quote.bid_price, Price::from("4137.75"));
+        assert_eq!(quote.ask_price, Price::from("4137.75"));
+        assert_eq!(quote.bid_price.precision, instrument.price_precision());
+        assert_eq!(quote.bid_size, Quantity::from(0));
+        assert_eq!(quote.ts_event, msg.ts_recv);
+        assert_eq!(quote.ts_init, 1);
+    }
+
+    #[rstest]
+    fn test_decode_instrument_def_msg_with_quote_v1_undefined_reference_price() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.trading_reference_price = dbn::UNDEF_PRICE;
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let (_, quote) =
+            decode_instrument_def_msg_with_quote_v1(&msg, instrument_id, 0, true, None).unwrap();
+
+        assert!(quote.is_none());
+    }
+
+    #[rstest]
+    fn test_decode_instrument_def_msg_with_quote_v1_not_included() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let (_, quote) =
+            decode_instrument_def_msg_with_quote_v1(&msg, instrument_id, 0, false, None).unwrap();
+
+        assert!(quote.is_none());
+    }
+
+    #[rstest]
+    fn test_decode_instrument_def_msg_v1_treasury_note() {
+        let mut msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        msg.instrument_class = 'B' as c_char;
+        msg.raw_symbol = str_to_c_chars("91282CJJ1");
+        msg.currency = str_to_c_chars("USD");
+        msg.min_price_increment = 7_812_500; // 1/128
+        msg.activation = dbn::UNDEF_TIMESTAMP;
+        msg.expiration = 2_015_625_600_000_000_000; // 2033-11-15
+        let instrument_id = InstrumentId::from("91282CJJ1.XNAS");
+
+        let instrument = decode_instrument_def_msg_v1(&msg, instrument_id, 0, None).unwrap();
+        let bond = instrument.as_any().downcast_ref::<Bond>().unwrap();
+
+        assert_eq!(bond.asset_class(), AssetClass::Debt);
+        assert_eq!(bond.instrument_class(), InstrumentClass::Bond);
+        assert_eq!(bond.coupon, None);
+        assert_eq!(bond.issue_ns, None);
+        assert_eq!(bond.maturity_ns, 2_015_625_600_000_000_000);
+        assert_eq!(bond.face_value, Quantity::from(100));
+        assert_eq!(bond.price_precision(), 7);
+        assert_eq!(bond.price_increment(), Price::from("0.0078125"));
+        assert_eq!(bond.lot_size(), Some(Quantity::from(100)));
+    }
+
+    #[rstest]
+    fn test_decode_bond_v1_undefined_expiration() {
+        let mut msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        msg.instrument_class = 'B' as c_char;
+        msg.expiration = dbn::UNDEF_TIMESTAMP;
+        let instrument_id = InstrumentId::from("91282CJJ1.XNAS");
+
+        let result = decode_bond_v1(&msg, instrument_id, 0);
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_check_dbn_version_supported() {
+        let decoder = Decoder::from_zstd_file(test_data_path().join("trades.dbn.zst")).unwrap();
+
+        let version = check_dbn_version(decoder.metadata()).unwrap();
+
+        assert_eq!(version, SupportedVersion::V1);
+    }
+
+    #[rstest]
+    fn test_check_dbn_version_unsupported() {
+        let decoder = Decoder::from_zstd_file(test_data_path().join("trades.dbn.zst")).unwrap();
+        let mut metadata = decoder.metadata().clone();
+        metadata.version = 99;
+
+        let result = check_dbn_version(&metadata);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Unsupported DBN version 99, expected version 1 or 2"
+        );
+    }
+
+    #[rstest]
+    #[case('A', BookAction::Add, DatabentoBookAction::Add)]
+    #[case('C', BookAction::Delete, DatabentoBookAction::Cancel)]
+    #[case('F', BookAction::Update, DatabentoBookAction::Fill)]
+    #[case('M', BookAction::Update, DatabentoBookAction::Modify)]
+    #[case('R', BookAction::Clear, DatabentoBookAction::Clear)]
+    fn test_decode_mbo_msg_with_raw_action(
+        #[case] action: char,
+        #[case] expected: BookAction,
+        #[case] expected_databento_action: DatabentoBookAction,
+    ) {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.action = action as c_char;
+        msg.side = 'B' as c_char;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (delta, trade) = decode_mbo_msg_with_raw_action(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            false,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+        let delta = delta.unwrap();
+
+        assert!(trade.is_none());
+        assert_eq!(delta.raw_action, action as c_char);
+        assert_eq!(delta.delta.action, expected);
+        assert_eq!(delta.action, expected_databento_action);
+    }
+
+    #[rstest]
+    fn test_parse_databento_book_action_distinguishes_fill_and_modify() {
+        let fill = parse_databento_book_action('F' as c_char).unwrap();
+        let modify = parse_databento_book_action('M' as c_char).unwrap();
+
+        assert_ne!(fill, modify);
+        assert_eq!(BookAction::from(fill), BookAction::from(modify));
+        assert!(parse_databento_book_action('X' as c_char).is_err());
+    }
+
+    #[rstest]
+    fn test_decode_mbo_msg_with_raw_action_trade() {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.action = 'T' as c_char;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (delta, trade) = decode_mbo_msg_with_raw_action(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            true,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(delta.is_none());
+        assert!(trade.is_some());
+    }
+
+    #[rstest]
+    #[case("EUR/USD", "EUR", "USD", 10_000, 5, "0.00001")]
+    #[case("USD/JPY", "USD", "JPY", 1_000_000, 3, "0.001")]
+    fn test_decode_currency_pair_v1(
+        #[case] raw_symbol: &str,
+        #[case] base: &str,
+        #[case] quote: &str,
+        #[case] min_price_increment: i64,
+        #[case] expected_precision: u8,
+        #[case] expected_increment: &str,
+    ) {
+        let mut msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        msg.instrument_class = 'X' as c_char;
+        msg.raw_symbol = str_to_c_chars(raw_symbol);
+        msg.asset = str_to_c_chars(base);
+        msg.currency = str_to_c_chars(quote);
+        msg.min_price_increment = min_price_increment;
+        let instrument_id = InstrumentId::from(format!("{raw_symbol}.XNAS").as_str());
+
+        let instrument = decode_instrument_def_msg_v1(&msg, instrument_id, 0, None).unwrap();
+        let pair = instrument.as_any().downcast_ref::<CurrencyPair>().unwrap();
+
+        assert_eq!(pair.instrument_class(), InstrumentClass::Spot);
+        assert_eq!(pair.base_currency, Currency::from(base));
+        assert_eq!(pair.quote_currency, Currency::from(quote));
+        assert_eq!(pair.price_precision(), expected_precision);
+        assert_eq!(pair.price_increment(), Price::from(expected_increment));
+    }
+
+    #[rstest]
+    fn test_decode_currency_pair_v1_invalid_base_currency() {
+        let mut msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        msg.instrument_class = 'X' as c_char;
+        msg.asset = str_to_c_chars("");
+        msg.currency = str_to_c_chars("USD");
+        let instrument_id = InstrumentId::from("EUR/USD.XNAS");
+
+        assert!(decode_currency_pair_v1(&msg, instrument_id, 0).is_err());
+    }
+
+    #[rstest]
+    fn test_decode_error_invalid_book_action() {
+        let err = parse_book_action('Z' as c_char).unwrap_err();
+
+        assert!(matches!(err, DatabentoDecodeError::InvalidBookAction('Z')));
+    }
+
+    #[rstest]
+    fn test_decode_error_invalid_option_kind() {
+        let err = parse_option_kind('X' as c_char).unwrap_err();
+
+        assert!(matches!(err, DatabentoDecodeError::InvalidOptionKind('X')));
+    }
+
+    #[rstest]
+    #[case("ESVUFR", Some(AssetClass::Equity), Some(InstrumentClass::Spot))]
+    #[case("DBFTFB", Some(AssetClass::Debt), Some(InstrumentClass::Bond))]
+    #[case("FFIXSX", Some(AssetClass::Index), Some(InstrumentClass::Future))]
+    #[case("FFCPSX", Some(AssetClass::FX), Some(InstrumentClass::Future))]
+    #[case("FCEPSX", Some(AssetClass::Commodity), Some(InstrumentClass::Future))]
+    #[case("FMIXSX", Some(AssetClass::Index), Some(InstrumentClass::FutureSpread))]
+    #[case("FXXXXX", None, None)]
+    #[case("OCAFPS", None, Some(InstrumentClass::Option))]
+    #[case("OPASPS", Some(AssetClass::Equity), Some(InstrumentClass::Option))]
+    #[case("OCEICS", Some(AssetClass::Index), Some(InstrumentClass::Option))]
+    #[case("OMXFXX", None, Some(InstrumentClass::OptionSpread))]
+    #[case("RWSTCA", Some(AssetClass::Equity), Some(InstrumentClass::Warrant))]
+    #[case("RSXXXX", None, Some(InstrumentClass::Warrant))]
+    #[case("IFXXXP", Some(AssetClass::FX), Some(InstrumentClass::Spot))]
+    #[case("ITNXXX", Some(AssetClass::Commodity), Some(InstrumentClass::Spot))]
+    #[case("JFTXFP", Some(AssetClass::FX), Some(InstrumentClass::Forward))]
+    #[case("SRCCSP", Some(AssetClass::Debt), Some(InstrumentClass::Swap))]
+    #[case("KFXXXX", Some(AssetClass::FX), Some(InstrumentClass::FutureSpread))]
+    #[case("MMMXXX", None, None)]
+    #[case("FFI", Some(AssetClass::Index), Some(InstrumentClass::Future))]
+    fn test_parse_cfi_iso10926(
+        #[case] cfi: &str,
+        #[case] expected_asset_class: Option<AssetClass>,
+        #[case] expected_instrument_class: Option<InstrumentClass>,
+    ) {
+        let (asset_class, instrument_class) = parse_cfi_iso10926(cfi).unwrap();
+
+        assert_eq!(asset_class, expected_asset_class);
+        assert_eq!(instrument_class, expected_instrument_class);
+    }
+
+    fn symbol_map_for(msg: &dbn::MboMsg, raw_symbol: &str) -> DatabentoSymbolMap {
+        let publisher_venue_map = IndexMap::from([(msg.hd.publisher_id, Venue::from("GLBX"))]);
+        let mut symbol_map = DatabentoSymbolMap::new(publisher_venue_map);
+        let mapping = dbn::SymbolMappingMsg::new(
+            msg.hd.instrument_id,
+            msg.hd.ts_event,
+            dbn::SType::RawSymbol,
+            raw_symbol,
+            dbn::SType::RawSymbol,
+            raw_symbol,
+            msg.hd.ts_event,
+            dbn::UNDEF_TIMESTAMP,
+        )
+        .unwrap();
+        symbol_map.on_symbol_mapping(&mapping).unwrap();
+        symbol_map
+    }
+
+    #[rstest]
+    fn test_decode_record_with_map() {
+        let msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let symbol_map = symbol_map_for(&msg, "ESM4");
+
+        let (data, _) = decode_record_with_map(&rec_ref, &symbol_map, 2, None, false).unwrap();
+
+        match data {
+            Some(Data::Delta(delta)) => {
+                assert_eq!(delta.instrument_id.symbol, Symbol::from("ESM4"));
+                assert_eq!(delta.instrument_id.venue, Venue::from("GLBX"));
+            }
+            _ => panic!("Expected `OrderBookDelta`, was {data:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_record_with_map_remapped_symbol() {
+        let msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let mut symbol_map = symbol_map_for(&msg, "ESM4");
+        let mapping = dbn::SymbolMappingMsg::new(
+            msg.hd.instrument_id,
+            msg.hd.ts_event,
+            dbn::SType::RawSymbol,
+            "ESU4",
+            dbn::SType::RawSymbol,
+            "ESU4",
+            msg.hd.ts_event,
+            dbn::UNDEF_TIMESTAMP,
+        )
+        .unwrap();
+        symbol_map.on_symbol_mapping(&mapping).unwrap();
+
+        let instrument_id = symbol_map.get_instrument_id_for_record(&rec_ref).unwrap();
+
+        assert_eq!(instrument_id, InstrumentId::from("ESU4.GLBX"));
+    }
+
+    #[rstest]
+    fn test_decode_record_with_map_unmapped_instrument() {
+        let msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let symbol_map = DatabentoSymbolMap::new(IndexMap::new());
+
+        let err = decode_record_with_map(&rec_ref, &symbol_map, 2, None, false).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "No raw symbol mapped for instrument ID {}",
+                msg.hd.instrument_id
+            )
+        );
+    }
+
+    #[rstest]
+    fn test_decode_symbol_mapping_msg() {
+        let msg = dbn::SymbolMappingMsg::new(
+            5_482,
+            1_609_113_600_000_000_000,
+            dbn::SType::Continuous,
+            "ES.c.0",
+            dbn::SType::RawSymbol,
+            "ESH1",
+            1_609_113_600_000_000_000,
+            1_609_200_000_000_000_000,
+        )
+        .unwrap();
+
+        let (instrument_id, raw_symbol, start_ts, end_ts) =
+            decode_symbol_mapping_msg(&msg).unwrap();
+
+        assert_eq!(instrument_id, 5_482);
+        assert_eq!(raw_symbol, Ustr::from("ESH1"));
+        assert_eq!(start_ts, 1_609_113_600_000_000_000);
+        assert_eq!(end_ts, 1_609_200_000_000_000_000);
+    }
+
+    #[rstest]
+    fn test_decode_symbol_mapping_msg_empty_symbol() {
+        let msg = dbn::SymbolMappingMsg::new(
+            5_482,
+            1_609_113_600_000_000_000,
+            dbn::SType::Continuous,
+            "ES.c.0",
+            dbn::SType::RawSymbol,
+            "",
+            1_609_113_600_000_000_000,
+            1_609_200_000_000_000_000,
+        )
+        .unwrap();
+
+        assert!(decode_symbol_mapping_msg(&msg).is_err());
+    }
+
+    #[rstest]
+    #[case(TradeIdPolicy::Sequence, "1170352")]
+    #[case(TradeIdPolicy::TsRecvSequence, "1609160400099150057-1170352")]
+    #[case(TradeIdPolicy::PublisherSequence, "1-1170352")]
+    fn test_decode_trade_id(#[case] policy: TradeIdPolicy, #[case] expected: &str) {
+        let trade_id = decode_trade_id(1_170_352, 1_609_160_400_099_150_057, 1, policy).unwrap();
+
+        assert_eq!(trade_id, TradeId::new(expected).unwrap());
+    }
+
+    #[rstest]
+    #[case(TradeIdPolicy::Sequence, true)]
+    #[case(TradeIdPolicy::TsRecvSequence, false)]
+    fn test_decode_trade_msg_trade_id_across_sessions(
+        #[case] policy: TradeIdPolicy,
+        #[case] expected_collision: bool,
+    ) {
+        let first_session = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        // The next session resets its sequence numbers
+        let mut next_session = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        next_session.ts_recv += NANOSECONDS_IN_DAY;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let trade1 = decode_trade_msg(
+            &first_session,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                trade_id_policy: policy,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let trade2 = decode_trade_msg(
+            &next_session,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                trade_id_policy: policy,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(trade1.trade_id == trade2.trade_id, expected_collision);
+    }
+
+    #[rstest]
+    fn test_decode_trade_msg_with_latency() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (trade, latency) =
+            decode_trade_msg_with_latency(&msg, instrument_id, 2, 0, &DecodeOptions::default())
+                .unwrap();
+
+        // Gateway receive at 1609160400099150057 with a 19251ns exchange send latency
+        assert_eq!(trade.ts_event, 1_609_160_400_099_150_057);
+        assert_eq!(latency.ts_recv, 1_609_160_400_099_150_057);
+        assert_eq!(latency.ts_in_delta, 19_251);
+        assert_eq!(latency.ts_exchange, 1_609_160_400_099_130_806);
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_with_latency() {
+        let msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (quote, _, latency) = decode_mbp1_msg_with_latency(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            false,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(quote.ts_event, 1_609_160_400_006_136_329);
+        assert_eq!(latency.ts_exchange, 1_609_160_400_006_119_115);
+    }
+
+    #[rstest]
+    fn test_decode_latency_saturates() {
+        let latency = decode_latency(10, 20);
+
+        assert_eq!(latency.ts_exchange, 0);
+    }
+
+    #[rstest]
+    fn test_decode_trade_msg_negative_spread_price() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.price = -1_250_000_000; // Calendar spread trading at -1.25
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4-ESU4.GLBX");
+
+        let (data, _) = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap();
+
+        match data {
+            Some(Data::Trade(trade)) => {
+                assert_eq!(trade.price.raw, -1_250_000_000);
+                assert_eq!(trade.price.precision, 2);
+                assert_eq!(trade.price.to_string(), "-1.25");
+            }
+            _ => panic!("Expected `TradeTick`, was {data:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_negative_spread_prices() {
+        let mut msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        msg.levels[0].bid_px = -1_500_000_000;
+        msg.levels[0].ask_px = -1_250_000_000;
+        let instrument_id = InstrumentId::from("ESM4-ESU4.GLBX");
+
+        let (quote, _) = decode_mbp1_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            false,
+            &DecodeOptions {
+                crossed_quote_policy: CrossedQuotePolicy::Reject,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(quote.bid_price.raw, -1_500_000_000);
+        assert_eq!(quote.ask_price.raw, -1_250_000_000);
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_negative_spread_prices() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.open = -125_000_000_000;
+        msg.high = -100_000_000_000;
+        msg.low = -150_000_000_000;
+        msg.close = -125_000_000_000;
+        let instrument_id = InstrumentId::from("ESM4-ESU4.GLBX");
+
+        let bar = decode_ohlcv_msg(&msg, instrument_id, 2, 0, &DecodeOptions::default()).unwrap();
+
+        assert_eq!(bar.open.to_string(), "-1.25");
+        assert_eq!(bar.high.to_string(), "-1.00");
+        assert_eq!(bar.low.to_string(), "-1.50");
+    }
+
+    #[rstest]
+    fn test_decode_min_price_increment_negative_spread_increment() {
+        let increment = decode_min_price_increment(-50_000_000, Currency::USD()).unwrap();
+
+        assert_eq!(increment.raw, 50_000_000);
+        assert_eq!(increment.precision, 2);
+    }
+
+    #[rstest]
+    #[case("OCAFPS", Some(ExerciseStyle::American))]
+    #[case("OPASPS", Some(ExerciseStyle::American))]
+    #[case("OCEICS", Some(ExerciseStyle::European))]
+    #[case("OPBTPS", Some(ExerciseStyle::Bermudan))]
+    #[case("OCXFPS", None)]
+    #[case("OMXXXX", None)]
+    #[case("FFICSX", None)]
+    #[case("OC", None)]
+    #[case("", None)]
+    fn test_parse_cfi_exercise_style(#[case] cfi: &str, #[case] expected: Option<ExerciseStyle>) {
+        assert_eq!(parse_cfi_exercise_style(cfi), expected);
+    }
+
+    #[rstest]
+    fn test_decode_options_contract_v1_exercise_style_american_equity() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-opra.dbn.zst");
+        msg.cfi = str_to_c_chars("OPASPS");
+        let instrument_id = InstrumentId::from("SPY   240119P00340000.OPRA");
+
+        let option = decode_options_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(option.asset_class, AssetClass::Equity);
+        assert_eq!(option.exercise_style, Some(ExerciseStyle::American));
+    }
+
+    #[rstest]
+    fn test_decode_options_contract_v1_exercise_style_european_index() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-opt.dbn.zst");
+        msg.cfi = str_to_c_chars("OCEICS");
+        let instrument_id = InstrumentId::from("SPXW C4250.XCBO");
+
+        let option = decode_options_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(option.asset_class, AssetClass::Index);
+        assert_eq!(option.exercise_style, Some(ExerciseStyle::European));
+    }
+
+    #[rstest]
+    fn test_decode_options_contract_v1_exercise_style_unknown() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-opt.dbn.zst");
+        msg.cfi = str_to_c_chars("OCXFPS");
+        let instrument_id = InstrumentId::from("ESM4 C4250.GLBX");
+
+        let option = decode_options_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(option.exercise_style, None);
+    }
+
+    #[rstest]
+    #[case(TsInitSource::Recv, None, 1_609_160_400_099_150_057)]
+    #[case(TsInitSource::Recv, Some(5), 1_609_160_400_099_150_057)]
+    #[case(TsInitSource::Event, None, 1_609_160_400_098_821_953)]
+    #[case(TsInitSource::Provided, Some(5), 5)]
+    fn test_decode_record_with_ts_init_source_trade(
+        #[case] source: TsInitSource,
+        #[case] ts_init: Option<UnixNanos>,
+        #[case] expected: UnixNanos,
+    ) {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (data, _) =
+            decode_record_with_ts_init_source(&rec_ref, instrument_id, 2, ts_init, false, source)
+                .unwrap();
+
+        match data {
+            Some(Data::Trade(trade)) => assert_eq!(trade.ts_init, expected),
+            _ => panic!("Expected trade"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_ts_init_recv_non_trade_records() {
+        let stat = load_first_record::<dbn::StatMsg>("statistics.dbn.zst");
+        let imbalance = load_first_record::<dbn::ImbalanceMsg>("imbalance.dbn.zst");
+        let status = status_msg(1, 0);
+
+        let stat_ts_init =
+            decode_ts_init(&dbn::RecordRef::from(&stat), TsInitSource::Recv, None).unwrap();
+        let imbalance_ts_init =
+            decode_ts_init(&dbn::RecordRef::from(&imbalance), TsInitSource::Recv, None).unwrap();
+        let status_ts_init =
+            decode_ts_init(&dbn::RecordRef::from(&status), TsInitSource::Recv, None).unwrap();
+
+        assert_eq!(stat_ts_init, stat.ts_recv);
+        assert_eq!(imbalance_ts_init, imbalance.ts_recv);
+        assert_eq!(status_ts_init, status.ts_recv);
+        assert_ne!(status_ts_init, status.hd.ts_event);
+    }
+
+    #[rstest]
+    fn test_decode_record_with_ts_init_source_trade_wall_clock() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let clock = get_atomic_clock_realtime();
+
+        let before = clock.get_time_ns();
+        let (data, _) = decode_record_with_ts_init_source(
+            &rec_ref,
+            instrument_id,
+            2,
+            Some(5),
+            false,
+            TsInitSource::WallClock,
+        )
+        .unwrap();
+        let after = clock.get_time_ns();
+
+        match data {
+            Some(Data::Trade(trade)) => {
+                assert!(trade.ts_init >= before && trade.ts_init <= after);
+            }
+            _ => panic!("Expected trade"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_record_with_ts_init_source_provided_missing() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let err = decode_record_with_ts_init_source(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            TsInitSource::Provided,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("No `ts_init` provided"));
+    }
+
+    #[rstest]
+    fn test_precision_map_default_without_definition() {
+        let precision_map = PrecisionMap::default();
+
+        assert_eq!(
+            precision_map.get_price_precision(&InstrumentId::from("ESM3.GLBX")),
+            Currency::USD().precision
+        );
+    }
+
+    #[rstest]
+    fn test_precision_map_display_factor() {
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+        let mut precision_map = PrecisionMap::default();
+        precision_map.insert_display_factor(instrument_id, 0);
+
+        assert_eq!(precision_map.get_display_factor(&instrument_id), None);
+
+        precision_map.insert_display_factor(instrument_id, 10_000_000);
+        let options = precision_map.options_for(&instrument_id, &DecodeOptions::default());
+
+        assert_eq!(
+            precision_map.get_display_factor(&instrument_id),
+            Some(10_000_000)
+        );
+        assert_eq!(options.display_factor, Some(10_000_000));
+    }
+
+    #[rstest]
+    fn test_decode_record_with_precision_map_after_definition() {
+        let mut def_msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        def_msg.min_price_increment = 100_000; // 0.0001
+        def_msg.display_factor = DISPLAY_FACTOR_ONE;
+        let trade_msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&trade_msg);
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+        let mut precision_map = PrecisionMap::default();
+
+        let (before, _) =
+            decode_record_with_precision_map(&rec_ref, instrument_id, &precision_map, None, false)
+                .unwrap();
+        decode_instrument_def_msg_with_precision_map_v1(
+            &def_msg,
+            instrument_id,
+            0,
+            None,
+            &mut precision_map,
+        )
+        .unwrap();
+        let (after, _) =
+            decode_record_with_precision_map(&rec_ref, instrument_id, &precision_map, None, false)
+                .unwrap();
+
+        assert!(precision_map.contains(&instrument_id));
+        assert_eq!(precision_map.get_price_precision(&instrument_id), 4);
+        match (before, after) {
+            (Some(Data::Trade(before)), Some(Data::Trade(after))) => {
+                assert_eq!(before.price.precision, 2);
+                assert_eq!(after.price.precision, 4);
+                assert_eq!(before.price.raw, after.price.raw);
+            }
+            _ => panic!("Expected trades"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_options_contract_v1_undefined_strike_price() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-opra.dbn.zst");
+        msg.strike_price = dbn::UNDEF_PRICE;
+        let instrument_id = InstrumentId::from("SPY   240119P00340000.OPRA");
+
+        let err = decode_options_contract_v1(&msg, instrument_id, 0, None).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Undefined `strike_price` for options contract SPY   240119P00340000.OPRA"
+        );
+    }
+
+    #[rstest]
+    #[case(RecordFlags::F_LAST, true, false, false, false, false)]
+    #[case(RecordFlags::F_TOB, false, true, false, false, false)]
+    #[case(RecordFlags::F_SNAPSHOT, false, false, true, false, false)]
+    #[case(RecordFlags::F_MBP, false, false, false, true, false)]
+    #[case(RecordFlags::F_BAD_TS_RECV, false, false, false, false, true)]
+    #[case(0, false, false, false, false, false)]
+    fn test_parse_record_flags(
+        #[case] raw: u8,
+        #[case] is_last: bool,
+        #[case] is_tob: bool,
+        #[case] is_snapshot: bool,
+        #[case] is_mbp: bool,
+        #[case] is_bad_ts_recv: bool,
+    ) {
+        let flags = parse_record_flags(raw);
+
+        assert_eq!(flags.raw(), raw);
+        assert_eq!(flags.is_last(), is_last);
+        assert_eq!(flags.is_tob(), is_tob);
+        assert_eq!(flags.is_snapshot(), is_snapshot);
+        assert_eq!(flags.is_mbp(), is_mbp);
+        assert_eq!(flags.is_bad_ts_recv(), is_bad_ts_recv);
+        assert!(!flags.is_maybe_bad_book());
+    }
+
+    #[rstest]
+    fn test_parse_record_flags_combination() {
+        let flags = parse_record_flags(RecordFlags::F_LAST | RecordFlags::F_SNAPSHOT | 0b11);
+
+        assert!(flags.is_last());
+        assert!(flags.is_snapshot());
+        assert!(!flags.is_mbp());
+        assert!(flags.contains(RecordFlags::F_LAST | RecordFlags::F_SNAPSHOT));
+        assert_eq!(flags.raw(), 0b1010_0011);
+    }
+
+    #[rstest]
+    fn test_parse_record_flags_mbo_last() {
+        let msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (delta, _) =
+            decode_mbo_msg(&msg, instrument_id, 2, 0, false, &DecodeOptions::default()).unwrap();
+
+        let flags = parse_record_flags(delta.unwrap().flags);
+        assert_eq!(flags.raw(), RecordFlags::F_LAST);
+        assert!(flags.is_last());
+        assert!(!flags.is_snapshot());
+    }
+
+    #[rstest]
+    fn test_decode_mbo_msg_clear_then_add_snapshot() {
+        let mut clear_msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        clear_msg.action = 'R' as c_char;
+        clear_msg.side = 'N' as c_char;
+        clear_msg.price = dbn::UNDEF_PRICE;
+        clear_msg.size = 0;
+        clear_msg.order_id = 0;
+        clear_msg.flags = RecordFlags::F_SNAPSHOT;
+        let mut add_msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        add_msg.action = 'A' as c_char;
+        add_msg.flags = RecordFlags::F_SNAPSHOT | RecordFlags::F_LAST;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (clear, clear_trade) = decode_mbo_msg(
+            &clear_msg,
+            instrument_id,
+            2,
+            0,
+            true,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+        let (add, _) = decode_mbo_msg(
+            &add_msg,
+            instrument_id,
+            2,
+            0,
+            true,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+
+        let clear = clear.unwrap();
+        let add = add.unwrap();
+        assert!(clear_trade.is_none());
+        assert_eq!(clear.action, BookAction::Clear);
+        assert_eq!(clear.order.side, OrderSide::NoOrderSide);
+        assert_eq!(clear.order.price.raw, 0);
+        assert_eq!(clear.order.size.raw, 0);
+        assert!(parse_record_flags(clear.flags).is_snapshot());
+        assert!(!parse_record_flags(clear.flags).is_last());
+        assert_eq!(add.action, BookAction::Add);
+        assert_eq!(add.order.side, OrderSide::Sell);
+        assert!(parse_record_flags(add.flags).is_snapshot());
+        assert!(parse_record_flags(add.flags).is_last());
+    }
+
+    #[rstest]
+    #[case('N', 'N')]
+    #[case('N', 'B')]
+    fn test_decode_mbo_msg_none_action_skipped(#[case] action: char, #[case] side: char) {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.action = action as c_char;
+        msg.side = side as c_char;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (delta, trade) =
+            decode_mbo_msg(&msg, instrument_id, 2, 0, true, &DecodeOptions::default()).unwrap();
+
+        assert!(delta.is_none());
+        assert!(trade.is_none());
+        assert!(
+            validate_record(&rec_ref, instrument_id, 2, false, &DecodeOptions::default()).is_ok()
+        );
+    }
+
+    #[rstest]
+    #[case('A', AggressorSide::Seller)]
+    #[case('B', AggressorSide::Buyer)]
+    #[case('N', AggressorSide::NoAggressor)]
+    fn test_decode_mbo_msg_trade_action(#[case] side: char, #[case] expected: AggressorSide) {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.action = 'T' as c_char;
+        msg.side = side as c_char;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let (delta, trade) =
+            decode_mbo_msg(&msg, instrument_id, 2, 0, true, &DecodeOptions::default()).unwrap();
+
+        let trade = trade.unwrap();
+        assert!(delta.is_none());
+        assert_eq!(trade.price.raw, msg.price);
+        assert_eq!(trade.aggressor_side, expected);
+    }
+
+    #[rstest]
+    #[case('A', false)]
+    #[case('T', true)]
+    #[case('T', false)]
+    fn test_decode_record_data_mbo(#[case] action: char, #[case] include_trades: bool) {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.action = action as c_char;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let result = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            include_trades,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+
+        match (action, include_trades) {
+            ('A', _) => assert!(matches!(result, DecodedRecord::One(Data::Delta(_)))),
+            ('T', true) => assert!(matches!(result, DecodedRecord::One(Data::Trade(_)))),
+            _ => assert!(matches!(result, DecodedRecord::Empty)),
+        }
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_decode_record_data_mbp1(#[case] include_trades: bool) {
+        let mut msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        msg.action = 'T' as c_char;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let result = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            include_trades,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+
+        if include_trades {
+            assert!(matches!(
+                result,
+                DecodedRecord::Two(Data::Quote(_), Data::Trade(_))
+            ));
+        } else {
+            assert!(matches!(result, DecodedRecord::One(Data::Quote(_))));
+        }
+    }
+
+    #[rstest]
+    fn test_decoded_record_into_options() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let trade = decode_trade_msg(
+            &msg,
+            InstrumentId::from("ESM4.GLBX"),
+            2,
+            0,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+
+        let empty: (Option<Data>, Option<Data>) = DecodedRecord::Empty.into();
+        let one: (Option<Data>, Option<Data>) = DecodedRecord::One(Data::Trade(trade)).into();
+        let two: (Option<Data>, Option<Data>) =
+            DecodedRecord::Two(Data::Trade(trade), Data::Trade(trade)).into();
+
+        assert!(matches!(empty, (None, None)));
+        assert!(matches!(one, (Some(Data::Trade(_)), None)));
+        assert!(matches!(two, (Some(Data::Trade(_)), Some(Data::Trade(_)))));
+    }
+
+    #[rstest]
+    fn test_decode_mbp10_msg_include_trades() {
+        let mut msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        msg.action = 'T' as c_char;
+        msg.side = 'B' as c_char;
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (_, excluded) =
+            decode_mbp10_msg(&msg, instrument_id, 2, 0, false, &DecodeOptions::default()).unwrap();
+        let (depth, trade) =
+            decode_mbp10_msg(&msg, instrument_id, 2, 0, true, &DecodeOptions::default()).unwrap();
+
+        let trade = trade.unwrap();
+        assert!(excluded.is_none());
+        assert_eq!(depth.bids[0].price, Price::from("3720.25"));
+        assert_eq!(trade.price.raw, msg.price);
+        assert_eq!(trade.size, Quantity::from(i64::from(msg.size)));
+        assert_eq!(trade.aggressor_side, AggressorSide::Buyer);
+        assert_eq!(trade.ts_event, msg.ts_recv);
+    }
+
+    #[rstest]
+    fn test_decode_record_mbp10_include_trades() {
+        let mut msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        msg.action = 'T' as c_char;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let result = decode_record(&rec_ref, instrument_id, 2, None, true).unwrap();
+
+        assert!(matches!(
+            result,
+            (Some(Data::Depth10(_)), Some(Data::Trade(_)))
+        ));
+    }
+
+    #[rstest]
+    fn test_parse_currency_cached() {
+        let first = parse_currency("AUD").unwrap();
+        let second = parse_currency("AUD").unwrap();
+
+        assert_eq!(first, Currency::AUD());
+        assert_eq!(second, first);
+        assert!(CURRENCY_CACHE.with(|cache| cache.borrow().contains_key("AUD")));
+    }
+
+    #[rstest]
+    fn test_parse_currency_cache_hit_skips_parsing() {
+        // Seed an entry `Currency::from_str` would reject, so only a cache hit can return it
+        CURRENCY_CACHE.with(|cache| {
+            cache
+                .borrow_mut()
+                .insert("ZZX".to_string(), Currency::AUD())
+        });
+        let cache_len = CURRENCY_CACHE.with(|cache| cache.borrow().len());
+
+        let currency = parse_currency("ZZX").unwrap();
+
+        assert_eq!(currency, Currency::AUD());
+        assert!(Currency::from_str("ZZX").is_err());
+        assert_eq!(CURRENCY_CACHE.with(|cache| cache.borrow().len()), cache_len);
+    }
+
+    #[rstest]
+    fn test_parse_currency_unknown_not_cached() {
+        assert!(parse_currency("ZZY").is_err());
+        assert!(CURRENCY_CACHE.with(|cache| !cache.borrow().contains_key("ZZY")));
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_bar_spec_override() {
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1m.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let spec = BarSpecification::new(5, BarAggregation::Minute, PriceType::Last);
+
+        let bar = decode_ohlcv_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                bar_ts_init_policy: BarTsInitPolicy::BarClose,
+                bar_close_timestamps: true,
+                bar_spec_override: Some(spec),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            bar.bar_type,
+            BarType::from("ESM4.GLBX-5-MINUTE-LAST-EXTERNAL")
+        );
+        assert_eq!(
+            bar.ts_event,
+            msg.hd.ts_event + 5 * 60 * NANOSECONDS_IN_SECOND
+        );
+        assert_eq!(bar.ts_init, bar.ts_event);
+    }
+
+    #[rstest]
+    #[case(BarSpecification::new(5, BarAggregation::Second, PriceType::Last))]
+    #[case(BarSpecification::new(5, BarAggregation::Minute, PriceType::Mid))]
+    #[case(BarSpecification::new(0, BarAggregation::Minute, PriceType::Last))]
+    fn test_decode_bar_type_with_override_invalid(#[case] spec: BarSpecification) {
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1m.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let err = decode_bar_type_with_override(&msg, instrument_id, Some(spec)).unwrap_err();
+
+        assert!(err.to_string().starts_with("Invalid bar spec override"));
+    }
+
+    #[rstest]
+    #[case(BarValidationPolicy::Off)]
+    #[case(BarValidationPolicy::Reject)]
+    #[case(BarValidationPolicy::Skip)]
+    fn test_decode_ohlcv_msg_validation_well_formed(#[case] policy: BarValidationPolicy) {
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let bar = decode_ohlcv_msg_with_empty_bar_policy(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                bar_validation_policy: policy,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .unwrap();
+
+        assert!(validate_bar(&bar).is_ok());
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_validation_high_below_low() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        std::mem::swap(&mut msg.high, &mut msg.low);
+        msg.high -= 1_000_000_000;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let decode = |policy| {
+            decode_ohlcv_msg_with_empty_bar_policy(
+                &msg,
+                instrument_id,
+                2,
+                0,
+                &DecodeOptions {
+                    bar_validation_policy: policy,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let unvalidated = decode(BarValidationPolicy::Off).unwrap();
+        let err = decode(BarValidationPolicy::Reject).unwrap_err();
+        let skipped = decode(BarValidationPolicy::Skip).unwrap();
+
+        assert!(unvalidated.is_some());
+        assert!(matches!(err, DatabentoDecodeError::InvalidBar { .. }));
+        assert!(err.to_string().contains("below low"));
+        assert!(skipped.is_none());
+    }
+
+    #[rstest]
+    fn test_decode_record_data_bar_validation_policy() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        std::mem::swap(&mut msg.high, &mut msg.low);
+        msg.high -= 1_000_000_000;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let decode = |policy| {
+            decode_record_data(
+                &rec_ref,
+                instrument_id,
+                2,
+                None,
+                false,
+                &DecodeOptions {
+                    bar_validation_policy: policy,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let unvalidated = decode(BarValidationPolicy::Off).unwrap();
+        let err = decode(BarValidationPolicy::Reject).unwrap_err();
+        let skipped = decode(BarValidationPolicy::Skip).unwrap();
+
+        assert!(matches!(unvalidated, DecodedRecord::One(Data::Bar(_))));
+        assert!(matches!(
+            err.root(),
+            DatabentoDecodeError::InvalidBar { .. }
+        ));
+        assert!(matches!(skipped, DecodedRecord::Empty));
+    }
+
+    #[rstest]
+    #[case("ESVUFR", Some(EquityClass::CommonStock))]
+    #[case("EPNXFR", Some(EquityClass::PreferredStock))]
+    #[case("EDSXFR", Some(EquityClass::DepositaryReceipt))]
+    #[case("CEOGEU", Some(EquityClass::Etf))]
+    #[case("CRXXXX", Some(EquityClass::Reit))]
+    #[case("CIOGES", Some(EquityClass::Fund))]
+    #[case("ELXXXX", Some(EquityClass::Other))]
+    #[case("FFICSX", None)]
+    #[case("", None)]
+    fn test_parse_cfi_equity_class(#[case] value: &str, #[case] expected: Option<EquityClass>) {
+        assert_eq!(parse_cfi_equity_class(value), expected);
+    }
+
+    #[rstest]
+    fn test_decode_equity_etf_vs_common_stock() {
+        let mut etf_msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        etf_msg.cfi = str_to_c_chars("CEOGEU");
+        let mut stock_msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        stock_msg.cfi = str_to_c_chars("ESVUFR");
+        let instrument_id = InstrumentId::from("MSFT.XNAS");
+
+        let (etf, etf_class) = decode_equity_with_class_v1(&etf_msg, instrument_id, 0).unwrap();
+        let (stock, stock_class) =
+            decode_equity_with_class_v1(&stock_msg, instrument_id, 0).unwrap();
+
+        assert_eq!(etf.asset_class(), AssetClass::Equity);
+        assert_eq!(stock.asset_class(), AssetClass::Equity);
+        assert_eq!(etf_class, Some(EquityClass::Etf));
+        assert_eq!(stock_class, Some(EquityClass::CommonStock));
+    }
+
+    #[rstest]
+    fn test_decode_equity_with_class_empty_cfi() {
+        let mut msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        msg.cfi = str_to_c_chars("");
+        let instrument_id = InstrumentId::from("MSFT.XNAS");
+
+        let (_, equity_class) = decode_equity_with_class_v1(&msg, instrument_id, 0).unwrap();
+
+        assert_eq!(equity_class, None);
+    }
+
+    #[rstest]
+    fn test_coalesce_depth10_snapshots() {
+        let first_msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let depths: Vec<OrderBookDepth10> = (0..5)
+            .map(|i| {
+                let mut msg = first_msg;
+                msg.sequence = i;
+                msg.flags = if i < 3 {
+                    RecordFlags::F_SNAPSHOT | RecordFlags::F_LAST
+                } else {
+                    RecordFlags::F_LAST
+                };
+                decode_mbp10_msg(&msg, instrument_id, 2, 0, false, &DecodeOptions::default())
+                    .unwrap()
+                    .0
+            })
+            .collect();
+
+        let coalesced = coalesce_depth10_snapshots(depths);
+
+        assert!(is_snapshot(coalesced[0].flags));
+        assert!(!is_snapshot(coalesced[1].flags));
+        assert_eq!(
+            coalesced
+                .iter()
+                .map(|depth| depth.sequence)
+                .collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+    }
+
+    #[rstest]
+    fn test_decode_record_sequence_quote() {
+        let msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (data, _) = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap();
+        let sequence = decode_record_sequence(&rec_ref).unwrap();
+
+        assert!(matches!(data, Some(Data::Quote(_))));
+        assert_eq!(sequence, Some(msg.sequence));
+    }
+
+    #[rstest]
+    fn test_decode_record_sequence_trade() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (data, _) = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap();
+        let sequence = decode_record_sequence(&rec_ref).unwrap();
+
+        assert!(matches!(data, Some(Data::Trade(_))));
+        assert_eq!(sequence, Some(msg.sequence));
+    }
+
+    #[rstest]
+    fn test_decode_record_sequence_matches_depth() {
+        let msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (data, _) = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap();
+        let sequence = decode_record_sequence(&rec_ref).unwrap();
+
+        match data {
+            Some(Data::Depth10(depth)) => assert_eq!(sequence.map(u64::from), Some(depth.sequence)),
+            _ => panic!("Expected depth"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_record_sequence_none_for_bars() {
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+
+        let sequence = decode_record_sequence(&dbn::RecordRef::from(&msg)).unwrap();
+
+        assert_eq!(sequence, None);
+    }
+
+    #[rstest]
+    fn test_decode_options_contract_opra_default_multiplier() {
+        let msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-opra.dbn.zst");
+        let instrument_id = InstrumentId::from("SPY   240119P00340000.OPRA");
+
+        let option = decode_options_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(option.multiplier, Quantity::from(100));
+        assert_eq!(option.lot_size, Quantity::from(1));
+    }
+
+    #[rstest]
+    #[case(i64::MAX, 10, 30, 10)] // Mini options
+    #[case(i64::MAX, i32::MAX, 30, 100)]
+    #[case(i64::MAX, i32::MAX, 22, 100)]
+    #[case(i64::MAX, i32::MAX, 1, 1)]
+    #[case(50_000_000_000, i32::MAX, 1, 50)]
+    fn test_decode_options_multiplier(
+        #[case] unit_of_measure_qty: i64,
+        #[case] contract_multiplier: i32,
+        #[case] publisher_id: PublisherId,
+        #[case] expected: i64,
+    ) {
+        let multiplier =
+            decode_options_multiplier(unit_of_measure_qty, contract_multiplier, publisher_id)
+                .unwrap();
+
+        assert_eq!(multiplier, Quantity::from(expected));
+    }
+
+    fn trades_symbol_map() -> DatabentoSymbolMap {
+        let mut symbol_map = DatabentoSymbolMap::new(IndexMap::new());
+        let msg = dbn::SymbolMappingMsg::new(
+            5482,
+            0,
+            dbn::SType::RawSymbol,
+            "ESH1",
+            dbn::SType::RawSymbol,
+            "ESH1",
+            0,
+            dbn::UNDEF_TIMESTAMP,
+        )
+        .unwrap();
+        symbol_map.on_symbol_mapping(&msg).unwrap();
+        symbol_map
+    }
+
+    #[rstest]
+    fn test_decode_dbn_record_bytes_round_trip() {
+        let symbol_map = trades_symbol_map();
+        let msgs = load_records::<dbn::TradeMsg>("trades.dbn.zst");
+        let mut buf = Vec::new();
+        for msg in &msgs {
+            buf.extend_from_slice(msg.as_ref());
+        }
+
+        let (consumed, first) = decode_dbn_record_bytes(&buf, &symbol_map, 2, None, false).unwrap();
+        let (consumed_last, last) =
+            decode_dbn_record_bytes(&buf[consumed..], &symbol_map, 2, None, false).unwrap();
+
+        assert_eq!(consumed, mem::size_of::<dbn::TradeMsg>());
+        assert_eq!(consumed + consumed_last, buf.len());
+        match (first, last) {
+            (DecodedRecord::One(Data::Trade(first)), DecodedRecord::One(Data::Trade(last))) => {
+                assert_eq!(first.instrument_id, InstrumentId::from("ESH1.GLBX"));
+                assert_eq!(first.ts_event, msgs[0].ts_recv);
+                assert_eq!(last.ts_event, msgs[1].ts_recv);
+            }
+            _ => panic!("Expected trades"),
+        }
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(10)]
+    #[case(20)]
+    fn test_decode_dbn_record_bytes_incomplete(#[case] available: usize) {
+        let symbol_map = trades_symbol_map();
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let buf: &[u8] = msg.as_ref();
+
+        let err =
+            decode_dbn_record_bytes(&buf[..available], &symbol_map, 2, None, false).unwrap_err();
+
+        assert!(matches!(
+            err,
+            DatabentoDecodeError::Incomplete { available: a, .. } if a == available
+        ));
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_fractional_volume() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.volume = 12_345_678; // 0.12345678 BTC in units of the smallest lot
+        let instrument_id = InstrumentId::from("BTCUSDT.BINANCE");
+
+        let bar = decode_ohlcv_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                size_precision: 8,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(bar.open, Price::from("3720.25"));
+        assert_eq!(bar.close, Price::from("3720.50"));
+        assert_eq!(bar.volume.precision, 8);
+        assert_eq!(bar.volume.raw, 123_456_780);
+        assert_eq!(bar.volume.to_string(), "0.12345678");
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_msg_volume_ignores_display_factor() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.volume = 10;
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let bar = decode_ohlcv_msg(
+            &msg,
+            instrument_id,
+            4,
+            0,
+            &DecodeOptions {
+                display_factor: Some(10_000_000), // Quoted in cents
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(bar.volume, Quantity::from(10));
+    }
+
+    #[rstest]
+    fn test_decode_record_with_precision_map_fractional_volume() {
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        msg.volume = 12_345_678; // 0.12345678 BTC in units of the smallest lot
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("BTCUSDT.BINANCE");
+        let mut precision_map = PrecisionMap::default();
+        precision_map.insert_size_precision(instrument_id, 8);
+
+        let (data, _) =
+            decode_record_with_precision_map(&rec_ref, instrument_id, &precision_map, None, false)
+                .unwrap();
+
+        match data {
+            Some(Data::Bar(bar)) => {
+                assert_eq!(bar.open, Price::from("3720.25"));
+                assert_eq!(bar.close, Price::from("3720.50"));
+                assert_eq!(bar.volume.precision, 8);
+                assert_eq!(bar.volume.to_string(), "0.12345678");
+            }
+            _ => panic!("Expected bar"),
+        }
+    }
+
+    #[rstest]
+    #[case(1_234, 1, 0, "1234")]
+    #[case(150, 1, 2, "1.50")]
+    #[case(3, 100, 0, "300")]
+    fn test_decode_bar_volume(
+        #[case] volume: u64,
+        #[case] size_multiplier: u64,
+        #[case] size_precision: u8,
+        #[case] expected: &str,
+    ) {
+        let volume = decode_bar_volume(volume, size_multiplier, size_precision).unwrap();
+
+        assert_eq!(volume, Quantity::from(expected));
+    }
+
+    #[rstest]
+    fn test_decode_bar_volume_overflow() {
+        assert!(decode_bar_volume(u64::MAX, 1, 0).is_err());
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_with_deltas_reconstructs_top_of_book() {
+        let msgs = load_records::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let mut book = OrderBookMbp::new(instrument_id, true);
+
+        for msg in &msgs {
+            let (quote, _, deltas) = decode_mbp1_msg_with_deltas(
+                msg,
+                instrument_id,
+                2,
+                0,
+                false,
+                &DecodeOptions::default(),
+            )
+            .unwrap();
+            for delta in deltas {
+                book.apply_delta(delta);
+            }
+
+            assert_eq!(deltas[0].order.side, OrderSide::Buy);
+            assert_eq!(deltas[1].order.side, OrderSide::Sell);
+            assert_eq!(book.best_bid_price(), Some(quote.bid_price));
+            assert_eq!(book.best_ask_price(), Some(quote.ask_price));
+            assert_eq!(book.best_bid_size(), Some(quote.bid_size));
+            assert_eq!(book.best_ask_size(), Some(quote.ask_size));
+        }
+    }
+
+    #[rstest]
+    fn test_decode_record_channel_id_mbo() {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.channel_id = 7;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (data, _) = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap();
+        let channel_id = decode_record_channel_id(&rec_ref).unwrap();
+
+        assert!(matches!(data, Some(Data::Delta(_))));
+        assert_eq!(channel_id, Some(7));
+    }
+
+    #[rstest]
+    fn test_decode_record_channel_id_none_for_trades() {
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+
+        let channel_id = decode_record_channel_id(&dbn::RecordRef::from(&msg)).unwrap();
+
+        assert_eq!(channel_id, None);
+    }
+
+    #[rstest]
+    fn test_decode_index_instrument_v1() {
+        let mut msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        msg.instrument_class = 'Y' as c_char;
+        msg.min_price_increment = 10_000_000; // 0.01
+        let instrument_id = InstrumentId::from("SPX.XCBO");
+
+        let instrument = decode_instrument_def_msg_v1(&msg, instrument_id, 0, None).unwrap();
+
+        let index = instrument
+            .as_any()
+            .downcast_ref::<IndexInstrument>()
+            .unwrap();
+        assert_eq!(index.id, instrument_id);
+        assert_eq!(index.asset_class(), AssetClass::Index);
+        assert_eq!(index.instrument_class(), InstrumentClass::Spot);
+        assert_eq!(index.price_precision, 2);
+        assert_eq!(index.price_increment, Price::from("0.01"));
+        assert_eq!(index.lot_size(), None);
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_price_limits() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.high_limit_price = 4_500_250_000_000; // 4500.25
+        msg.low_limit_price = 4_100_000_000_000; // 4100.00
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.max_price(), Some(Price::from("4500.25")));
+        assert_eq!(future.min_price(), Some(Price::from("4100.00")));
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_undefined_price_limits() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.high_limit_price = dbn::UNDEF_PRICE;
+        msg.low_limit_price = dbn::UNDEF_PRICE;
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.max_price(), None);
+        assert_eq!(future.min_price(), None);
+    }
+
+    #[rstest]
+    fn test_decode_records_lenient_mixed_batch() {
+        let trade = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let definition = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        let records = vec![
+            dbn::RecordRef::from(&trade),
+            dbn::RecordRef::from(&definition),
+            dbn::RecordRef::from(&trade),
+        ];
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (data, errors) = decode_records_lenient(&records, instrument_id, 2, None, false);
+
+        assert_eq!(data.len(), 2);
+        assert!(data.iter().all(|data| matches!(data, Data::Trade(_))));
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            DatabentoDecodeError::UnsupportedRType(dbn::rtype::INSTRUMENT_DEF)
+        ));
+    }
+
+    #[rstest]
+    #[case(0.01, 2)]
+    #[case(0.25, 2)]
+    #[case(0.0001, 4)]
+    #[case(0.000_000_001, 9)]
+    #[case(0.5, 1)]
+    #[case(1.0, 0)]
+    #[case(25.0, 0)]
+    fn test_precision_from_increment(#[case] increment: f64, #[case] expected: u8) {
+        assert_eq!(precision_from_increment(increment), expected);
+    }
+
+    #[rstest]
+    #[case(10_000_000, 2)]
+    #[case(250_000_000, 2)]
+    #[case(100_000, 4)]
+    #[case(1, 9)]
+    #[case(1_000_000_000, 0)]
+    #[case(5_000_000_000, 0)]
+    fn test_precision_from_raw_increment(#[case] raw: i64, #[case] expected: u8) {
+        assert_eq!(precision_from_raw_increment(raw), expected);
+    }
+
+    #[rstest]
+    fn test_decode_record_data_heartbeat() {
+        let msg = dbn::SystemMsg::heartbeat(1_000);
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let result = decode_record_data(
+            &rec_ref,
+            instrument_id,
+            2,
+            None,
+            false,
+            &DecodeOptions::default(),
+        )
+        .unwrap();
+
+        assert!(matches!(result, DecodedRecord::Heartbeat));
+    }
+
+    #[rstest]
+    fn test_decode_gateway_record_system_msg() {
+        let msg = dbn::SystemMsg::new(1_000, "Subscription request 1 succeeded").unwrap();
+
+        let result = decode_gateway_record(&dbn::RecordRef::from(&msg)).unwrap();
+
+        match result {
+            DecodedRecord::System(text) => assert_eq!(text, "Subscription request 1 succeeded"),
+            _ => panic!("Expected system message, was {result:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_gateway_record_error_msg() {
+        let msg = dbn::ErrorMsg::new(1_000, "Authentication failed");
+
+        let result = decode_gateway_record(&dbn::RecordRef::from(&msg)).unwrap();
+
+        match result {
+            DecodedRecord::Error(text) => assert_eq!(text, "Authentication failed"),
+            _ => panic!("Expected error message, was {result:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_size_near_max() {
+        let size = decode_size(u32::MAX, 1).unwrap();
+
+        assert_eq!(size.raw, u64::from(u32::MAX) * FIXED_SCALAR as u64);
+    }
+
+    #[rstest]
+    fn test_decode_size_overflow_is_error() {
+        let result = decode_size(u32::MAX - 1, 100);
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_decode_mbp10_as_deltas_reconstructs_depth() {
+        let mut msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        msg.levels[9].bid_px = dbn::UNDEF_PRICE;
+        msg.levels[9].bid_sz = dbn::UNDEF_ORDER_SIZE;
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let deltas =
+            decode_mbp10_as_deltas(&msg, instrument_id, 2, 0, &DecodeOptions::default()).unwrap();
+        let (depth, _) =
+            decode_mbp10_msg(&msg, instrument_id, 2, 0, false, &DecodeOptions::default()).unwrap();
+
+        assert_eq!(deltas.len(), 1 + 2 * DEPTH10_LEN - 1);
+        assert_eq!(deltas[0].action, BookAction::Clear);
+        let (bids, asks): (Vec<_>, Vec<_>) = deltas[1..]
+            .iter()
+            .inspect(|delta| assert_eq!(delta.action, BookAction::Add))
+            .map(|delta| delta.order)
+            .partition(|order| order.side == OrderSide::Buy);
+        assert_eq!(bids.len(), DEPTH10_LEN - 1);
+        assert_eq!(asks.len(), DEPTH10_LEN);
+        for (order, expected) in bids.iter().zip(&depth.bids) {
+            assert_eq!((order.price, order.size), (expected.price, expected.size));
+        }
+        for (order, expected) in asks.iter().zip(&depth.asks) {
+            assert_eq!((order.price, order.size), (expected.price, expected.size));
+        }
+        assert!(deltas[..deltas.len() - 1]
+            .iter()
+            .all(|delta| delta.flags & RecordFlags::F_LAST == 0));
+        assert_eq!(deltas.last().unwrap().flags, msg.flags);
+
+        let mut book = OrderBookMbp::new(instrument_id, false);
+        for delta in deltas {
+            book.apply_delta(delta);
+        }
+        assert_eq!(book.best_bid_price(), Some(depth.bids[0].price));
+        assert_eq!(book.best_ask_price(), Some(depth.asks[0].price));
+        assert_eq!(book.bids().count(), DEPTH10_LEN - 1);
+    }
+
+    #[rstest]
+    fn test_decode_mbp10_msg_size_precision() {
+        let mut msg = load_first_record::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        msg.action = 'T' as c_char;
+        msg.size = 150;
+        msg.levels[0].bid_sz = 250;
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let options = DecodeOptions {
+            size_precision: 2,
+            ..Default::default()
+        };
+
+        let (depth, trade) = decode_mbp10_msg(&msg, instrument_id, 2, 0, true, &options).unwrap();
+        let deltas = decode_mbp10_as_deltas(&msg, instrument_id, 2, 0, &options).unwrap();
+
+        assert_eq!(depth.bids[0].size, Quantity::from("2.50"));
+        assert_eq!(trade.unwrap().size, Quantity::from("1.50"));
+        assert_eq!(deltas[1].order.size, Quantity::from("2.50"));
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_undefined_activation() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.activation = dbn::UNDEF_TIMESTAMP;
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(future.activation_ns, 0);
+        assert_eq!(future.expiration_ns, msg.expiration);
+    }
+
+    #[rstest]
+    fn test_decode_futures_contract_v1_undefined_expiration() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.expiration = dbn::UNDEF_TIMESTAMP;
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let future = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(decode_expiration(msg.expiration), None);
+        assert_eq!(future.expiration_ns, UnixNanos::MAX);
+    }
+
+    #[rstest]
+    #[case("JPY", Currency::JPY())]
+    #[case("", Currency::USD())]
+    fn test_decode_futures_spread_v1_currency(#[case] value: &str, #[case] expected: Currency) {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.currency = str_to_c_chars(value);
+        let instrument_id = InstrumentId::from("ESM3-ESU3.GLBX");
+
+        let spread = decode_futures_spread_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(spread.currency, expected);
+    }
+
+    #[rstest]
+    fn test_decode_futures_spread_v1_undefined_expiration() {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.expiration = dbn::UNDEF_TIMESTAMP;
+        let instrument_id = InstrumentId::from("ESM3-ESU3.GLBX");
+
+        let spread = decode_futures_spread_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(spread.expiration_ns, UnixNanos::MAX);
+    }
+
+    #[rstest]
+    #[case(150, 0, "150")]
+    #[case(150, 2, "1.50")]
+    #[case(1, 8, "0.00000001")]
+    fn test_decode_scaled_size(
+        #[case] size: u32,
+        #[case] size_precision: u8,
+        #[case] expected: &str,
+    ) {
+        let quantity = decode_scaled_size(size, 1, size_precision).unwrap();
+
+        assert_eq!(quantity, Quantity::from(expected));
+        assert_eq!(quantity.precision, size_precision);
+    }
+
+    #[rstest]
+    fn test_decode_trade_msg_fractional_size() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.size = 12_345;
+        let instrument_id = InstrumentId::from("BTCUSD.XCME");
+
+        let trade = decode_trade_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            &DecodeOptions {
+                size_precision: 4,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(trade.size, Quantity::from("1.2345"));
+    }
+
+    #[rstest]
+    #[case(250_000_000, 2)] // 0.25
+    #[case(100_000, 4)] // 0.0001
+    #[case(5_000_000_000, 0)] // 5
+    fn test_decode_instrument_def_msg_with_precision_v1(
+        #[case] min_price_increment: i64,
+        #[case] expected: u8,
+    ) {
+        let mut msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        msg.min_price_increment = min_price_increment;
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+
+        let (instrument, price_precision) =
+            decode_instrument_def_msg_with_precision_v1(&msg, instrument_id, 0, None).unwrap();
+
+        assert_eq!(price_precision, expected);
+        assert_eq!(instrument.price_precision(), expected);
+        assert_eq!(
+            price_precision,
+            precision_from_raw_increment(min_price_increment)
+        );
+    }
+
+    #[rstest]
+    fn test_mbo_depth_builder_20_levels() {
+        let template = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        let mut builder = MboDepthBuilder::new(instrument_id);
+
+        // Two orders per level, 20 levels each side around a mid of 4000.00
+        let mut order_id = 0;
+        for i in 0..20 {
+            for (side, price) in [
+                ('B', 3_999_750_000_000 - i * 250_000_000),
+                ('A', 4_000_000_000_000 + i * 250_000_000),
+            ] {
+                for _ in 0..2 {
+                    order_id += 1;
+                    let mut msg = template.clone();
+                    msg.action = 'A' as c_char;
+                    msg.side = side as c_char;
+                    msg.price = price;
+                    msg.size = 5;
+                    msg.order_id = order_id;
+                    let (delta, _) =
+                        decode_mbo_msg(&msg, instrument_id, 2, 0, false, &DecodeOptions::default())
+                            .unwrap();
+                    builder.apply(delta.unwrap());
+                }
+            }
+        }
+
+        let depth = builder.depth(20).unwrap();
+
+        assert_eq!(depth.bids.len(), 20);
+        assert_eq!(depth.asks.len(), 20);
+        assert_eq!(depth.bids[0].price, Price::from("3999.75"));
+        assert_eq!(depth.bids[19].price, Price::from("3995.00"));
+        assert_eq!(depth.asks[0].price, Price::from("4000.00"));
+        assert_eq!(depth.asks[19].price, Price::from("4004.75"));
+        assert_eq!(depth.bids[0].size, Quantity::from(10));
+        assert_eq!(depth.bids[0].count, 2);
+
+        // Fewer levels than requested are not padded
+        assert_eq!(builder.depth(MAX_BOOK_DEPTH).unwrap().bids.len(), 20);
+        assert!(builder.depth(MAX_BOOK_DEPTH + 1).is_err());
+    }
+
+    #[rstest]
+    fn test_decode_record_with_raw_instrument_id() {
+        let mut msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        msg.action = 'T' as c_char;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (data1, data2) =
+            decode_record_with_raw_instrument_id(&rec_ref, instrument_id, 2, None, true).unwrap();
+
+        let (data1, data2) = (data1.unwrap(), data2.unwrap());
+        assert_eq!(data1.raw_instrument_id, 5482);
+        assert_eq!(data2.raw_instrument_id, 5482);
+        assert!(matches!(data1.data, Data::Quote(quote) if quote.instrument_id == instrument_id));
+        assert!(matches!(data2.data, Data::Trade(trade) if trade.instrument_id == instrument_id));
+    }
+
+    #[rstest]
+    fn test_decode_error_invalid_cfi() {
+        let err = parse_cfi_iso10926("F").unwrap_err();
+
+        assert!(matches!(err, DatabentoDecodeError::InvalidCfi(ref value) if value == "F"));
+    }
+
+    #[rstest]
+    fn test_decode_error_unknown_currency() {
+        let err = parse_currency("ZZZ").unwrap_err();
+
+        assert!(matches!(err, DatabentoDecodeError::UnknownCurrency(ref value) if value == "ZZZ"));
+    }
+
+    #[rstest]
+    fn test_decode_error_utf8() {
+        let value: [c_char; 3] = [0xFFu8 as c_char, 0x41, 0];
+
+        let err = fixed_bytes_to_str(&value).unwrap_err();
+
+        assert!(matches!(err, DatabentoDecodeError::Utf8(_)));
+    }
+
+    #[rstest]
+    fn test_fixed_bytes_to_str_trims_at_nul() {
+        let value: [c_char; 8] = str_to_c_chars("ESH1");
+
+        assert_eq!(fixed_bytes_to_str(&value).unwrap(), "ESH1");
+    }
+
+    #[rstest]
+    fn test_fixed_bytes_to_str_without_nul_terminator() {
+        let value: [c_char; dbn::SYMBOL_CSTR_LEN_V1] = [b'A' as c_char; dbn::SYMBOL_CSTR_LEN_V1];
+
+        let result = fixed_bytes_to_str(&value).unwrap();
+
+        assert_eq!(result.len(), 22);
+        assert!(result.chars().all(|c| c == 'A'));
+    }
+
+    #[rstest]
+    fn test_decode_error_unsupported_rtype() {
+        let msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("MSFT.XNAS");
+
+        let err = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap_err();
+
+        assert!(matches!(
+            err,
+            DatabentoDecodeError::UnsupportedRType(dbn::rtype::INSTRUMENT_DEF)
+        ));
+    }
+
+    #[rstest]
+    fn test_decode_error_unsupported_instrument_class() {
+        let mut msg = load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition.dbn.zst");
+        msg.instrument_class = 'Z' as c_char;
+        let instrument_id = InstrumentId::from("MSFT.XNAS");
+
+        let err = decode_instrument_def_msg_v1(&msg, instrument_id, 0, None).unwrap_err();
+
+        assert!(matches!(
+            err,
+            DatabentoDecodeError::UnsupportedInstrumentClass('Z')
+        ));
+    }
+
+    #[rstest]
+    fn test_decode_error_unsupported_version() {
+        let decoder = Decoder::from_zstd_file(test_data_path().join("trades.dbn.zst")).unwrap();
+        let mut metadata = decoder.metadata().clone();
+        metadata.version = 99;
+
+        let err = check_dbn_version(&metadata).unwrap_err();
+
+        assert!(matches!(err, DatabentoDecodeError::UnsupportedVersion(99)));
+    }
+
+    #[rstest]
+    fn test_decode_error_context_root() {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.action = 'Z' as c_char;
+        msg.side = 'B' as c_char;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+
+        let err = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap_err();
+
+        assert!(matches!(err, DatabentoDecodeError::Context { .. }));
+        assert!(err.to_string().contains("ESM4.GLBX"));
+        assert!(matches!(
+            err.root(),
+            DatabentoDecodeError::InvalidBookAction('Z')
+        ));
+    }
+
+    #[rstest]
+    fn test_decode_error_into_anyhow() {
+        let err: anyhow::Error = parse_book_action('Z' as c_char).unwrap_err().into();
+
+        assert_eq!(err.to_string(), "Invalid `BookAction`, was 'Z'");
+        assert!(err.downcast_ref::<DatabentoDecodeError>().is_some());
+    }
+
+    fn crossed_mbp1_msg() -> dbn::Mbp1Msg {
+        let mut msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        msg.levels[0].bid_px = 3_721_000_000_000;
+        msg.levels[0].ask_px = 3_720_500_000_000;
+        msg.levels[0].bid_sz = 5;
+        msg.levels[0].ask_sz = 7;
+        msg
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_crossed_quote_allow() {
+        let msg = crossed_mbp1_msg();
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (quote, _) = decode_mbp1_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            false,
+            &DecodeOptions {
+                crossed_quote_policy: CrossedQuotePolicy::Allow,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(quote.bid_price, Price::from("3721.00"));
+        assert_eq!(quote.ask_price, Price::from("3720.50"));
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_crossed_quote_normalize() {
+        let msg = crossed_mbp1_msg();
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let (quote, _) = decode_mbp1_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            false,
+            &DecodeOptions {
+                crossed_quote_policy: CrossedQuotePolicy::Normalize,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(quote.bid_price, Price::from("3721.00"));
+        assert_eq!(quote.ask_price, Price::from("3721.00"));
+        assert_eq!(quote.bid_size, Quantity::from(5));
+        assert_eq!(quote.ask_size, Quantity::from(7));
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_crossed_quote_reject() {
+        let msg = crossed_mbp1_msg();
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let err = decode_mbp1_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            false,
+            &DecodeOptions {
+                crossed_quote_policy: CrossedQuotePolicy::Reject,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, DatabentoDecodeError::CrossedQuote { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Crossed quote for ESH1.GLBX, bid 3721.00 above ask 3720.50"
+        );
+    }
+
+    #[rstest]
+    fn test_decode_mbp1_msg_crossed_quote_reject_ignores_empty_side() {
+        let mut msg = crossed_mbp1_msg();
+        msg.levels[0].ask_sz = 0;
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let result = decode_mbp1_msg(
+            &msg,
+            instrument_id,
+            2,
+            0,
+            false,
+            &DecodeOptions {
+                crossed_quote_policy: CrossedQuotePolicy::Reject,
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    fn test_decode_asset_class_override_wins_over_cfi() {
+        let msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        let instrument_id = InstrumentId::from("ESM3.GLBX");
+        let overrides = HashMap::from([(instrument_id, AssetClass::Commodity)]);
+
+        let inferred = decode_futures_contract_v1(&msg, instrument_id, 0, None).unwrap();
+        let overridden =
+            decode_futures_contract_v1(&msg, instrument_id, 0, Some(&overrides)).unwrap();
+
+        // CFI code FFIXSX infers an index future
+        assert_eq!(inferred.asset_class(), AssetClass::Index);
+        assert_eq!(overridden.asset_class(), AssetClass::Commodity);
+    }
+
+    #[rstest]
+    fn test_decode_asset_class_override_other_instrument() {
+        let instrument_id = InstrumentId::from("ESM4.GLBX");
+        let overrides = HashMap::from([(InstrumentId::from("NQM4.GLBX"), AssetClass::Index)]);
+
+        let asset_class = decode_asset_class(instrument_id, "FXXXXX", Some(&overrides)).unwrap();
+
+        assert_eq!(asset_class, None);
+    }
+
+    #[rstest]
+    fn test_decode_asset_class_override_wins_over_opra() {
+        let instrument_id = InstrumentId::from("SPY   240621C00500000.OPRA");
+        let overrides = HashMap::from([(instrument_id, AssetClass::Index)]);
+
+        let asset_class = decode_asset_class(instrument_id, "", Some(&overrides)).unwrap();
+
+        assert_eq!(asset_class, Some(AssetClass::Index));
+    }
+
+    #[rstest]
+    fn test_decode_record_mismatched_payload_returns_error() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.hd.rtype = dbn::rtype::MBP_10;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let err = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap_err();
+
+        assert!(matches!(
+            err,
+            DatabentoDecodeError::InvalidRecord {
+                rtype: dbn::rtype::MBP_10,
+                length: 48,
+            }
+        ));
+    }
+
+    #[rstest]
+    fn test_decode_record_truncated_mbo_returns_error() {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.hd.length = 10; // Truncated to 40 bytes, as if partially written
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let err = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap_err();
+
+        assert!(matches!(
+            err,
+            DatabentoDecodeError::InvalidRecord {
+                rtype: dbn::rtype::MBO,
+                length: 40,
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Invalid record for RType 0xa0 with length 40"
+        );
+    }
+
+    #[rstest]
+    fn test_decode_record_invalid_rtype_returns_error() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.hd.rtype = 0xFF;
+        let rec_ref = dbn::RecordRef::from(&msg);
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let err = decode_record(&rec_ref, instrument_id, 2, None, false).unwrap_err();
+
+        assert!(matches!(err, DatabentoDecodeError::Dbn(_)));
+    }
+
+    #[rstest]
+    fn test_decode_record_order_key_mismatched_payload_returns_error() {
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.hd.rtype = dbn::rtype::MBP_10;
+        let rec_ref = dbn::RecordRef::from(&msg);
+
+        assert!(decode_record_order_key(&rec_ref, 0).is_err());
+    }
+
+    #[rstest]
+    fn test_decode_records_matches_sequential_decode_record() {
+        let mut msgs = load_records::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        for msg in msgs.iter_mut().step_by(2) {
+            msg.action = 'T' as c_char;
+        }
+        let records: Vec<dbn::RecordRef> = msgs.iter().map(dbn::RecordRef::from).collect();
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let mut expected = Vec::new();
+        for rec_ref in &records {
+            let (data1, data2) = decode_record(rec_ref, instrument_id, 2, None, true).unwrap();
+            expected.extend(data1);
+            expected.extend(data2);
+        }
+
+        let decoded: Vec<Data> =
+            decode_records(records.iter().copied(), instrument_id, 2, None, true)
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+        assert_eq!(decoded.len(), expected.len());
+        assert!(decoded.len() > msgs.len()); // Quotes interleaved with trades
+        for (data, expected) in decoded.iter().zip(&expected) {
+            assert_eq!(format!("{data:?}"), format!("{expected:?}"));
+        }
+    }
+
+    #[rstest]
+    fn test_decode_records_mbp10() {
+        let msgs = load_records::<dbn::Mbp10Msg>("mbp-10.dbn.zst");
+        let records: Vec<dbn::RecordRef> = msgs.iter().map(dbn::RecordRef::from).collect();
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let decoded: Vec<Data> =
+            decode_records(records.iter().copied(), instrument_id, 2, None, false)
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+        assert_eq!(decoded.len(), msgs.len());
+        match &decoded[0] {
+            Data::Depth10(depth) => {
+                assert_eq!(depth.bids[0].price, Price::from("3720.25"));
+                assert_eq!(depth.bid_counts[0], msgs[0].levels[0].bid_ct);
+            }
+            data => panic!("Expected `OrderBookDepth10`, was {data:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_records_propagates_error() {
+        let mut msg = load_first_record::<dbn::MboMsg>("mbo.dbn.zst");
+        msg.action = 'Z' as c_char;
+        msg.side = 'B' as c_char;
+        let records = [dbn::RecordRef::from(&msg)];
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+
+        let mut decoded = decode_records(records.iter().copied(), instrument_id, 2, None, false);
+
+        assert!(decoded.next().unwrap().is_err());
+        assert!(decoded.next().is_none());
+    }
 }
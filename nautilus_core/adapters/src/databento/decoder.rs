@@ -0,0 +1,686 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{collections::HashMap, mem};
+
+use anyhow::Result;
+
+use nautilus_core::time::UnixNanos;
+use nautilus_model::{
+    data::{bar::BarSpecification, Data},
+    enums::BarAggregation,
+    identifiers::instrument_id::InstrumentId,
+    instruments::Instrument,
+    types::{currency::Currency, price::Price},
+};
+use pyo3::prelude::*;
+
+use super::{
+    decode::{
+        decode_bar_type, decode_gateway_record, decode_instrument_def_msg_with_precision_map,
+        decode_instrument_def_msg_with_precision_map_v1, decode_record_data, decode_ts_init,
+        get_checked, DatabentoDecodeError, DecodeOptions, DecodedRecord, PrecisionMap,
+        TsInitSource,
+    },
+    symbology::DatabentoSymbolMap,
+};
+
+/// Configuration for a [`DatabentoDecoder`].
+#[derive(Clone, Copy, Debug)]
+pub struct DatabentoDecoderConfig {
+    /// The price precision for instruments with no definition decoded.
+    pub price_precision: u8,
+    /// The source of the `ts_init` for decoded data.
+    pub ts_init_source: TsInitSource,
+    /// If trades should be decoded from MBO, MBP-1 and MBP-10 records.
+    pub include_trades: bool,
+    /// If records with an unsupported `rtype` should be skipped (with a warning) rather
+    /// than returning an error, so a mixed stream decodes the records it can.
+    pub skip_unsupported: bool,
+    /// The options for decoding records, with the `size_precision` being the default
+    /// for instruments with no size precision set.
+    pub options: DecodeOptions,
+}
+
+impl Default for DatabentoDecoderConfig {
+    /// Creates a new default [`DatabentoDecoderConfig`] instance.
+    fn default() -> Self {
+        Self {
+            price_precision: Currency::USD().precision,
+            ts_init_source: TsInitSource::default(),
+            include_trades: false,
+            skip_unsupported: false,
+            options: DecodeOptions::default(),
+        }
+    }
+}
+
+/// Provides a stateful decoder of DBN records into Nautilus data.
+///
+/// Instrument IDs are resolved from the symbol mappings, and price precisions from
+/// the instrument definitions, decoded so far. The last bar close per instrument is
+/// kept as the `prior_close` for empty bars decoded with
+/// [`EmptyBarPolicy::CarryForward`](super::decode::EmptyBarPolicy::CarryForward).
+///
+/// Bar spec overrides set per instrument apply to the OHLCV records with the same base
+/// aggregation, so a stream mixing OHLCV schemas is decoded with the spec for each.
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "nautilus_trader.core.nautilus_pyo3.databento")
+)]
+pub struct DatabentoDecoder {
+    config: DatabentoDecoderConfig,
+    symbol_map: DatabentoSymbolMap,
+    precision_map: PrecisionMap,
+    last_closes: HashMap<InstrumentId, Price>,
+    bar_spec_overrides: HashMap<(InstrumentId, BarAggregation), BarSpecification>,
+}
+
+impl DatabentoDecoder {
+    #[must_use]
+    pub fn new(config: DatabentoDecoderConfig, symbol_map: DatabentoSymbolMap) -> Self {
+        Self {
+            config,
+            symbol_map,
+            precision_map: PrecisionMap::new(config.price_precision, config.options.size_precision),
+            last_closes: HashMap::new(),
+            bar_spec_overrides: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &DatabentoDecoderConfig {
+        &self.config
+    }
+
+    #[must_use]
+    pub fn precision_map(&self) -> &PrecisionMap {
+        &self.precision_map
+    }
+
+    /// Returns the close of the last bar decoded for the given `instrument_id`.
+    #[must_use]
+    pub fn last_close(&self, instrument_id: &InstrumentId) -> Option<Price> {
+        self.last_closes.get(instrument_id).copied()
+    }
+
+    /// Sets the size precision for the given `instrument_id`, for venues which allow
+    /// fractional lots.
+    pub fn set_size_precision(&mut self, instrument_id: InstrumentId, size_precision: u8) {
+        self.precision_map
+            .insert_size_precision(instrument_id, size_precision);
+    }
+
+    /// Sets the bar spec override for the OHLCV records of the given `instrument_id` with
+    /// the same base aggregation as the `spec` (e.g. a 5-minute spec for `ohlcv-1m`),
+    /// taking precedence over the `bar_spec_override` of the config options.
+    pub fn set_bar_spec_override(&mut self, instrument_id: InstrumentId, spec: BarSpecification) {
+        self.bar_spec_overrides
+            .insert((instrument_id, spec.aggregation), spec);
+    }
+
+    /// Maps the DBN `instrument_id` to the `raw_symbol`, as an alternative to decoding
+    /// the symbol mapping records of a session.
+    pub fn add_symbol_mapping(&mut self, instrument_id: u32, raw_symbol: &str) -> Result<()> {
+        let msg = dbn::SymbolMappingMsg::new(
+            instrument_id,
+            0,
+            dbn::SType::RawSymbol,
+            raw_symbol,
+            dbn::SType::RawSymbol,
+            raw_symbol,
+            0,
+            dbn::UNDEF_TIMESTAMP,
+        )?;
+        self.symbol_map.on_symbol_mapping(&msg)
+    }
+
+    /// Decodes the given record, with symbol mappings and instrument definitions
+    /// updating the decoder state (and decoding to [`DecodedRecord::Empty`]).
+    pub fn decode(
+        &mut self,
+        rec_ref: &dbn::RecordRef,
+    ) -> Result<DecodedRecord, DatabentoDecodeError> {
+        self.decode_with_ts_init(rec_ref, None)
+    }
+
+    /// Decodes the given record as per [`DatabentoDecoder::decode`], with the `ts_init`
+    /// used when the configured source is [`TsInitSource::Provided`].
+    pub fn decode_with_ts_init(
+        &mut self,
+        rec_ref: &dbn::RecordRef,
+        ts_init: Option<UnixNanos>,
+    ) -> Result<DecodedRecord, DatabentoDecodeError> {
+        match self.decode_record(rec_ref, ts_init) {
+            Err(e) if self.config.skip_unsupported && is_unsupported_rtype(rec_ref, &e) => {
+                log::warn!("Skipping record: {e}");
+                Ok(DecodedRecord::Empty)
+            }
+            result => result,
+        }
+    }
+
+    fn decode_record(
+        &mut self,
+        rec_ref: &dbn::RecordRef,
+        ts_init: Option<UnixNanos>,
+    ) -> Result<DecodedRecord, DatabentoDecodeError> {
+        match rec_ref.rtype()? {
+            dbn::RType::SymbolMapping => {
+                let msg = get_checked::<dbn::SymbolMappingMsg>(rec_ref)?;
+                self.symbol_map.on_symbol_mapping(msg)?;
+                Ok(DecodedRecord::Empty)
+            }
+            dbn::RType::InstrumentDef => {
+                self.decode_instrument_def(rec_ref)?;
+                Ok(DecodedRecord::Empty)
+            }
+            dbn::RType::System | dbn::RType::Error => decode_gateway_record(rec_ref),
+            _ => {
+                let instrument_id = self.symbol_map.get_instrument_id_for_record(rec_ref)?;
+                let ts_init = decode_ts_init(rec_ref, self.config.ts_init_source, ts_init)?;
+                let mut options = self
+                    .precision_map
+                    .options_for(&instrument_id, &self.config.options);
+                if let Some(close) = self.last_close(&instrument_id) {
+                    options.prior_close = Some(close);
+                }
+                if let Some(spec) = self.get_bar_spec_override(rec_ref, instrument_id)? {
+                    options.bar_spec_override = Some(spec);
+                }
+
+                let decoded = decode_record_data(
+                    rec_ref,
+                    instrument_id,
+                    self.precision_map.get_price_precision(&instrument_id),
+                    Some(ts_init),
+                    self.config.include_trades,
+                    &options,
+                )?;
+                if let DecodedRecord::One(Data::Bar(bar)) = &decoded {
+                    self.last_closes.insert(instrument_id, bar.close);
+                }
+
+                Ok(decoded)
+            }
+        }
+    }
+
+    // The per-instrument bar spec override for the base aggregation of an OHLCV record
+    fn get_bar_spec_override(
+        &self,
+        rec_ref: &dbn::RecordRef,
+        instrument_id: InstrumentId,
+    ) -> Result<Option<BarSpecification>, DatabentoDecodeError> {
+        if self.bar_spec_overrides.is_empty() {
+            return Ok(None);
+        }
+        let Some(msg) = rec_ref.get::<dbn::OhlcvMsg>() else {
+            return Ok(None);
+        };
+
+        let base_spec = decode_bar_type(msg, instrument_id)?.spec;
+        Ok(self
+            .bar_spec_overrides
+            .get(&(instrument_id, base_spec.aggregation))
+            .copied())
+    }
+
+    /// Decodes the given instrument definition record, updating the price precision
+    /// and display factor for its instrument.
+    pub fn decode_instrument_def(
+        &mut self,
+        rec_ref: &dbn::RecordRef,
+    ) -> Result<Box<dyn Instrument>, DatabentoDecodeError> {
+        let instrument_id = self.symbol_map.get_instrument_id_for_record(rec_ref)?;
+
+        // The DBN version of the definition is implied by its record length
+        if rec_ref.record_size() >= mem::size_of::<dbn::InstrumentDefMsg>() {
+            let msg = get_checked::<dbn::InstrumentDefMsg>(rec_ref)?;
+            decode_instrument_def_msg_with_precision_map(
+                msg,
+                instrument_id,
+                msg.ts_recv,
+                None,
+                &mut self.precision_map,
+            )
+        } else {
+            let msg = get_checked::<dbn::compat::InstrumentDefMsgV1>(rec_ref)?;
+            decode_instrument_def_msg_with_precision_map_v1(
+                msg,
+                instrument_id,
+                msg.ts_recv,
+                None,
+                &mut self.precision_map,
+            )
+        }
+    }
+}
+
+/// Returns whether the `error` decoding the record is due to its `rtype` being
+/// unknown to DBN or not currently supported.
+fn is_unsupported_rtype(rec_ref: &dbn::RecordRef, error: &DatabentoDecodeError) -> bool {
+    rec_ref.rtype().is_err() || matches!(error.root(), DatabentoDecodeError::UnsupportedRType(_))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{ffi::c_char, path::PathBuf};
+
+    use dbn::decode::{dbn::Decoder, DecodeStream};
+    use indexmap::IndexMap;
+    use nautilus_model::{
+        data::bar::BarType,
+        enums::{MarketStatus, PriceType},
+        types::quantity::Quantity,
+    };
+    use rstest::rstest;
+    use streaming_iterator::StreamingIterator;
+
+    use super::*;
+    use crate::databento::decode::{
+        BarValidationPolicy, CrossedQuotePolicy, EmptyBarPolicy, TradeIdPolicy, DISPLAY_FACTOR_ONE,
+    };
+
+    fn load_first_record<T>(filename: &str) -> T
+    where
+        T: dbn::Record + dbn::HasRType + Clone + 'static,
+    {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tests/test_data/databento")
+            .join(filename);
+        let decoder = Decoder::from_zstd_file(path).unwrap();
+        let mut dbn_stream = decoder.decode_stream::<T>();
+        dbn_stream.next().unwrap().clone()
+    }
+
+    fn symbol_mapping_msg(instrument_id: u32, symbol: &str) -> dbn::SymbolMappingMsg {
+        let mut msg = dbn::SymbolMappingMsg::new(
+            instrument_id,
+            0,
+            dbn::SType::RawSymbol,
+            symbol,
+            dbn::SType::RawSymbol,
+            symbol,
+            0,
+            dbn::UNDEF_TIMESTAMP,
+        )
+        .unwrap();
+        msg.hd.publisher_id = 1; // GLBX.MDP3
+        msg
+    }
+
+    fn decoder(config: DatabentoDecoderConfig) -> DatabentoDecoder {
+        let mut decoder = DatabentoDecoder::new(config, DatabentoSymbolMap::new(IndexMap::new()));
+        let msg = symbol_mapping_msg(5482, "ESH1");
+        decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+        decoder
+    }
+
+    #[rstest]
+    fn test_decode_with_default_config() {
+        let mut decoder = decoder(DatabentoDecoderConfig::default());
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+
+        let result = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+
+        match result {
+            DecodedRecord::One(Data::Trade(trade)) => {
+                assert_eq!(trade.instrument_id, InstrumentId::from("ESH1.GLBX"));
+                assert_eq!(trade.price.precision, 2);
+                assert_eq!(trade.ts_init, msg.ts_recv);
+            }
+            _ => panic!("Expected trade"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_with_non_default_config() {
+        let config = DatabentoDecoderConfig {
+            price_precision: 4,
+            ts_init_source: TsInitSource::Event,
+            include_trades: true,
+            options: DecodeOptions {
+                trade_id_policy: TradeIdPolicy::PublisherSequence,
+                crossed_quote_policy: CrossedQuotePolicy::Normalize,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut decoder = decoder(config);
+        let mut msg = load_first_record::<dbn::Mbp1Msg>("mbp-1.dbn.zst");
+        msg.action = 'T' as c_char;
+        msg.levels[0].bid_px = msg.levels[0].ask_px + 250_000_000; // Crossed by 0.25
+
+        let result = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+
+        match result {
+            DecodedRecord::Two(Data::Quote(quote), Data::Trade(trade)) => {
+                assert_eq!(quote.bid_price.precision, 4);
+                assert_eq!(quote.ask_price, quote.bid_price);
+                assert_eq!(quote.ts_init, msg.hd.ts_event);
+                assert_eq!(trade.trade_id.to_string(), format!("1-{}", msg.sequence));
+            }
+            _ => panic!("Expected quote and trade"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_precision_from_definition() {
+        let mut decoder = decoder(DatabentoDecoderConfig::default());
+        let mut def_msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        def_msg.hd.instrument_id = 5482;
+        def_msg.hd.publisher_id = 1;
+        def_msg.min_price_increment = 100_000; // 0.0001
+        def_msg.display_factor = DISPLAY_FACTOR_ONE;
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+
+        let definition = decoder.decode(&dbn::RecordRef::from(&def_msg)).unwrap();
+        let result = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+
+        assert!(matches!(definition, DecodedRecord::Empty));
+        assert_eq!(
+            decoder
+                .precision_map()
+                .get_price_precision(&InstrumentId::from("ESH1.GLBX")),
+            4
+        );
+        match result {
+            DecodedRecord::One(Data::Trade(trade)) => assert_eq!(trade.price.precision, 4),
+            _ => panic!("Expected trade"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_display_factor_from_definition() {
+        let mut decoder = decoder(DatabentoDecoderConfig::default());
+        let mut def_msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        def_msg.hd.instrument_id = 5482;
+        def_msg.hd.publisher_id = 1;
+        def_msg.min_price_increment = 100_000; // 0.0001
+        def_msg.display_factor = 10_000_000; // Quoted in cents
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+
+        decoder.decode(&dbn::RecordRef::from(&def_msg)).unwrap();
+        let result = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+
+        assert_eq!(
+            decoder
+                .precision_map()
+                .get_display_factor(&InstrumentId::from("ESH1.GLBX")),
+            Some(10_000_000)
+        );
+        match result {
+            DecodedRecord::One(Data::Trade(trade)) => {
+                assert_eq!(trade.price, Price::from("37.2025"));
+            }
+            _ => panic!("Expected trade"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_ohlcv_same_prices_before_and_after_definition() {
+        let mut decoder = decoder(DatabentoDecoderConfig::default());
+        let mut def_msg =
+            load_first_record::<dbn::compat::InstrumentDefMsgV1>("definition-glbx-es-fut.dbn.zst");
+        def_msg.hd.instrument_id = 5482;
+        def_msg.hd.publisher_id = 1;
+        def_msg.min_price_increment = 250_000_000; // 0.25
+        def_msg.display_factor = DISPLAY_FACTOR_ONE;
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+
+        let before = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+        decoder.decode(&dbn::RecordRef::from(&def_msg)).unwrap();
+        let after = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+
+        match (before, after) {
+            (DecodedRecord::One(Data::Bar(before)), DecodedRecord::One(Data::Bar(after))) => {
+                assert_eq!(before.open, Price::from("3720.25"));
+                assert_eq!(after.open, before.open);
+                assert_eq!(after.close, before.close);
+            }
+            _ => panic!("Expected bars"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_empty_bar_carries_forward_last_close() {
+        let config = DatabentoDecoderConfig {
+            options: DecodeOptions {
+                empty_bar_policy: EmptyBarPolicy::CarryForward,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut decoder = decoder(config);
+        let msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        let mut empty_msg = msg.clone();
+        empty_msg.open = dbn::UNDEF_PRICE;
+        empty_msg.high = dbn::UNDEF_PRICE;
+        empty_msg.low = dbn::UNDEF_PRICE;
+        empty_msg.close = dbn::UNDEF_PRICE;
+
+        let before = decoder.decode(&dbn::RecordRef::from(&empty_msg)).unwrap();
+        let first = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+        let empty = decoder.decode(&dbn::RecordRef::from(&empty_msg)).unwrap();
+
+        assert!(matches!(before, DecodedRecord::Empty));
+        let close = match first {
+            DecodedRecord::One(Data::Bar(bar)) => bar.close,
+            _ => panic!("Expected bar"),
+        };
+        assert_eq!(
+            decoder.last_close(&InstrumentId::from("ESH1.GLBX")),
+            Some(close)
+        );
+        match empty {
+            DecodedRecord::One(Data::Bar(bar)) => {
+                assert_eq!(bar.open, close);
+                assert_eq!(bar.close, close);
+            }
+            _ => panic!("Expected carried forward bar"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_bar_spec_override_per_aggregation() {
+        let mut decoder = decoder(DatabentoDecoderConfig::default());
+        let instrument_id = InstrumentId::from("ESH1.GLBX");
+        decoder.set_bar_spec_override(
+            instrument_id,
+            BarSpecification::new(5, BarAggregation::Minute, PriceType::Last),
+        );
+        let msg_1m = load_first_record::<dbn::OhlcvMsg>("ohlcv-1m.dbn.zst");
+        let msg_1s = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+
+        let result_1m = decoder.decode(&dbn::RecordRef::from(&msg_1m)).unwrap();
+        let result_1s = decoder.decode(&dbn::RecordRef::from(&msg_1s)).unwrap();
+
+        match (result_1m, result_1s) {
+            (DecodedRecord::One(Data::Bar(bar_1m)), DecodedRecord::One(Data::Bar(bar_1s))) => {
+                assert_eq!(
+                    bar_1m.bar_type,
+                    BarType::from("ESH1.GLBX-5-MINUTE-LAST-EXTERNAL")
+                );
+                assert_eq!(
+                    bar_1s.bar_type,
+                    BarType::from("ESH1.GLBX-1-SECOND-LAST-EXTERNAL")
+                );
+            }
+            _ => panic!("Expected bars"),
+        }
+    }
+
+    #[rstest]
+    #[case(BarValidationPolicy::Reject)]
+    #[case(BarValidationPolicy::Skip)]
+    fn test_decode_bar_validation_policy(#[case] policy: BarValidationPolicy) {
+        let config = DatabentoDecoderConfig {
+            options: DecodeOptions {
+                bar_validation_policy: policy,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut decoder = decoder(config);
+        let mut msg = load_first_record::<dbn::OhlcvMsg>("ohlcv-1s.dbn.zst");
+        std::mem::swap(&mut msg.high, &mut msg.low);
+        msg.high -= 1_000_000_000;
+
+        let result = decoder.decode(&dbn::RecordRef::from(&msg));
+
+        match policy {
+            BarValidationPolicy::Reject => assert!(matches!(
+                result.unwrap_err().root(),
+                DatabentoDecodeError::InvalidBar { .. }
+            )),
+            _ => assert!(matches!(result.unwrap(), DecodedRecord::Empty)),
+        }
+        assert_eq!(decoder.last_close(&InstrumentId::from("ESH1.GLBX")), None);
+    }
+
+    #[rstest]
+    fn test_decode_mixed_stream_with_statistics() {
+        let config = DatabentoDecoderConfig {
+            skip_unsupported: true,
+            ..Default::default()
+        };
+        let mut decoder = decoder(config);
+        let trade_msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        let mut stat_msg = load_first_record::<dbn::StatMsg>("statistics.dbn.zst");
+        stat_msg.hd.instrument_id = 5482;
+
+        let trade = decoder.decode(&dbn::RecordRef::from(&trade_msg)).unwrap();
+        let stats = decoder.decode(&dbn::RecordRef::from(&stat_msg)).unwrap();
+
+        assert!(matches!(trade, DecodedRecord::One(Data::Trade(_))));
+        match stats {
+            DecodedRecord::Statistics(stats) => {
+                assert_eq!(stats.instrument_id, InstrumentId::from("ESH1.GLBX"));
+                assert_eq!(stats.quantity, Some(Quantity::from(10_181)));
+            }
+            _ => panic!("Expected statistics"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_status_halt_and_resume() {
+        let mut decoder = decoder(DatabentoDecoderConfig::default());
+        let status_msg = |trading_status: u8| dbn::StatusMsg {
+            hd: dbn::RecordHeader::new::<dbn::StatusMsg>(dbn::rtype::STATUS, 1, 5482, 0),
+            ts_recv: 0,
+            group: [0; 21],
+            trading_status,
+            halt_reason: 0,
+            trading_event: 0,
+        };
+
+        let halt = decoder
+            .decode(&dbn::RecordRef::from(&status_msg(8)))
+            .unwrap();
+        let resume = decoder
+            .decode(&dbn::RecordRef::from(&status_msg(7)))
+            .unwrap();
+
+        match (halt, resume) {
+            (DecodedRecord::Status(halt), DecodedRecord::Status(resume)) => {
+                assert_eq!(halt.instrument_id, InstrumentId::from("ESH1.GLBX"));
+                assert_eq!(halt.status, MarketStatus::Halt);
+                assert_eq!(resume.status, MarketStatus::Open);
+            }
+            _ => panic!("Expected status updates"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_heartbeat_without_mapping() {
+        let mut decoder = DatabentoDecoder::new(
+            DatabentoDecoderConfig::default(),
+            DatabentoSymbolMap::new(IndexMap::new()),
+        );
+        let msg = dbn::SystemMsg::heartbeat(0);
+
+        let result = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+
+        assert!(matches!(result, DecodedRecord::Heartbeat));
+    }
+
+    #[rstest]
+    fn test_decode_fractional_size() {
+        let mut decoder = decoder(DatabentoDecoderConfig::default());
+        decoder.set_size_precision(InstrumentId::from("ESH1.GLBX"), 2);
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.size = 150;
+
+        let result = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+
+        match result {
+            DecodedRecord::One(Data::Trade(trade)) => {
+                assert_eq!(trade.size, Quantity::from("1.50"));
+                assert_eq!(trade.size.precision, 2);
+            }
+            _ => panic!("Expected trade"),
+        }
+    }
+
+    #[rstest]
+    fn test_decode_unmapped_instrument() {
+        let mut decoder = DatabentoDecoder::new(
+            DatabentoDecoderConfig::default(),
+            DatabentoSymbolMap::new(IndexMap::new()),
+        );
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+
+        let err = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("No raw symbol mapped for instrument ID 5482"));
+    }
+
+    #[rstest]
+    fn test_decode_unsupported_rtype_strict() {
+        let mut decoder = decoder(DatabentoDecoderConfig::default());
+        let mut msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        msg.hd.rtype = 0xFF;
+
+        let result = decoder.decode(&dbn::RecordRef::from(&msg));
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_decode_unsupported_rtype_skipped() {
+        let config = DatabentoDecoderConfig {
+            skip_unsupported: true,
+            ..Default::default()
+        };
+        let mut decoder = decoder(config);
+        let mut unsupported = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+        unsupported.hd.rtype = 0xFF;
+        let msg = load_first_record::<dbn::TradeMsg>("trades.dbn.zst");
+
+        let skipped = decoder.decode(&dbn::RecordRef::from(&unsupported)).unwrap();
+        let result = decoder.decode(&dbn::RecordRef::from(&msg)).unwrap();
+
+        assert!(matches!(skipped, DecodedRecord::Empty));
+        assert!(matches!(result, DecodedRecord::One(Data::Trade(_))));
+    }
+}
@@ -18,7 +18,7 @@ use std::{collections::HashMap, env, fs, path::PathBuf};
 use anyhow::{bail, Result};
 use dbn::{
     compat::InstrumentDefMsgV1,
-    decode::{dbn::Decoder, DbnMetadata, DecodeStream},
+    decode::{dbn::Decoder, DbnMetadata, DecodeRecordRef, DecodeStream, DynReader},
     Publisher, Record,
 };
 use indexmap::IndexMap;
@@ -33,8 +33,14 @@ use streaming_iterator::StreamingIterator;
 use ustr::Ustr;
 
 use super::{
-    decode::{decode_instrument_def_msg_v1, decode_record, raw_ptr_to_ustr},
-    types::{DatabentoPublisher, Dataset, PublisherId},
+    decode::{
+        check_dbn_version, decode_imbalance_msg, decode_instrument_def_msg_v1,
+        decode_instrument_def_msg_with_precision_map,
+        decode_instrument_def_msg_with_precision_map_v1, decode_raw_symbol, decode_record,
+        decode_record_with_precision_map, decode_statistics_msg, decode_ts_init,
+        fixed_bytes_to_str, get_checked, EmptySymbolPolicy, PrecisionMap, TsInitSource,
+    },
+    types::{DatabentoImbalance, DatabentoPublisher, DatabentoStatistics, Dataset, PublisherId},
 };
 
 /// Provides a Nautilus data loader for Databento Binary Encoding (DBN) format data.
@@ -58,7 +64,6 @@ use super::{
 ///  - ``FUTURE_SPREAD``
 ///  - ``OPTION_SPEAD``
 ///  - ``MIXED_SPREAD``
-///  - ``FX_SPOT``
 ///
 /// # References
 /// https://docs.databento.com/knowledge-base/new-users/dbn-encoding
@@ -176,19 +181,19 @@ impl DatabentoDataLoader {
     ) -> Result<InstrumentId> {
         let (instrument_id, nanoseconds) = match record.rtype()? {
             dbn::RType::Mbo => {
-                let msg = record.get::<dbn::MboMsg>().unwrap(); // SAFETY: RType known
+                let msg = get_checked::<dbn::MboMsg>(record)?;
                 (msg.hd.instrument_id, msg.ts_recv)
             }
             dbn::RType::Mbp0 => {
-                let msg = record.get::<dbn::TradeMsg>().unwrap(); // SAFETY: RType known
+                let msg = get_checked::<dbn::TradeMsg>(record)?;
                 (msg.hd.instrument_id, msg.ts_recv)
             }
             dbn::RType::Mbp1 => {
-                let msg = record.get::<dbn::Mbp1Msg>().unwrap(); // SAFETY: RType known
+                let msg = get_checked::<dbn::Mbp1Msg>(record)?;
                 (msg.hd.instrument_id, msg.ts_recv)
             }
             dbn::RType::Mbp10 => {
-                let msg = record.get::<dbn::Mbp10Msg>().unwrap(); // SAFETY: RType known
+                let msg = get_checked::<dbn::Mbp10Msg>(record)?;
                 (msg.hd.instrument_id, msg.ts_recv)
             }
             dbn::RType::Ohlcv1S
@@ -196,9 +201,17 @@ impl DatabentoDataLoader {
             | dbn::RType::Ohlcv1H
             | dbn::RType::Ohlcv1D
             | dbn::RType::OhlcvEod => {
-                let msg = record.get::<dbn::OhlcvMsg>().unwrap(); // SAFETY: RType known
+                let msg = get_checked::<dbn::OhlcvMsg>(record)?;
                 (msg.hd.instrument_id, msg.hd.ts_event)
             }
+            dbn::RType::Statistics => {
+                let msg = get_checked::<dbn::StatMsg>(record)?;
+                (msg.hd.instrument_id, msg.ts_recv)
+            }
+            dbn::RType::Imbalance => {
+                let msg = get_checked::<dbn::ImbalanceMsg>(record)?;
+                (msg.hd.instrument_id, msg.ts_recv)
+            }
             _ => bail!("RType is currently unsupported by NautilusTrader"),
         };
 
@@ -217,6 +230,69 @@ impl DatabentoDataLoader {
         Ok(InstrumentId::new(symbol, venue))
     }
 
+    fn resolve_instrument_id(
+        &self,
+        rec_ref: &dbn::RecordRef,
+        metadata: &dbn::Metadata,
+        instrument_id: Option<InstrumentId>,
+    ) -> InstrumentId {
+        if let Some(instrument_id) = instrument_id {
+            return instrument_id;
+        }
+
+        let publisher = rec_ref.publisher().expect("Invalid `publisher` for record");
+        let publisher_id = publisher as PublisherId;
+        let venue = self
+            .publisher_venue_map
+            .get(&publisher_id)
+            .unwrap_or_else(|| panic!("`Venue` not found for `publisher_id` {publisher_id}"));
+        let mut instrument_id = self
+            .get_nautilus_instrument_id_for_record(rec_ref, metadata, *venue)
+            .unwrap_or_else(|_| panic!("Error resolving symbology mapping for {:?}", rec_ref));
+
+        if publisher == Publisher::GlbxMdp3Glbx {
+            // Source actual exchange from GLBX instrument
+            // definitions if they were loaded.
+            if let Some(venue) = self.glbx_exchange_map.get(&instrument_id.symbol) {
+                instrument_id.venue = *venue;
+            }
+        };
+
+        instrument_id
+    }
+
+    fn resolve_mapped_instrument_id(
+        &self,
+        rec_ref: &dbn::RecordRef,
+        symbol_map: &dbn::TsSymbolMap,
+    ) -> Result<InstrumentId> {
+        let header = rec_ref.header();
+        let Some(venue) = self.publisher_venue_map.get(&header.publisher_id) else {
+            bail!(
+                "`Venue` not found for `publisher_id` {}",
+                header.publisher_id
+            )
+        };
+
+        let ts_recv = decode_ts_init(rec_ref, TsInitSource::Recv, None)?;
+        let datetime = time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(ts_recv))?;
+        let Some(raw_symbol) = symbol_map.get(datetime.date(), header.instrument_id) else {
+            bail!(
+                "No raw symbol found for instrument ID {}",
+                header.instrument_id
+            )
+        };
+
+        let symbol = Symbol::from_str_unchecked(raw_symbol);
+        let venue = match rec_ref.publisher()? {
+            // Source actual exchange from GLBX instrument definitions if they were loaded
+            Publisher::GlbxMdp3Glbx => *self.glbx_exchange_map.get(&symbol).unwrap_or(venue),
+            _ => *venue,
+        };
+
+        Ok(InstrumentId::new(symbol, venue))
+    }
+
     pub fn schema_from_file(&self, path: PathBuf) -> Result<Option<String>> {
         let decoder = Decoder::from_zstd_file(path)?;
         let metadata = decoder.metadata();
@@ -228,6 +304,7 @@ impl DatabentoDataLoader {
         path: PathBuf,
     ) -> Result<impl Iterator<Item = Result<Box<dyn Instrument>>> + '_> {
         let mut decoder = Decoder::from_zstd_file(path)?;
+        check_dbn_version(decoder.metadata())?;
         decoder.set_upgrade_policy(dbn::VersionUpgradePolicy::Upgrade);
         let mut dbn_stream = decoder.decode_stream::<InstrumentDefMsgV1>();
 
@@ -239,9 +316,15 @@ impl DatabentoDataLoader {
                     let rec_ref = dbn::RecordRef::from(rec);
                     let msg = rec_ref.get::<InstrumentDefMsgV1>().unwrap();
 
-                    let raw_symbol = unsafe {
-                        raw_ptr_to_ustr(rec.raw_symbol.as_ptr())
-                            .expect("Error obtaining `raw_symbol` pointer")
+                    let raw_symbol =
+                        fixed_bytes_to_str(&rec.raw_symbol).expect("Error decoding `raw_symbol`");
+                    let raw_symbol = match decode_raw_symbol(
+                        raw_symbol,
+                        msg.hd.instrument_id,
+                        EmptySymbolPolicy::default(),
+                    ) {
+                        Ok(raw_symbol) => raw_symbol,
+                        Err(e) => return Some(Err(e.into())),
                     };
                     let symbol = Symbol { value: raw_symbol };
 
@@ -263,9 +346,9 @@ impl DatabentoDataLoader {
                     };
                     let instrument_id = InstrumentId::new(symbol, venue);
 
-                    match decode_instrument_def_msg_v1(rec, instrument_id, msg.ts_recv) {
+                    match decode_instrument_def_msg_v1(rec, instrument_id, msg.ts_recv, None) {
                         Ok(data) => Some(Ok(data)),
-                        Err(e) => Some(Err(e)),
+                        Err(e) => Some(Err(e.into())),
                     }
                 }
                 None => None,
@@ -284,6 +367,7 @@ impl DatabentoDataLoader {
     {
         let decoder = Decoder::from_zstd_file(path)?;
         let metadata = decoder.metadata().clone();
+        check_dbn_version(&metadata)?;
         let mut dbn_stream = decoder.decode_stream::<T>();
 
         let price_precision = Currency::USD().precision; // Hard coded for now
@@ -293,39 +377,8 @@ impl DatabentoDataLoader {
             match dbn_stream.get() {
                 Some(rec) => {
                     let rec_ref = dbn::RecordRef::from(rec);
-                    let instrument_id = match &instrument_id {
-                        Some(id) => *id, // Copy
-                        None => {
-                            let publisher =
-                                rec_ref.publisher().expect("Invalid `publisher` for record");
-                            let publisher_id = publisher as PublisherId;
-                            let venue =
-                                self.publisher_venue_map
-                                    .get(&publisher_id)
-                                    .unwrap_or_else(|| {
-                                        panic!(
-                                            "`Venue` not found for `publisher_id` {publisher_id}"
-                                        )
-                                    });
-                            let mut instrument_id = self
-                                .get_nautilus_instrument_id_for_record(&rec_ref, &metadata, *venue)
-                                .unwrap_or_else(|_| {
-                                    panic!("Error resolving symbology mapping for {:?}", rec_ref)
-                                });
-
-                            if publisher == Publisher::GlbxMdp3Glbx {
-                                // Source actual exchange from GLBX instrument
-                                // definitions if they were loaded.
-                                if let Some(venue) =
-                                    self.glbx_exchange_map.get(&instrument_id.symbol)
-                                {
-                                    instrument_id.venue = *venue;
-                                }
-                            };
-
-                            instrument_id
-                        }
-                    };
+                    let instrument_id =
+                        self.resolve_instrument_id(&rec_ref, &metadata, instrument_id);
 
                     match decode_record(
                         &rec_ref,
@@ -335,11 +388,224 @@ impl DatabentoDataLoader {
                         include_trades,
                     ) {
                         Ok(data) => Some(Ok(data)),
-                        Err(e) => Some(Err(e)),
+                        Err(e) => Some(Err(e.into())),
                     }
                 }
                 None => None,
             }
         }))
     }
+
+    /// Reads the DBN file at the given `path` (which may be zstd compressed), yielding
+    /// the decoded Nautilus data for all schemas within the file.
+    ///
+    /// Instrument IDs are resolved from the symbol mappings embedded in the file metadata
+    /// (unless an `instrument_id` is given). The price precision of each instrument is taken
+    /// from any instrument definitions preceding its records in the file, otherwise defaults
+    /// to the USD precision. Records which do not decode to `Data` are skipped.
+    pub fn read_data(
+        &self,
+        path: PathBuf,
+        instrument_id: Option<InstrumentId>,
+        include_trades: bool,
+    ) -> Result<impl Iterator<Item = Result<Data>> + '_> {
+        let mut decoder = Decoder::new(DynReader::from_file(path)?)?;
+        let metadata = decoder.metadata().clone();
+        check_dbn_version(&metadata)?;
+        let symbol_map = metadata.symbol_map()?;
+
+        let mut precision_map = PrecisionMap::default();
+        let mut pending: Option<Data> = None;
+
+        Ok(std::iter::from_fn(move || loop {
+            if let Some(data) = pending.take() {
+                return Some(Ok(data));
+            }
+
+            let rec_ref = match decoder.decode_record_ref() {
+                Ok(Some(rec_ref)) => rec_ref,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            let is_definition = match rec_ref.rtype() {
+                Ok(dbn::RType::InstrumentDef) => true,
+                Ok(
+                    dbn::RType::Mbo
+                    | dbn::RType::Mbp0
+                    | dbn::RType::Mbp1
+                    | dbn::RType::Mbp10
+                    | dbn::RType::Ohlcv1S
+                    | dbn::RType::Ohlcv1M
+                    | dbn::RType::Ohlcv1H
+                    | dbn::RType::Ohlcv1D
+                    | dbn::RType::OhlcvEod,
+                ) => false,
+                Ok(_) => continue, // Not `Data`
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            let instrument_id = match instrument_id {
+                Some(instrument_id) => instrument_id,
+                None => match self.resolve_mapped_instrument_id(&rec_ref, &symbol_map) {
+                    Ok(instrument_id) => instrument_id,
+                    Err(e) => return Some(Err(e)),
+                },
+            };
+
+            if is_definition {
+                let result = if metadata.version == 1 {
+                    get_checked::<InstrumentDefMsgV1>(&rec_ref).and_then(|msg| {
+                        decode_instrument_def_msg_with_precision_map_v1(
+                            msg,
+                            instrument_id,
+                            msg.ts_recv,
+                            None,
+                            &mut precision_map,
+                        )
+                    })
+                } else {
+                    get_checked::<dbn::InstrumentDefMsg>(&rec_ref).and_then(|msg| {
+                        decode_instrument_def_msg_with_precision_map(
+                            msg,
+                            instrument_id,
+                            msg.ts_recv,
+                            None,
+                            &mut precision_map,
+                        )
+                    })
+                };
+                match result {
+                    Ok(_) => continue,
+                    Err(e) => return Some(Err(e.into())),
+                }
+            }
+
+            match decode_record_with_precision_map(
+                &rec_ref,
+                instrument_id,
+                &precision_map,
+                None,
+                include_trades,
+            ) {
+                Ok((Some(data), extra)) => {
+                    pending = extra;
+                    return Some(Ok(data));
+                }
+                Ok((None, Some(data))) => return Some(Ok(data)),
+                Ok((None, None)) => continue,
+                Err(e) => return Some(Err(e.into())),
+            }
+        }))
+    }
+
+    pub fn read_statistics_records(
+        &self,
+        path: PathBuf,
+        instrument_id: Option<InstrumentId>,
+    ) -> Result<impl Iterator<Item = Result<DatabentoStatistics>> + '_> {
+        let decoder = Decoder::from_zstd_file(path)?;
+        let metadata = decoder.metadata().clone();
+        check_dbn_version(&metadata)?;
+        let mut dbn_stream = decoder.decode_stream::<dbn::StatMsg>();
+
+        let price_precision = Currency::USD().precision; // Hard coded for now
+
+        Ok(std::iter::from_fn(move || loop {
+            dbn_stream.advance();
+            let rec = dbn_stream.get()?;
+            let rec_ref = dbn::RecordRef::from(rec);
+            let instrument_id = self.resolve_instrument_id(&rec_ref, &metadata, instrument_id);
+
+            match decode_statistics_msg(rec, instrument_id, price_precision, rec.ts_recv) {
+                Ok(Some(stats)) => return Some(Ok(stats)),
+                Ok(None) => continue, // Statistic type not mapped
+                Err(e) => return Some(Err(e.into())),
+            }
+        }))
+    }
+
+    pub fn read_imbalance_records(
+        &self,
+        path: PathBuf,
+        instrument_id: Option<InstrumentId>,
+    ) -> Result<impl Iterator<Item = Result<DatabentoImbalance>> + '_> {
+        let decoder = Decoder::from_zstd_file(path)?;
+        let metadata = decoder.metadata().clone();
+        check_dbn_version(&metadata)?;
+        let mut dbn_stream = decoder.decode_stream::<dbn::ImbalanceMsg>();
+
+        let price_precision = Currency::USD().precision; // Hard coded for now
+
+        Ok(std::iter::from_fn(move || {
+            dbn_stream.advance();
+            let rec = dbn_stream.get()?;
+            let rec_ref = dbn::RecordRef::from(rec);
+            let instrument_id = self.resolve_instrument_id(&rec_ref, &metadata, instrument_id);
+
+            Some(
+                decode_imbalance_msg(rec, instrument_id, price_precision, rec.ts_recv)
+                    .map_err(Into::into),
+            )
+        }))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::time::UnixNanos;
+    use nautilus_model::data::HasTsInit;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn test_data_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/test_data/databento")
+    }
+
+    fn data_loader() -> DatabentoDataLoader {
+        let publishers_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../nautilus_trader/adapters/databento/publishers.json");
+        DatabentoDataLoader::new(Some(publishers_path)).unwrap()
+    }
+
+    #[rstest]
+    #[case(
+        "trades.dbn.zst",
+        2,
+        1_609_160_400_099_150_057,
+        1_609_160_400_108_142_648
+    )]
+    #[case(
+        "mbp-1.dbn.zst",
+        2,
+        1_609_160_400_006_136_329,
+        1_609_160_400_006_246_513
+    )]
+    fn test_read_data(
+        #[case] filename: &str,
+        #[case] expected_count: usize,
+        #[case] expected_first_ts: UnixNanos,
+        #[case] expected_last_ts: UnixNanos,
+    ) {
+        let loader = data_loader();
+
+        let data: Vec<Data> = loader
+            .read_data(test_data_path().join(filename), None, false)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(data.len(), expected_count);
+        match &data[0] {
+            Data::Quote(quote) => assert_eq!(quote.instrument_id, InstrumentId::from("ESH1.GLBX")),
+            Data::Trade(trade) => assert_eq!(trade.instrument_id, InstrumentId::from("ESH1.GLBX")),
+            _ => panic!("Expected quote or trade"),
+        }
+        assert_eq!(data[0].get_ts_init(), expected_first_ts);
+        assert_eq!(data[data.len() - 1].get_ts_init(), expected_last_ts);
+    }
 }
@@ -13,8 +13,10 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
+pub mod checkpoint;
 pub mod common;
 pub mod decode;
+pub mod decoder;
 pub mod loader;
 pub mod symbology;
 pub mod types;
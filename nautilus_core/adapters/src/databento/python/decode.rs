@@ -25,7 +25,7 @@ use pyo3::{exceptions::PyRuntimeError, prelude::*, types::PyTuple};
 
 use crate::databento::decode::{
     decode_equity_v1, decode_futures_contract_v1, decode_mbo_msg, decode_mbp10_msg,
-    decode_mbp1_msg, decode_options_contract_v1, decode_trade_msg,
+    decode_mbp1_msg, decode_options_contract_v1, decode_trade_msg, DecodeOptions,
 };
 
 #[pyfunction]
@@ -45,7 +45,7 @@ pub fn py_decode_futures_contract(
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
 ) -> PyResult<FuturesContract> {
-    decode_futures_contract_v1(record, instrument_id, ts_init).map_err(to_pyvalue_err)
+    decode_futures_contract_v1(record, instrument_id, ts_init, None).map_err(to_pyvalue_err)
 }
 
 #[pyfunction]
@@ -55,7 +55,7 @@ pub fn py_decode_options_contract(
     instrument_id: InstrumentId,
     ts_init: UnixNanos,
 ) -> PyResult<OptionsContract> {
-    decode_options_contract_v1(record, instrument_id, ts_init).map_err(to_pyvalue_err)
+    decode_options_contract_v1(record, instrument_id, ts_init, None).map_err(to_pyvalue_err)
 }
 
 #[pyfunction]
@@ -67,7 +67,14 @@ pub fn py_decode_mbo_msg(
     price_precision: u8,
     ts_init: UnixNanos,
 ) -> PyResult<PyObject> {
-    let result = decode_mbo_msg(record, instrument_id, price_precision, ts_init, false);
+    let result = decode_mbo_msg(
+        record,
+        instrument_id,
+        price_precision,
+        ts_init,
+        false,
+        &DecodeOptions::default(),
+    );
 
     match result {
         Ok((Some(data), None)) => Ok(data.into_py(py)),
@@ -84,7 +91,14 @@ pub fn py_decode_trade_msg(
     price_precision: u8,
     ts_init: UnixNanos,
 ) -> PyResult<TradeTick> {
-    decode_trade_msg(record, instrument_id, price_precision, ts_init).map_err(to_pyvalue_err)
+    decode_trade_msg(
+        record,
+        instrument_id,
+        price_precision,
+        ts_init,
+        &DecodeOptions::default(),
+    )
+    .map_err(to_pyvalue_err)
 }
 
 #[pyfunction]
@@ -103,6 +117,7 @@ pub fn py_decode_mbp1_msg(
         price_precision,
         ts_init,
         include_trades,
+        &DecodeOptions::default(),
     );
 
     match result {
@@ -132,5 +147,14 @@ pub fn py_decode_mbp10_msg(
     price_precision: u8,
     ts_init: UnixNanos,
 ) -> PyResult<OrderBookDepth10> {
-    decode_mbp10_msg(record, instrument_id, price_precision, ts_init).map_err(to_pyvalue_err)
+    decode_mbp10_msg(
+        record,
+        instrument_id,
+        price_precision,
+        ts_init,
+        false,
+        &DecodeOptions::default(),
+    )
+    .map(|(depth, _)| depth)
+    .map_err(to_pyvalue_err)
 }
@@ -0,0 +1,107 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use dbn::decode::{dbn::RecordDecoder, DecodeRecordRef};
+use indexmap::IndexMap;
+use nautilus_core::python::to_pyvalue_err;
+use nautilus_model::data::Data;
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+};
+
+use super::loader::convert_instrument_to_pyobject;
+use crate::databento::{
+    decode::DecodedRecord,
+    decoder::{DatabentoDecoder, DatabentoDecoderConfig},
+    symbology::DatabentoSymbolMap,
+};
+
+#[pymethods]
+impl DatabentoDecoder {
+    #[new]
+    fn py_new(price_precision: Option<u8>, include_trades: Option<bool>) -> Self {
+        let default = DatabentoDecoderConfig::default();
+        let config = DatabentoDecoderConfig {
+            price_precision: price_precision.unwrap_or(default.price_precision),
+            include_trades: include_trades.unwrap_or(default.include_trades),
+            ..default
+        };
+        Self::new(config, DatabentoSymbolMap::new(IndexMap::new()))
+    }
+
+    #[pyo3(name = "add_symbol_mapping")]
+    fn py_add_symbol_mapping(&mut self, instrument_id: u32, raw_symbol: &str) -> PyResult<()> {
+        self.add_symbol_mapping(instrument_id, raw_symbol)
+            .map_err(to_pyvalue_err)
+    }
+
+    #[pyo3(name = "decode_record_bytes")]
+    fn py_decode_record_bytes(&mut self, py: Python, data: &[u8]) -> PyResult<Vec<PyObject>> {
+        let mut decoder = new_record_decoder(data)?;
+        let rec_ref = decode_record_ref(&mut decoder)?;
+
+        match self.decode(&rec_ref).map_err(to_pyvalue_err)? {
+            DecodedRecord::One(data) => Ok(vec![data_to_pyobject(py, data)?]),
+            DecodedRecord::Two(first, second) => Ok(vec![
+                data_to_pyobject(py, first)?,
+                data_to_pyobject(py, second)?,
+            ]),
+            DecodedRecord::Statistics(stats) => Ok(vec![stats.into_py(py)]),
+            DecodedRecord::Imbalance(imbalance) => Ok(vec![imbalance.into_py(py)]),
+            DecodedRecord::Status(status) => Ok(vec![status.into_py(py)]),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    #[pyo3(name = "decode_instrument_def_bytes")]
+    fn py_decode_instrument_def_bytes(&mut self, py: Python, data: &[u8]) -> PyResult<PyObject> {
+        let mut decoder = new_record_decoder(data)?;
+        let rec_ref = decode_record_ref(&mut decoder)?;
+        let instrument = self
+            .decode_instrument_def(&rec_ref)
+            .map_err(to_pyvalue_err)?;
+
+        convert_instrument_to_pyobject(py, instrument)
+    }
+}
+
+fn new_record_decoder(data: &[u8]) -> PyResult<RecordDecoder<&[u8]>> {
+    RecordDecoder::with_version(
+        data,
+        dbn::DBN_VERSION,
+        dbn::VersionUpgradePolicy::AsIs,
+        false,
+    )
+    .map_err(to_pyvalue_err)
+}
+
+fn decode_record_ref<'a>(decoder: &'a mut RecordDecoder<&[u8]>) -> PyResult<dbn::RecordRef<'a>> {
+    decoder
+        .decode_record_ref()
+        .map_err(to_pyvalue_err)?
+        .ok_or_else(|| PyValueError::new_err("No DBN record in bytes"))
+}
+
+fn data_to_pyobject(py: Python, data: Data) -> PyResult<PyObject> {
+    match data {
+        Data::Delta(delta) => Ok(delta.into_py(py)),
+        Data::Depth10(depth) => Ok(depth.into_py(py)),
+        Data::Quote(quote) => Ok(quote.into_py(py)),
+        Data::Trade(trade) => Ok(trade.into_py(py)),
+        Data::Bar(bar) => Ok(bar.into_py(py)),
+        Data::Deltas(_) => Err(PyTypeError::new_err("Unsupported `OrderBookDeltas` data")),
+    }
+}
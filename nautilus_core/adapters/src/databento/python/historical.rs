@@ -37,7 +37,7 @@ use tokio::sync::Mutex;
 use super::loader::convert_instrument_to_pyobject;
 use crate::databento::{
     common::get_date_time_range,
-    decode::{decode_instrument_def_msg, decode_record, raw_ptr_to_ustr},
+    decode::{decode_instrument_def_msg, decode_record, fixed_bytes_to_str},
     symbology::decode_nautilus_instrument_id,
     types::{DatabentoPublisher, PublisherId},
 };
@@ -151,7 +151,7 @@ impl DatabentoHistoricalClient {
             let mut instruments = Vec::new();
 
             while let Ok(Some(msg)) = decoder.decode_record::<dbn::InstrumentDefMsg>().await {
-                let raw_symbol = unsafe { raw_ptr_to_ustr(msg.raw_symbol.as_ptr()).unwrap() };
+                let raw_symbol = Ustr::from(fixed_bytes_to_str(&msg.raw_symbol).unwrap());
                 let symbol = Symbol { value: raw_symbol };
 
                 let publisher = msg.hd.publisher().expect("Invalid `publisher` for record");
@@ -168,7 +168,7 @@ impl DatabentoHistoricalClient {
                 };
                 let instrument_id = InstrumentId::new(symbol, venue);
 
-                let result = decode_instrument_def_msg(msg, instrument_id, ts_init);
+                let result = decode_instrument_def_msg(msg, instrument_id, ts_init, None);
                 match result {
                     Ok(instrument) => instruments.push(instrument),
                     Err(e) => eprintln!("{e:?}"),
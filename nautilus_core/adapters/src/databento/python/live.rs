@@ -13,9 +13,9 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
-use std::{collections::HashMap, ffi::CStr, fs, str::FromStr, sync::Arc};
+use std::{collections::HashMap, fs, str::FromStr, sync::Arc};
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{bail, Result};
 use databento::{
     dbn::{PitSymbolMap, Record, SymbolIndex, VersionUpgradePolicy},
     live::Subscription,
@@ -42,7 +42,11 @@ use ustr::Ustr;
 
 use super::loader::convert_instrument_to_pyobject;
 use crate::databento::{
-    decode::{decode_instrument_def_msg, decode_record},
+    decode::{
+        decode_instrument_def_msg_with_precision_map, decode_raw_symbol, decode_record_data,
+        fixed_bytes_to_str, DecodeOptions, DecodedRecord, EmptySymbolPolicy, PrecisionMap,
+        TickSizeMonitor,
+    },
     types::{DatabentoPublisher, PublisherId},
 };
 
@@ -178,6 +182,8 @@ impl DatabentoLiveClient {
             let mut client = arc_client.lock().await;
             let mut symbol_map = PitSymbolMap::new();
             let mut instrument_id_map: HashMap<u32, InstrumentId> = HashMap::new();
+            let mut tick_size_monitor = TickSizeMonitor::new();
+            let mut precision_map = PrecisionMap::default();
 
             let timeout_duration = Duration::from_millis(10);
             let relock_interval = timeout_duration.as_nanos() as u64;
@@ -227,26 +233,52 @@ impl DatabentoLiveClient {
                     instrument_id_map.remove(&msg.hd.instrument_id);
                     handle_symbol_mapping_msg(msg, &mut symbol_map);
                 } else if let Some(msg) = record.get::<dbn::InstrumentDefMsg>() {
-                    handle_instrument_def_msg(
+                    let instrument_id = resolve_instrument_def_id(
                         msg,
                         &publisher_venue_map,
                         &glbx_exchange_map,
                         &mut instrument_id_map,
+                    )
+                    .map_err(to_pyvalue_err)?;
+                    handle_instrument_def_msg(
+                        msg,
+                        instrument_id,
+                        &mut tick_size_monitor,
+                        &mut precision_map,
                         clock,
                         &callback,
                     )
                     .map_err(to_pyvalue_err)?;
                 } else {
-                    let (mut data1, data2) = handle_record(
+                    let decoded = handle_record(
                         record,
                         &symbol_map,
                         &publisher_venue_map,
                         &glbx_exchange_map,
                         &mut instrument_id_map,
+                        &precision_map,
                         clock,
                     )
                     .map_err(to_pyvalue_err)?;
 
+                    let (mut data1, data2) = match decoded {
+                        DecodedRecord::Statistics(stats) => {
+                            Python::with_gil(|py| call_python(py, &callback, stats.into_py(py)));
+                            continue;
+                        }
+                        DecodedRecord::Imbalance(imbalance) => {
+                            Python::with_gil(|py| {
+                                call_python(py, &callback, imbalance.into_py(py));
+                            });
+                            continue;
+                        }
+                        DecodedRecord::Status(status) => {
+                            Python::with_gil(|py| call_python(py, &callback, status.into_py(py)));
+                            continue;
+                        }
+                        decoded => decoded.into(),
+                    };
+
                     if let Some(msg) = record.get::<dbn::MboMsg>() {
                         // SAFETY: An MBO message will always produce a delta
                         if let Data::Delta(delta) = data1.clone().unwrap() {
@@ -364,27 +396,50 @@ fn update_instrument_id_map(
     instrument_id
 }
 
-fn handle_instrument_def_msg(
+fn resolve_instrument_def_id(
     msg: &dbn::InstrumentDefMsg,
     publisher_venue_map: &IndexMap<PublisherId, Venue>,
     glbx_exchange_map: &HashMap<Symbol, Venue>,
     instrument_id_map: &mut HashMap<u32, InstrumentId>,
-    clock: &AtomicTime,
-    callback: &PyObject,
-) -> Result<()> {
-    let c_str: &CStr = unsafe { CStr::from_ptr(msg.raw_symbol.as_ptr()) };
-    let raw_symbol: &str = c_str.to_str().map_err(|e| anyhow!(e))?;
+) -> Result<InstrumentId> {
+    let raw_symbol = fixed_bytes_to_str(&msg.raw_symbol)?;
+    let raw_symbol = decode_raw_symbol(
+        raw_symbol,
+        msg.hd.instrument_id,
+        EmptySymbolPolicy::default(),
+    )?;
 
-    let instrument_id = update_instrument_id_map(
+    Ok(update_instrument_id_map(
         msg.header(),
-        raw_symbol,
+        raw_symbol.as_str(),
         publisher_venue_map,
         glbx_exchange_map,
         instrument_id_map,
-    );
+    ))
+}
+
+fn handle_instrument_def_msg(
+    msg: &dbn::InstrumentDefMsg,
+    instrument_id: InstrumentId,
+    tick_size_monitor: &mut TickSizeMonitor,
+    precision_map: &mut PrecisionMap,
+    clock: &AtomicTime,
+    callback: &PyObject,
+) -> Result<()> {
+    if let Some(change) =
+        tick_size_monitor.update(instrument_id, msg.min_price_increment, msg.ts_recv)?
+    {
+        info!("{change:?}");
+    }
 
     let ts_init = clock.get_time_ns();
-    let result = decode_instrument_def_msg(msg, instrument_id, ts_init);
+    let result = decode_instrument_def_msg_with_precision_map(
+        msg,
+        instrument_id,
+        ts_init,
+        None,
+        precision_map,
+    );
 
     match result {
         Ok(instrument) => Python::with_gil(|py| {
@@ -394,7 +449,7 @@ fn handle_instrument_def_msg(
                 Err(e) => bail!(e),
             }
         }),
-        Err(e) => Err(e),
+        Err(e) => Err(e.into()),
     }
 }
 
@@ -404,8 +459,9 @@ fn handle_record(
     publisher_venue_map: &IndexMap<PublisherId, Venue>,
     glbx_exchange_map: &HashMap<Symbol, Venue>,
     instrument_id_map: &mut HashMap<u32, InstrumentId>,
+    precision_map: &PrecisionMap,
     clock: &AtomicTime,
-) -> Result<(Option<Data>, Option<Data>)> {
+) -> Result<DecodedRecord> {
     let raw_symbol = symbol_map
         .get_for_rec(&rec_ref)
         .expect("Cannot resolve `raw_symbol` from `symbol_map`");
@@ -418,20 +474,27 @@ fn handle_record(
         instrument_id_map,
     );
 
-    let price_precision = 2; // Hard coded for now
     let ts_init = clock.get_time_ns();
 
-    decode_record(
+    // Precisions are from the instrument definitions received so far
+    let result = decode_record_data(
         &rec_ref,
         instrument_id,
-        price_precision,
+        precision_map.get_price_precision(&instrument_id),
         Some(ts_init),
         true, // Always include trades
-    )
+        &precision_map.options_for(&instrument_id, &DecodeOptions::default()),
+    )?;
+
+    Ok(result)
 }
 
 fn call_python_with_data(py: Python, callback: &PyObject, data: Data) {
     let py_obj = data_to_pycapsule(py, data);
+    call_python(py, callback, py_obj);
+}
+
+fn call_python(py: Python, callback: &PyObject, py_obj: PyObject) {
     match callback.call1(py, (py_obj,)) {
         Ok(_) => {}
         Err(e) => eprintln!("Error on callback, {e:?}"), // Just print error for now
@@ -14,6 +14,7 @@
 // -------------------------------------------------------------------------------------------------
 
 pub mod decode;
+pub mod decoder;
 pub mod historical;
 pub mod live;
 pub mod loader;
@@ -24,7 +25,12 @@ use pyo3::prelude::*;
 #[pymodule]
 pub fn databento(_: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<super::types::DatabentoPublisher>()?;
+    m.add_class::<super::types::DatabentoStatisticType>()?;
+    m.add_class::<super::types::DatabentoStatistics>()?;
+    m.add_class::<super::types::DatabentoImbalance>()?;
+    m.add_class::<super::types::DatabentoInstrumentStatus>()?;
     m.add_class::<super::loader::DatabentoDataLoader>()?;
+    m.add_class::<super::decoder::DatabentoDecoder>()?;
     m.add_class::<live::DatabentoLiveClient>()?;
     m.add_class::<historical::DatabentoHistoricalClient>()?;
     m.add_function(wrap_pyfunction!(decode::py_decode_equity, m)?)?;
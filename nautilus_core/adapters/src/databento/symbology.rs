@@ -15,13 +15,18 @@
 
 use std::collections::HashMap;
 
-use anyhow::{bail, Result};
-use databento::dbn::Record;
+use anyhow::{anyhow, bail, Result};
+use chrono::NaiveDate;
+use databento::dbn::{PitSymbolMap, Record};
 use indexmap::IndexMap;
-use nautilus_model::identifiers::{instrument_id::InstrumentId, symbol::Symbol, venue::Venue};
+use nautilus_model::{
+    enums::OptionKind,
+    identifiers::{instrument_id::InstrumentId, symbol::Symbol, venue::Venue},
+    types::{fixed::FIXED_PRECISION, price::Price},
+};
 use ustr::Ustr;
 
-use super::types::PublisherId;
+use super::{decode::get_checked, types::PublisherId};
 
 pub fn decode_nautilus_instrument_id(
     rec_ref: &dbn::RecordRef,
@@ -32,19 +37,19 @@ pub fn decode_nautilus_instrument_id(
 ) -> Result<InstrumentId> {
     let (instrument_id, nanoseconds) = match rec_ref.rtype()? {
         dbn::RType::Mbo => {
-            let msg = rec_ref.get::<dbn::MboMsg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::MboMsg>(rec_ref)?;
             (msg.hd.instrument_id, msg.ts_recv)
         }
         dbn::RType::Mbp0 => {
-            let msg = rec_ref.get::<dbn::TradeMsg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::TradeMsg>(rec_ref)?;
             (msg.hd.instrument_id, msg.ts_recv)
         }
         dbn::RType::Mbp1 => {
-            let msg = rec_ref.get::<dbn::Mbp1Msg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::Mbp1Msg>(rec_ref)?;
             (msg.hd.instrument_id, msg.ts_recv)
         }
         dbn::RType::Mbp10 => {
-            let msg = rec_ref.get::<dbn::Mbp10Msg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::Mbp10Msg>(rec_ref)?;
             (msg.hd.instrument_id, msg.ts_recv)
         }
         dbn::RType::Ohlcv1S
@@ -52,7 +57,7 @@ pub fn decode_nautilus_instrument_id(
         | dbn::RType::Ohlcv1H
         | dbn::RType::Ohlcv1D
         | dbn::RType::OhlcvEod => {
-            let msg = rec_ref.get::<dbn::OhlcvMsg>().unwrap(); // SAFETY: RType known
+            let msg = get_checked::<dbn::OhlcvMsg>(rec_ref)?;
             (msg.hd.instrument_id, msg.hd.ts_event)
         }
         _ => bail!("RType is currently unsupported by NautilusTrader"),
@@ -81,3 +86,295 @@ pub fn decode_nautilus_instrument_id(
 
     Ok(InstrumentId::new(symbol, *venue))
 }
+
+/// Returns the Nautilus venue for the given Databento `publisher_id`, or `None` if
+/// the publisher is unknown.
+///
+/// Consolidated datasets (such as OPRA and DBEQ) have a publisher per venue, so
+/// records are routed to the venue they originated from.
+#[must_use]
+pub fn venue_from_publisher_id(publisher_id: PublisherId) -> Option<Venue> {
+    let venue = match publisher_id {
+        1 => "GLBX",
+        2 | 46 | 47 | 52 => "XNAS",
+        3 => "XBOS",
+        4 => "XPSX",
+        5 => "BATS",
+        6 => "BATY",
+        7 => "EDGA",
+        8 => "EDGX",
+        9 | 44 | 45 | 53 => "XNYS",
+        10 | 13 | 14 | 40 | 49 => "XCIS",
+        11 => "XASE",
+        12 | 39 | 48 => "XCHI",
+        15 => "MEMX",
+        16 | 42 | 51 => "EPRL",
+        17 | 54 => "FINN",
+        18 | 56 => "FINC",
+        19 | 55 => "FINY",
+        20 => "AMXO",
+        21 => "XBOX",
+        22 => "XCBO",
+        23 => "EMLD",
+        24 => "EDGO",
+        25 => "GMNI",
+        26 => "XISX",
+        27 => "MCRY",
+        28 => "XMIO",
+        29 => "ARCO",
+        30 => "OPRA",
+        31 => "MPRL",
+        32 => "XNDQ",
+        33 => "XBXO",
+        34 => "C2OX",
+        35 => "XPHL",
+        36 => "BATO",
+        37 => "MXOP",
+        38 | 41 | 50 => "IEXG",
+        43 => "ARCX",
+        57 => "IFEU",
+        58 => "NDEX",
+        59 | 60 => "DBEQ",
+        61 => "SPHR",
+        _ => return None,
+    };
+    Some(Venue::from_str_unchecked(venue))
+}
+
+/// The length of an OPRA packed symbol, with the root padded to six characters
+/// followed by the expiry, call/put and strike.
+const OPRA_SYMBOL_LEN: usize = 21;
+
+/// The components of an OPRA packed symbol (such as `SPY   240119P00340000`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpraSymbol {
+    /// The root symbol of the underlying.
+    pub root: Ustr,
+    /// The expiration date.
+    pub expiry: NaiveDate,
+    /// The option kind (call or put).
+    pub option_kind: OptionKind,
+    /// The strike price, with a precision of 3.
+    pub strike_price: Price,
+}
+
+impl OpraSymbol {
+    /// Returns the canonical Nautilus options symbol, being the packed symbol with
+    /// the root padding removed (such as `SPY240119P00340000`).
+    #[must_use]
+    pub fn to_symbol(&self) -> Symbol {
+        let kind = match self.option_kind {
+            OptionKind::Call => 'C',
+            OptionKind::Put => 'P',
+        };
+        let strike = self.strike_price.raw / 10i64.pow(u32::from(FIXED_PRECISION - 3));
+        Symbol::from(
+            format!(
+                "{}{}{kind}{strike:08}",
+                self.root,
+                self.expiry.format("%y%m%d"),
+            )
+            .as_str(),
+        )
+    }
+}
+
+/// Parses the OPRA packed `raw_symbol` into its components.
+///
+/// The symbol has a fixed layout of a six character (space padded) root, the expiry
+/// as `YYMMDD`, `C` or `P` for the option kind, and the strike in thousandths.
+pub fn parse_opra_symbol(raw_symbol: &str) -> Result<OpraSymbol> {
+    if raw_symbol.len() != OPRA_SYMBOL_LEN || !raw_symbol.is_ascii() {
+        bail!("Invalid OPRA symbol '{raw_symbol}'");
+    }
+
+    let root = raw_symbol[..6].trim_end();
+    if root.is_empty() {
+        bail!("Empty root in OPRA symbol '{raw_symbol}'");
+    }
+    let expiry = NaiveDate::parse_from_str(&raw_symbol[6..12], "%y%m%d")
+        .map_err(|e| anyhow!("Invalid expiry in OPRA symbol '{raw_symbol}': {e}"))?;
+    let option_kind = match &raw_symbol[12..13] {
+        "C" => OptionKind::Call,
+        "P" => OptionKind::Put,
+        c => bail!("Invalid option kind '{c}' in OPRA symbol '{raw_symbol}'"),
+    };
+    let strike: u32 = raw_symbol[13..]
+        .parse()
+        .map_err(|e| anyhow!("Invalid strike in OPRA symbol '{raw_symbol}': {e}"))?;
+
+    Ok(OpraSymbol {
+        root: Ustr::from(root),
+        expiry,
+        option_kind,
+        strike_price: Price::new(f64::from(strike) / 1_000.0, 3)?,
+    })
+}
+
+/// Provides a point-in-time map from DBN instrument IDs to Nautilus instrument IDs,
+/// built from the `SymbolMappingMsg` records of a session.
+pub struct DatabentoSymbolMap {
+    symbol_map: PitSymbolMap,
+    publisher_venue_map: IndexMap<PublisherId, Venue>,
+}
+
+impl DatabentoSymbolMap {
+    /// Creates a new map, with venues resolved from `publisher_venue_map` before
+    /// falling back to [`venue_from_publisher_id`].
+    #[must_use]
+    pub fn new(publisher_venue_map: IndexMap<PublisherId, Venue>) -> Self {
+        Self {
+            symbol_map: PitSymbolMap::new(),
+            publisher_venue_map,
+        }
+    }
+
+    /// Updates the map with the raw symbol of the given symbol mapping message.
+    pub fn on_symbol_mapping(&mut self, msg: &dbn::SymbolMappingMsg) -> Result<()> {
+        self.symbol_map.on_symbol_mapping(msg)?;
+        Ok(())
+    }
+
+    /// Returns the Nautilus instrument ID for the given DBN `instrument_id` and `publisher_id`.
+    pub fn get_instrument_id(
+        &self,
+        instrument_id: u32,
+        publisher_id: PublisherId,
+    ) -> Result<InstrumentId> {
+        let raw_symbol = self
+            .symbol_map
+            .get(instrument_id)
+            .ok_or_else(|| anyhow!("No raw symbol mapped for instrument ID {instrument_id}"))?;
+        let venue = self
+            .publisher_venue_map
+            .get(&publisher_id)
+            .copied()
+            .or_else(|| venue_from_publisher_id(publisher_id))
+            .ok_or_else(|| anyhow!("No venue found for `publisher_id` {publisher_id}"))?;
+
+        let symbol = Symbol {
+            value: Ustr::from(raw_symbol),
+        };
+
+        Ok(InstrumentId::new(symbol, venue))
+    }
+
+    /// Returns the Nautilus instrument ID for the given record.
+    pub fn get_instrument_id_for_record(&self, rec_ref: &dbn::RecordRef) -> Result<InstrumentId> {
+        let header = rec_ref.header();
+        self.get_instrument_id(header.instrument_id, header.publisher_id)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(1, Some("GLBX"))]
+    #[case(2, Some("XNAS"))]
+    #[case(9, Some("XNYS"))]
+    #[case(22, Some("XCBO"))]
+    #[case(30, Some("OPRA"))]
+    #[case(38, Some("IEXG"))]
+    #[case(59, Some("DBEQ"))]
+    #[case(0, None)]
+    #[case(9_999, None)]
+    fn test_venue_from_publisher_id(
+        #[case] publisher_id: PublisherId,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            venue_from_publisher_id(publisher_id),
+            expected.map(Venue::from)
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "SPY   240119P00340000",
+        "SPY",
+        "2024-01-19",
+        OptionKind::Put,
+        "340.000"
+    )]
+    #[case(
+        "AAPL  241220C00195500",
+        "AAPL",
+        "2024-12-20",
+        OptionKind::Call,
+        "195.500"
+    )]
+    #[case(
+        "SPXW  240315C05125000",
+        "SPXW",
+        "2024-03-15",
+        OptionKind::Call,
+        "5125.000"
+    )]
+    #[case(
+        "BRKB1 250117P00000500",
+        "BRKB1",
+        "2025-01-17",
+        OptionKind::Put,
+        "0.500"
+    )]
+    fn test_parse_opra_symbol(
+        #[case] raw_symbol: &str,
+        #[case] root: &str,
+        #[case] expiry: &str,
+        #[case] option_kind: OptionKind,
+        #[case] strike_price: &str,
+    ) {
+        let symbol = parse_opra_symbol(raw_symbol).unwrap();
+
+        assert_eq!(symbol.root, Ustr::from(root));
+        assert_eq!(symbol.expiry, NaiveDate::from_str(expiry).unwrap());
+        assert_eq!(symbol.option_kind, option_kind);
+        assert_eq!(symbol.strike_price, Price::from(strike_price));
+        assert_eq!(
+            symbol.to_symbol(),
+            Symbol::from(raw_symbol.replace(' ', "").as_str())
+        );
+    }
+
+    #[rstest]
+    #[case("SPY")]
+    #[case("      240119P00340000")]
+    #[case("SPY   241319P00340000")]
+    #[case("SPY   240119X00340000")]
+    #[case("SPY   240119P0034000A")]
+    fn test_parse_opra_symbol_invalid(#[case] raw_symbol: &str) {
+        assert!(parse_opra_symbol(raw_symbol).is_err());
+    }
+
+    #[rstest]
+    fn test_symbol_map_falls_back_to_publisher_venue() {
+        let mut symbol_map = DatabentoSymbolMap::new(IndexMap::new());
+        let mapping = dbn::SymbolMappingMsg::new(
+            5_482,
+            0,
+            dbn::SType::RawSymbol,
+            "SPY   240119P00340000",
+            dbn::SType::RawSymbol,
+            "SPY   240119P00340000",
+            0,
+            dbn::UNDEF_TIMESTAMP,
+        )
+        .unwrap();
+        symbol_map.on_symbol_mapping(&mapping).unwrap();
+
+        // Consolidated OPRA levels route to the originating venue
+        let instrument_id = symbol_map.get_instrument_id(5_482, 22).unwrap();
+
+        assert_eq!(instrument_id.venue, Venue::from("XCBO"));
+        assert!(symbol_map.get_instrument_id(5_482, 9_999).is_err());
+    }
+}
@@ -13,6 +13,15 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
+use std::ffi::c_char;
+
+use nautilus_core::time::UnixNanos;
+use nautilus_model::{
+    data::{delta::OrderBookDelta, Data},
+    enums::{BookAction, HaltReason, MarketStatus, OrderSide},
+    identifiers::instrument_id::InstrumentId,
+    types::{price::Price, quantity::Quantity},
+};
 use pyo3::prelude::*;
 use serde::Deserialize;
 use ustr::Ustr;
@@ -34,3 +43,241 @@ pub struct DatabentoPublisher {
     pub venue: dbn::Venue,
     pub description: String,
 }
+
+/// Represents a Databento statistic type mapped by the adapter.
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "nautilus_trader.core.nautilus_pyo3.databento")
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DatabentoStatisticType {
+    /// The official opening price of the trading session, used as a reference price.
+    OpeningPrice,
+    SettlementPrice,
+    TradingSessionLowPrice,
+    TradingSessionHighPrice,
+    OpenInterest,
+    /// The official closing price of the trading session, used as a reference price.
+    ClosePrice,
+}
+
+/// Represents a Databento statistic for an instrument, such as a settlement price
+/// or the open interest.
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "nautilus_trader.core.nautilus_pyo3.databento", get_all)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DatabentoStatistics {
+    pub instrument_id: InstrumentId,
+    pub stat_type: DatabentoStatisticType,
+    /// The statistic price, `None` if the statistic is not a price.
+    pub price: Option<Price>,
+    /// The statistic quantity, `None` if the statistic is not a quantity.
+    pub quantity: Option<Quantity>,
+    pub sequence: u32,
+    /// The reference timestamp of the statistic (e.g. the trading session date).
+    pub ts_ref: UnixNanos,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+/// Represents a Databento auction imbalance for an instrument.
+///
+/// Prices are `None` where the venue reports none (the DBN `UNDEF_PRICE` sentinel).
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "nautilus_trader.core.nautilus_pyo3.databento", get_all)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DatabentoImbalance {
+    pub instrument_id: InstrumentId,
+    /// The price at which the imbalance shares are calculated.
+    pub ref_price: Option<Price>,
+    /// The hypothetical auction-clearing price for both cross and continuous orders.
+    pub cont_book_clr_price: Option<Price>,
+    /// The hypothetical auction-clearing price for cross orders only.
+    pub auct_interest_clr_price: Option<Price>,
+    /// The quantity of shares which are eligible to be matched at `ref_price`.
+    pub paired_qty: Quantity,
+    /// The quantity of shares which are not paired at `ref_price`.
+    pub total_imbalance_qty: Quantity,
+    /// The side of the imbalance.
+    pub side: OrderSide,
+    /// The venue-specific auction type code.
+    pub auction_type: c_char,
+    /// The venue-specific auction status code.
+    pub auction_status: u8,
+    /// The venue-specific significant imbalance indicator.
+    pub significant_imbalance: c_char,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+/// Represents a Databento trading status update for an instrument.
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "nautilus_trader.core.nautilus_pyo3.databento", get_all)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DatabentoInstrumentStatus {
+    pub instrument_id: InstrumentId,
+    pub status: MarketStatus,
+    pub halt_reason: HaltReason,
+    /// The venue-specific trading event code.
+    pub trading_event: u8,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+/// Represents a DBN book action, distinguishing a fill from a modify which both map
+/// to the coarser `BookAction::Update`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DatabentoBookAction {
+    /// An order was added to the book.
+    Add,
+    /// An order was cancelled (fully or partially).
+    Cancel,
+    /// An order was (partially) filled, reducing its size.
+    Fill,
+    /// An order was modified (price and/or size).
+    Modify,
+    /// The book was cleared.
+    Clear,
+}
+
+impl From<DatabentoBookAction> for BookAction {
+    fn from(value: DatabentoBookAction) -> Self {
+        match value {
+            DatabentoBookAction::Add => Self::Add,
+            DatabentoBookAction::Cancel => Self::Delete,
+            DatabentoBookAction::Fill | DatabentoBookAction::Modify => Self::Update,
+            DatabentoBookAction::Clear => Self::Clear,
+        }
+    }
+}
+
+/// Represents an order book delta decoded from a Databento MBO record, with the
+/// original DBN `action` char preserved alongside the mapped `BookAction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DatabentoOrderBookDelta {
+    pub delta: OrderBookDelta,
+    /// The raw DBN action char ('A' add, 'C' cancel, 'F' fill, 'M' modify, 'R' clear).
+    pub raw_action: c_char,
+    /// The DBN book action, distinguishing fill-driven size changes from modifies.
+    pub action: DatabentoBookAction,
+}
+
+/// Represents an aggregated price level of a book built from MBO deltas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DatabentoBookLevel {
+    pub price: Price,
+    /// The total size of the orders at the level.
+    pub size: Quantity,
+    /// The number of orders at the level.
+    pub count: u32,
+}
+
+/// Represents the top levels of a book deeper than `OrderBookDepth10` (up to
+/// `MAX_BOOK_DEPTH` levels per side), built from MBO deltas.
+///
+/// Unlike `OrderBookDepth10`, a side with fewer levels than requested is not padded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatabentoOrderBookDepth {
+    pub instrument_id: InstrumentId,
+    /// The bid levels, from the best (highest) price.
+    pub bids: Vec<DatabentoBookLevel>,
+    /// The ask levels, from the best (lowest) price.
+    pub asks: Vec<DatabentoBookLevel>,
+    pub sequence: u64,
+    pub ts_event: UnixNanos,
+}
+
+/// Represents Nautilus data decoded from a Databento record, with the numeric DBN
+/// `instrument_id` preserved for reconciling against the raw feed.
+#[derive(Clone, Debug)]
+pub struct DatabentoData {
+    pub data: Data,
+    /// The numeric DBN `instrument_id` from the record header.
+    pub raw_instrument_id: u32,
+}
+
+/// Represents the latency of a Databento record from the exchange to the Databento
+/// gateway, derived from the DBN `ts_in_delta`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DatabentoLatency {
+    /// The exchange send timestamp, as `ts_recv` less `ts_in_delta`.
+    pub ts_exchange: UnixNanos,
+    /// The Databento gateway receive timestamp.
+    pub ts_recv: UnixNanos,
+    /// The nanoseconds between the exchange sending and the gateway receiving the record.
+    pub ts_in_delta: i32,
+}
+
+/// Represents the DBN record `flags` bitset.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RecordFlags(u8);
+
+impl RecordFlags {
+    /// Marks the last record in a single event for a given `instrument_id`.
+    pub const F_LAST: u8 = 1 << 7;
+    /// Indicates a top-of-book message, not an individual order.
+    pub const F_TOB: u8 = 1 << 6;
+    /// Indicates the message was sourced from a replay, such as a snapshot server.
+    pub const F_SNAPSHOT: u8 = 1 << 5;
+    /// Indicates an aggregated price level message, not an individual order.
+    pub const F_MBP: u8 = 1 << 4;
+    /// Indicates the `ts_recv` value is inaccurate due to clock issues or packet reordering.
+    pub const F_BAD_TS_RECV: u8 = 1 << 3;
+    /// Indicates an unrecoverable gap was detected in the channel.
+    pub const F_MAYBE_BAD_BOOK: u8 = 1 << 2;
+
+    #[must_use]
+    pub const fn new(raw: u8) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw flags bitset.
+    #[must_use]
+    pub const fn raw(self) -> u8 {
+        self.0
+    }
+
+    /// Returns whether all of the given `flags` bits are set.
+    #[must_use]
+    pub const fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns whether the record is the last in its event, so book updates up to and
+    /// including it can be applied together.
+    #[must_use]
+    pub const fn is_last(self) -> bool {
+        self.contains(Self::F_LAST)
+    }
+
+    #[must_use]
+    pub const fn is_tob(self) -> bool {
+        self.contains(Self::F_TOB)
+    }
+
+    #[must_use]
+    pub const fn is_snapshot(self) -> bool {
+        self.contains(Self::F_SNAPSHOT)
+    }
+
+    #[must_use]
+    pub const fn is_mbp(self) -> bool {
+        self.contains(Self::F_MBP)
+    }
+
+    #[must_use]
+    pub const fn is_bad_ts_recv(self) -> bool {
+        self.contains(Self::F_BAD_TS_RECV)
+    }
+
+    #[must_use]
+    pub const fn is_maybe_bad_book(self) -> bool {
+        self.contains(Self::F_MAYBE_BAD_BOOK)
+    }
+}
@@ -490,6 +490,41 @@ pub enum CurrencyType {
     CommodityBacked = 3,
 }
 
+/// The style of exercise for an options contract.
+#[repr(C)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Display,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    AsRefStr,
+    FromRepr,
+    EnumIter,
+    EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(module = "nautilus_trader.core.nautilus_pyo3.model.enums")
+)]
+pub enum ExerciseStyle {
+    /// The option can be exercised on any trading day up to and including expiration.
+    #[pyo3(name = "AMERICAN")]
+    American = 1,
+    /// The option can only be exercised at expiration.
+    #[pyo3(name = "EUROPEAN")]
+    European = 2,
+    /// The option can be exercised on specified dates up to and including expiration.
+    #[pyo3(name = "BERMUDAN")]
+    Bermudan = 3,
+}
+
 /// The type of event for an instrument close.
 #[repr(C)]
 #[derive(
@@ -1158,6 +1193,7 @@ enum_strum_serde!(BookAction);
 enum_strum_serde!(BookType);
 enum_strum_serde!(ContingencyType);
 enum_strum_serde!(CurrencyType);
+enum_strum_serde!(ExerciseStyle);
 enum_strum_serde!(InstrumentCloseType);
 enum_strum_serde!(LiquiditySide);
 enum_strum_serde!(MarketStatus);
@@ -1182,6 +1218,7 @@ enum_for_python!(BookAction);
 enum_for_python!(BookType);
 enum_for_python!(ContingencyType);
 enum_for_python!(CurrencyType);
+enum_for_python!(ExerciseStyle);
 enum_for_python!(InstrumentCloseType);
 enum_for_python!(LiquiditySide);
 enum_for_python!(MarketStatus);
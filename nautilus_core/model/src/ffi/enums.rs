@@ -19,9 +19,9 @@ use nautilus_core::ffi::string::{cstr_to_str, str_to_cstr};
 
 use crate::enums::{
     AccountType, AggregationSource, AggressorSide, AssetClass, BarAggregation, BookAction,
-    BookType, ContingencyType, CurrencyType, HaltReason, InstrumentClass, InstrumentCloseType,
-    LiquiditySide, MarketStatus, OmsType, OptionKind, OrderSide, OrderStatus, OrderType,
-    PositionSide, PriceType, TimeInForce, TradingState, TrailingOffsetType, TriggerType,
+    BookType, ContingencyType, CurrencyType, ExerciseStyle, HaltReason, InstrumentClass,
+    InstrumentCloseType, LiquiditySide, MarketStatus, OmsType, OptionKind, OrderSide, OrderStatus,
+    OrderType, PositionSide, PriceType, TimeInForce, TradingState, TrailingOffsetType, TriggerType,
 };
 
 #[no_mangle]
@@ -194,6 +194,23 @@ pub unsafe extern "C" fn currency_type_from_cstr(ptr: *const c_char) -> Currency
         .unwrap_or_else(|_| panic!("invalid `CurrencyType` enum string value, was '{value}'"))
 }
 
+#[no_mangle]
+pub extern "C" fn exercise_style_to_cstr(value: ExerciseStyle) -> *const c_char {
+    str_to_cstr(value.as_ref())
+}
+
+/// Returns an enum from a Python string.
+///
+/// # Safety
+///
+/// - Assumes `ptr` is a valid C string pointer.
+#[no_mangle]
+pub unsafe extern "C" fn exercise_style_from_cstr(ptr: *const c_char) -> ExerciseStyle {
+    let value = cstr_to_str(ptr);
+    ExerciseStyle::from_str(value)
+        .unwrap_or_else(|_| panic!("invalid `ExerciseStyle` enum string value, was '{value}'"))
+}
+
 /// Returns an enum from a Python string.
 ///
 /// # Safety
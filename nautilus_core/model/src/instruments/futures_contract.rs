@@ -46,6 +46,7 @@ pub struct FuturesContract {
     pub activation_ns: UnixNanos,
     pub expiration_ns: UnixNanos,
     pub currency: Currency,
+    pub settlement_currency: Currency,
     pub price_precision: u8,
     pub price_increment: Price,
     pub multiplier: Quantity,
@@ -68,6 +69,7 @@ impl FuturesContract {
         activation_ns: UnixNanos,
         expiration_ns: UnixNanos,
         currency: Currency,
+        settlement_currency: Option<Currency>,
         price_precision: u8,
         price_increment: Price,
         multiplier: Quantity,
@@ -87,6 +89,7 @@ impl FuturesContract {
             activation_ns,
             expiration_ns,
             currency,
+            settlement_currency: settlement_currency.unwrap_or(currency),
             price_precision,
             price_increment,
             multiplier,
@@ -141,7 +144,7 @@ impl Instrument for FuturesContract {
     }
 
     fn settlement_currency(&self) -> Currency {
-        self.currency
+        self.settlement_currency
     }
 
     fn is_inverse(&self) -> bool {
@@ -0,0 +1,197 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{
+    any::Any,
+    hash::{Hash, Hasher},
+};
+
+use anyhow::Result;
+use nautilus_core::time::UnixNanos;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::Instrument;
+use crate::{
+    enums::{AssetClass, InstrumentClass},
+    identifiers::{instrument_id::InstrumentId, symbol::Symbol},
+    types::{currency::Currency, price::Price, quantity::Quantity},
+};
+
+/// Represents a market index (such as the S&P 500), which is quoted but not traded,
+/// so has no lot size or quantity limits.
+#[repr(C)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "nautilus_trader.core.nautilus_pyo3.model")
+)]
+#[cfg_attr(feature = "trivial_copy", derive(Copy))]
+pub struct IndexInstrument {
+    pub id: InstrumentId,
+    pub raw_symbol: Symbol,
+    pub currency: Currency,
+    pub price_precision: u8,
+    pub price_increment: Price,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+impl IndexInstrument {
+    pub fn new(
+        id: InstrumentId,
+        raw_symbol: Symbol,
+        currency: Currency,
+        price_precision: u8,
+        price_increment: Price,
+        ts_event: UnixNanos,
+        ts_init: UnixNanos,
+    ) -> Result<Self> {
+        Ok(Self {
+            id,
+            raw_symbol,
+            currency,
+            price_precision,
+            price_increment,
+            ts_event,
+            ts_init,
+        })
+    }
+}
+
+impl PartialEq<Self> for IndexInstrument {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for IndexInstrument {}
+
+impl Hash for IndexInstrument {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Instrument for IndexInstrument {
+    fn id(&self) -> InstrumentId {
+        self.id
+    }
+
+    fn raw_symbol(&self) -> Symbol {
+        self.raw_symbol
+    }
+
+    fn asset_class(&self) -> AssetClass {
+        AssetClass::Index
+    }
+
+    fn instrument_class(&self) -> InstrumentClass {
+        InstrumentClass::Spot
+    }
+
+    fn quote_currency(&self) -> Currency {
+        self.currency
+    }
+
+    fn base_currency(&self) -> Option<Currency> {
+        None
+    }
+
+    fn settlement_currency(&self) -> Currency {
+        self.currency
+    }
+
+    fn is_inverse(&self) -> bool {
+        false
+    }
+
+    fn price_precision(&self) -> u8 {
+        self.price_precision
+    }
+
+    fn size_precision(&self) -> u8 {
+        0
+    }
+
+    fn price_increment(&self) -> Price {
+        self.price_increment
+    }
+
+    fn size_increment(&self) -> Quantity {
+        Quantity::from(1)
+    }
+
+    fn multiplier(&self) -> Quantity {
+        Quantity::from(1)
+    }
+
+    fn lot_size(&self) -> Option<Quantity> {
+        None
+    }
+
+    fn max_quantity(&self) -> Option<Quantity> {
+        None
+    }
+
+    fn min_quantity(&self) -> Option<Quantity> {
+        None
+    }
+
+    fn max_price(&self) -> Option<Price> {
+        None
+    }
+
+    fn min_price(&self) -> Option<Price> {
+        None
+    }
+
+    fn ts_event(&self) -> UnixNanos {
+        self.ts_event
+    }
+
+    fn ts_init(&self) -> UnixNanos {
+        self.ts_init
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::{
+        enums::AssetClass,
+        instruments::{index::IndexInstrument, stubs::*, Instrument},
+    };
+
+    #[rstest]
+    fn test_equality(index_spx: IndexInstrument) {
+        let cloned = index_spx;
+        assert_eq!(index_spx, cloned);
+    }
+
+    #[rstest]
+    fn test_index_has_no_lot_size(index_spx: IndexInstrument) {
+        assert_eq!(index_spx.asset_class(), AssetClass::Index);
+        assert_eq!(index_spx.lot_size(), None);
+    }
+}
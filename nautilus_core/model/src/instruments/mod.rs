@@ -14,12 +14,14 @@
 // -------------------------------------------------------------------------------------------------
 
 use std::any::Any;
+pub mod bond;
 pub mod crypto_future;
 pub mod crypto_perpetual;
 pub mod currency_pair;
 pub mod equity;
 pub mod futures_contract;
 pub mod futures_spread;
+pub mod index;
 pub mod options_contract;
 pub mod options_spread;
 pub mod synthetic;
@@ -26,7 +26,7 @@ use ustr::Ustr;
 
 use super::Instrument;
 use crate::{
-    enums::{AssetClass, InstrumentClass, OptionKind},
+    enums::{AssetClass, ExerciseStyle, InstrumentClass, OptionKind},
     identifiers::{instrument_id::InstrumentId, symbol::Symbol},
     types::{currency::Currency, price::Price, quantity::Quantity},
 };
@@ -44,6 +44,8 @@ pub struct OptionsContract {
     pub asset_class: AssetClass,
     pub underlying: Ustr,
     pub option_kind: OptionKind,
+    /// The exercise style, `None` if unknown.
+    pub exercise_style: Option<ExerciseStyle>,
     pub activation_ns: UnixNanos,
     pub expiration_ns: UnixNanos,
     pub strike_price: Price,
@@ -68,6 +70,7 @@ impl OptionsContract {
         asset_class: AssetClass,
         underlying: Ustr,
         option_kind: OptionKind,
+        exercise_style: Option<ExerciseStyle>,
         activation_ns: UnixNanos,
         expiration_ns: UnixNanos,
         strike_price: Price,
@@ -89,6 +92,7 @@ impl OptionsContract {
             asset_class,
             underlying,
             option_kind,
+            exercise_style,
             activation_ns,
             expiration_ns,
             strike_price,
@@ -20,18 +20,66 @@ use rust_decimal_macros::dec;
 use ustr::Ustr;
 
 use crate::{
-    enums::{AssetClass, OptionKind},
+    enums::{AssetClass, ExerciseStyle, OptionKind},
     identifiers::{instrument_id::InstrumentId, symbol::Symbol, venue::Venue},
     instruments::{
-        crypto_future::CryptoFuture, crypto_perpetual::CryptoPerpetual,
+        bond::Bond, crypto_future::CryptoFuture, crypto_perpetual::CryptoPerpetual,
         currency_pair::CurrencyPair, equity::Equity, futures_contract::FuturesContract,
-        options_contract::OptionsContract,
+        index::IndexInstrument, options_contract::OptionsContract,
     },
     types::{currency::Currency, money::Money, price::Price, quantity::Quantity},
 };
 
 use super::{futures_spread::FuturesSpread, options_spread::OptionsSpread};
 
+////////////////////////////////////////////////////////////////////////////////
+// Bond
+////////////////////////////////////////////////////////////////////////////////
+
+#[fixture]
+pub fn bond_ust_10y() -> Bond {
+    let issue = Utc.with_ymd_and_hms(2023, 11, 15, 0, 0, 0).unwrap();
+    let maturity = Utc.with_ymd_and_hms(2033, 11, 15, 0, 0, 0).unwrap();
+    Bond::new(
+        InstrumentId::from("91282CJJ1.XNYS"),
+        Symbol::from("91282CJJ1"),
+        Some(Ustr::from("US91282CJJ18")),
+        Currency::USD(),
+        Some(dec!(4.5)),
+        Some(issue.timestamp_nanos_opt().unwrap() as UnixNanos),
+        maturity.timestamp_nanos_opt().unwrap() as UnixNanos,
+        Quantity::from(100),
+        4,
+        Price::from("0.0001"),
+        Some(Quantity::from(1)),
+        None,
+        None,
+        None,
+        None,
+        0,
+        0,
+    )
+    .unwrap()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// IndexInstrument
+////////////////////////////////////////////////////////////////////////////////
+
+#[fixture]
+pub fn index_spx() -> IndexInstrument {
+    IndexInstrument::new(
+        InstrumentId::from("SPX.XCBO"),
+        Symbol::from("SPX"),
+        Currency::USD(),
+        2,
+        Price::from("0.01"),
+        0,
+        0,
+    )
+    .unwrap()
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // CryptoFuture
 ////////////////////////////////////////////////////////////////////////////////
@@ -302,6 +350,7 @@ pub fn futures_contract_es() -> FuturesContract {
         activation.timestamp_nanos_opt().unwrap() as UnixNanos,
         expiration.timestamp_nanos_opt().unwrap() as UnixNanos,
         Currency::USD(),
+        None,
         2,
         Price::from("0.01"),
         Quantity::from(1),
@@ -361,6 +410,7 @@ pub fn options_contract_appl() -> OptionsContract {
         AssetClass::Equity,
         Ustr::from("AAPL"),
         OptionKind::Call,
+        Some(ExerciseStyle::American),
         activation.timestamp_nanos_opt().unwrap() as UnixNanos,
         expiration.timestamp_nanos_opt().unwrap() as UnixNanos,
         Price::from("149.0"),
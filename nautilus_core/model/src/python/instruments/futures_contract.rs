@@ -54,6 +54,7 @@ impl FuturesContract {
         min_quantity: Option<Quantity>,
         max_price: Option<Price>,
         min_price: Option<Price>,
+        settlement_currency: Option<Currency>,
     ) -> PyResult<Self> {
         Self::new(
             id,
@@ -63,6 +64,7 @@ impl FuturesContract {
             activation_ns,
             expiration_ns,
             currency,
+            settlement_currency,
             price_precision,
             price_increment,
             multiplier,
@@ -138,6 +140,12 @@ impl FuturesContract {
         self.currency
     }
 
+    #[getter]
+    #[pyo3(name = "settlement_currency")]
+    fn py_settlement_currency(&self) -> Currency {
+        self.settlement_currency
+    }
+
     #[getter]
     #[pyo3(name = "price_precision")]
     fn py_price_precision(&self) -> u8 {
@@ -215,6 +223,10 @@ impl FuturesContract {
         dict.set_item("activation_ns", self.activation_ns.to_u64())?;
         dict.set_item("expiration_ns", self.expiration_ns.to_u64())?;
         dict.set_item("currency", self.currency.code.to_string())?;
+        dict.set_item(
+            "settlement_currency",
+            self.settlement_currency.code.to_string(),
+        )?;
         dict.set_item("price_precision", self.price_precision)?;
         dict.set_item("price_increment", self.price_increment.to_string())?;
         dict.set_item("multiplier", self.multiplier.to_string())?;
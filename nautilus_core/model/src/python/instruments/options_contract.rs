@@ -26,7 +26,7 @@ use pyo3::{basic::CompareOp, prelude::*, types::PyDict};
 use rust_decimal::prelude::ToPrimitive;
 
 use crate::{
-    enums::{AssetClass, OptionKind},
+    enums::{AssetClass, ExerciseStyle, OptionKind},
     identifiers::{instrument_id::InstrumentId, symbol::Symbol},
     instruments::options_contract::OptionsContract,
     types::{currency::Currency, price::Price, quantity::Quantity},
@@ -56,6 +56,7 @@ impl OptionsContract {
         min_quantity: Option<Quantity>,
         max_price: Option<Price>,
         min_price: Option<Price>,
+        exercise_style: Option<ExerciseStyle>,
     ) -> PyResult<Self> {
         Self::new(
             id,
@@ -63,6 +64,7 @@ impl OptionsContract {
             asset_class,
             underlying.into(),
             option_kind,
+            exercise_style,
             activation_ns,
             expiration_ns,
             strike_price,
@@ -130,6 +132,12 @@ impl OptionsContract {
         self.option_kind
     }
 
+    #[getter]
+    #[pyo3(name = "exercise_style")]
+    fn py_exercise_style(&self) -> Option<ExerciseStyle> {
+        self.exercise_style
+    }
+
     #[getter]
     #[pyo3(name = "activation_ns")]
     fn py_activation_ns(&self) -> UnixNanos {
@@ -229,6 +237,10 @@ impl OptionsContract {
         dict.set_item("asset_class", self.asset_class.to_string())?;
         dict.set_item("underlying", self.underlying.to_string())?;
         dict.set_item("option_kind", self.option_kind.to_string())?;
+        dict.set_item(
+            "exercise_style",
+            self.exercise_style.map(|style| style.to_string()),
+        )?;
         dict.set_item("activation_ns", self.activation_ns.to_u64())?;
         dict.set_item("expiration_ns", self.expiration_ns.to_u64())?;
         dict.set_item("strike_price", self.strike_price.to_string())?;
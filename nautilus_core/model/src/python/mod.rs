@@ -53,6 +53,7 @@ pub fn model(_: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<enums::BookType>()?;
     m.add_class::<enums::ContingencyType>()?;
     m.add_class::<enums::CurrencyType>()?;
+    m.add_class::<enums::ExerciseStyle>()?;
     m.add_class::<enums::InstrumentCloseType>()?;
     m.add_class::<enums::LiquiditySide>()?;
     m.add_class::<enums::MarketStatus>()?;
@@ -669,6 +669,11 @@ class MarketStatus(Enum):
     PRE_CLOSE = "PRE_CLOSE"
     CLOSED = "CLOSED"
 
+class ExerciseStyle(Enum):
+    AMERICAN = "AMERICAN"
+    EUROPEAN = "EUROPEAN"
+    BERMUDAN = "BERMUDAN"
+
 class HaltReason(Enum):
     NOT_HALTED = "NOT_HALTED"
     GENERAL = "GENERAL"
@@ -1201,6 +1206,7 @@ class FuturesContract:
         min_quantity: Quantity | None = None,
         max_price: Price | None = None,
         min_price: Price | None = None,
+        settlement_currency: Currency | None = None,
     ) -> None: ...
     @property
     def id(self) -> InstrumentId: ...
@@ -1211,6 +1217,8 @@ class FuturesContract:
     @property
     def quote_currency(self) -> Currency: ...
     @property
+    def settlement_currency(self) -> Currency: ...
+    @property
     def price_precision(self) -> int: ...
     @property
     def size_precision(self) -> int: ...
@@ -1282,6 +1290,7 @@ class OptionsContract:
         min_quantity: Quantity | None = None,
         max_price: Price | None = None,
         min_price: Price | None = None,
+        exercise_style: ExerciseStyle | None = None,
     ) -> None : ...
     @property
     def id(self) -> InstrumentId: ...
@@ -2237,6 +2246,72 @@ class DatabentoPublisher:
     @property
     def description(self) -> str: ...
 
+class DatabentoStatisticType(Enum):
+    OpeningPrice = "OpeningPrice"
+    SettlementPrice = "SettlementPrice"
+    TradingSessionLowPrice = "TradingSessionLowPrice"
+    TradingSessionHighPrice = "TradingSessionHighPrice"
+    OpenInterest = "OpenInterest"
+    ClosePrice = "ClosePrice"
+
+class DatabentoStatistics:
+    @property
+    def instrument_id(self) -> InstrumentId: ...
+    @property
+    def stat_type(self) -> DatabentoStatisticType: ...
+    @property
+    def price(self) -> Price | None: ...
+    @property
+    def quantity(self) -> Quantity | None: ...
+    @property
+    def sequence(self) -> int: ...
+    @property
+    def ts_ref(self) -> int: ...
+    @property
+    def ts_event(self) -> int: ...
+    @property
+    def ts_init(self) -> int: ...
+
+class DatabentoImbalance:
+    @property
+    def instrument_id(self) -> InstrumentId: ...
+    @property
+    def ref_price(self) -> Price | None: ...
+    @property
+    def cont_book_clr_price(self) -> Price | None: ...
+    @property
+    def auct_interest_clr_price(self) -> Price | None: ...
+    @property
+    def paired_qty(self) -> Quantity: ...
+    @property
+    def total_imbalance_qty(self) -> Quantity: ...
+    @property
+    def side(self) -> OrderSide: ...
+    @property
+    def auction_type(self) -> int: ...
+    @property
+    def auction_status(self) -> int: ...
+    @property
+    def significant_imbalance(self) -> int: ...
+    @property
+    def ts_event(self) -> int: ...
+    @property
+    def ts_init(self) -> int: ...
+
+class DatabentoInstrumentStatus:
+    @property
+    def instrument_id(self) -> InstrumentId: ...
+    @property
+    def status(self) -> MarketStatus: ...
+    @property
+    def halt_reason(self) -> HaltReason: ...
+    @property
+    def trading_event(self) -> int: ...
+    @property
+    def ts_event(self) -> int: ...
+    @property
+    def ts_init(self) -> int: ...
+
 class DatabentoDataLoader:
     def __init__(
         self,
@@ -2260,6 +2335,16 @@ class DatabentoDataLoader:
     def load_bars(self, path: str, instrument_id: InstrumentId | None) -> list[Bar]: ...
     def load_bars_as_pycapsule(self, path: str, instrument_id: InstrumentId | None) -> object: ...
 
+class DatabentoDecoder:
+    def __init__(
+        self,
+        price_precision: int | None = None,
+        include_trades: bool | None = None,
+    ) -> None: ...
+    def add_symbol_mapping(self, instrument_id: int, raw_symbol: str) -> None: ...
+    def decode_record_bytes(self, data: bytes) -> list[Data | DatabentoStatistics | DatabentoImbalance | DatabentoInstrumentStatus]: ...
+    def decode_instrument_def_bytes(self, data: bytes) -> Instrument: ...
+
 class DatabentoHistoricalClient:
     def __init__(
         self,
@@ -0,0 +1,60 @@
+# -------------------------------------------------------------------------------------------------
+#  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+#  https://nautechsystems.io
+#
+#  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+#  You may not use this file except in compliance with the License.
+#  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+#
+#  Unless required by applicable law or agreed to in writing, software
+#  distributed under the License is distributed on an "AS IS" BASIS,
+#  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+#  See the License for the specific language governing permissions and
+#  limitations under the License.
+# -------------------------------------------------------------------------------------------------
+
+import struct
+
+from nautilus_trader.core import nautilus_pyo3
+
+
+def _trade_msg_bytes(instrument_id: int, ts_event: int) -> bytes:
+    # DBN `TradeMsg` layout: record header, price, size, action, side, flags, depth,
+    # ts_recv, ts_in_delta, sequence (48 bytes, so the header length is 12 words)
+    return struct.pack(
+        "<BBHIQqIccBBQiI",
+        12,  # length
+        0x00,  # rtype (MBP_0)
+        1,  # publisher_id
+        instrument_id,
+        ts_event,
+        3_720_250_000_000,  # price
+        5,  # size
+        b"T",  # action
+        b"A",  # side
+        0,  # flags
+        0,  # depth
+        ts_event,  # ts_recv
+        0,  # ts_in_delta
+        1170380,  # sequence
+    )
+
+
+def test_decode_record_bytes_trade() -> None:
+    # Arrange
+    decoder = nautilus_pyo3.DatabentoDecoder()
+    decoder.add_symbol_mapping(5482, "ESH1")
+    data = _trade_msg_bytes(5482, 1609160400099150057)
+
+    # Act
+    records = decoder.decode_record_bytes(data)
+
+    # Assert
+    assert len(records) == 1
+    trade = records[0]
+    assert isinstance(trade, nautilus_pyo3.TradeTick)
+    assert trade.instrument_id == nautilus_pyo3.InstrumentId.from_str("ESH1.GLBX")
+    assert trade.price == nautilus_pyo3.Price.from_str("3720.25")
+    assert trade.size == nautilus_pyo3.Quantity.from_int(5)
+    assert trade.aggressor_side == nautilus_pyo3.AggressorSide.SELLER
+    assert trade.ts_event == 1609160400099150057